@@ -0,0 +1,85 @@
+extern crate nalgebra as na;
+extern crate ncollide2d;
+extern crate nphysics2d;
+extern crate nphysics_testbed2d;
+
+use na::{Point2, Point3, Vector2};
+use ncollide2d::bounding_volume::AABB;
+use ncollide2d::shape::{Cuboid, ShapeHandle};
+use nphysics2d::object::{ColliderDesc, RigidBodyDesc};
+use nphysics2d::world::{FluidVolume, World};
+use nphysics_testbed2d::Testbed;
+
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vector2::new(0.0, -9.81));
+
+    /*
+     * Tank: a floor and two walls.
+     */
+    let tank_width = 8.0;
+    let tank_height = 4.0;
+    let thickness = 0.2;
+
+    ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector2::new(tank_width + thickness, thickness))))
+        .translation(-Vector2::y() * thickness)
+        .build(&mut world);
+
+    let wall = ShapeHandle::new(Cuboid::new(Vector2::new(thickness, tank_height)));
+    let mut wall_desc = ColliderDesc::new(wall);
+
+    for x in &[-tank_width, tank_width] {
+        wall_desc
+            .set_translation(Vector2::new(*x, tank_height))
+            .build(&mut world);
+    }
+
+    /*
+     * Water filling the bottom of the tank.
+     */
+    let water_level = 3.0;
+    let water = AABB::new(Point2::new(-tank_width, 0.0), Point2::new(tank_width, water_level));
+    let mut fluid = FluidVolume::new(water, 1.0);
+    fluid.set_linear_drag(2.0);
+    fluid.set_angular_drag(0.5);
+    world.add_fluid_volume(fluid);
+
+    // A sensor is used to display the water. It is ignored by the fluid volume.
+    let water_shape = ShapeHandle::new(Cuboid::new(Vector2::new(tank_width, water_level / 2.0)));
+    let water_collider = ColliderDesc::new(water_shape)
+        .translation(Vector2::y() * (water_level / 2.0))
+        .sensor(true)
+        .build(&mut world)
+        .handle();
+
+    /*
+     * Boxes of various densities dropped into the water. The ones denser than the water sink.
+     */
+    let num = 7;
+    let rad = 0.4;
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector2::repeat(rad)));
+
+    for i in 0usize..num {
+        let density = 0.25 + i as f32 * 0.2;
+        let collider_desc = ColliderDesc::new(cuboid.clone()).density(density);
+        let x = (i as f32 - (num - 1) as f32 / 2.0) * 2.0;
+
+        RigidBodyDesc::new()
+            .collider(&collider_desc)
+            .translation(Vector2::new(x, water_level + 2.0 + i as f32 * 0.5))
+            .rotation(i as f32 * 0.3)
+            .build(&mut world);
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+    testbed.set_collider_color(water_collider, Point3::new(0.5, 0.7, 1.0));
+    testbed.look_at(Point2::new(0.0, 3.0), 60.0);
+    testbed.run();
+}
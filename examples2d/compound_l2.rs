@@ -0,0 +1,80 @@
+extern crate nalgebra as na;
+extern crate ncollide2d;
+extern crate nphysics2d;
+extern crate nphysics_testbed2d;
+
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::shape::{Compound, Cuboid, Polyline, ShapeHandle};
+use nphysics2d::object::{ColliderDesc, RigidBodyDesc};
+use nphysics2d::world::World;
+use nphysics_testbed2d::Testbed;
+use std::f32::consts::FRAC_PI_2;
+
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vector2::new(0.0, -9.81));
+
+    /*
+     * Ground: a rotated compound made of a flat box and a polyline ramp.
+     */
+    let ground_size = 25.0;
+    let floor = ShapeHandle::new(Cuboid::new(Vector2::new(ground_size, 1.0)));
+    let ramp = ShapeHandle::new(Polyline::new(
+        vec![Point2::new(-5.0, 0.0), Point2::new(0.0, 3.0), Point2::new(5.0, 0.0)],
+        None,
+    ));
+
+    let ground_geoms = vec![
+        (Isometry2::new(-Vector2::y(), na::zero()), floor),
+        (Isometry2::new(Vector2::x() * 10.0, 0.3), ramp),
+    ];
+
+    ColliderDesc::new(ShapeHandle::new(Compound::new(ground_geoms)))
+        .rotation(0.1)
+        .build(&mut world);
+
+    /*
+     * L shaped geometry. The vertical part is a rotated copy of the horizontal one.
+     */
+    let length = 1.0f32;
+    let thickness = 0.2f32;
+
+    let arm = ShapeHandle::new(Cuboid::new(Vector2::new(length, thickness)));
+    let delta1 = Isometry2::new(Vector2::new(length - thickness, 0.0), na::zero());
+    let delta2 = Isometry2::new(Vector2::new(0.0, length - thickness), FRAC_PI_2);
+
+    let l_shape = ShapeHandle::new(Compound::new(vec![(delta1, arm.clone()), (delta2, arm)]));
+    let collider_desc = ColliderDesc::new(l_shape).density(1.0);
+
+    /*
+     * Create the rigid bodies, each rotated differently.
+     */
+    let num = 10;
+    let shift = 2.5 * length;
+    let centerx = shift * (num as f32) / 2.0;
+
+    let mut rb_desc = RigidBodyDesc::new().collider(&collider_desc);
+
+    for i in 0usize..num {
+        for j in 0usize..num {
+            let x = i as f32 * shift - centerx;
+            let y = j as f32 * shift + 3.0;
+            let angle = (i * num + j) as f32 * 0.7;
+
+            rb_desc
+                .set_translation(Vector2::new(x, y))
+                .set_rotation(angle)
+                .build(&mut world);
+        }
+    }
+
+    /*
+     * Run the simulation.
+     */
+    let testbed = Testbed::new(world);
+    testbed.run();
+}
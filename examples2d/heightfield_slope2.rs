@@ -0,0 +1,74 @@
+extern crate nalgebra as na;
+extern crate ncollide2d;
+extern crate nphysics2d;
+extern crate nphysics_testbed2d;
+extern crate rand;
+
+use na::{Point2, Vector2, DVector};
+use ncollide2d::shape::{Ball, HeightField, ShapeHandle};
+use nphysics2d::object::{RigidBodyDesc, ColliderDesc};
+use nphysics2d::world::World;
+use nphysics_testbed2d::Testbed;
+use rand::{Rng, SeedableRng, StdRng};
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vector2::new(0.0, -9.81));
+
+    /*
+     * Bumpy slope.
+     */
+    let nsubdivs = 100;
+    let slope_width = 40.0;
+    let slope_height = 10.0;
+    let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+    let heights = DVector::from_fn(nsubdivs + 1, |i, _| {
+        let descent = 1.0 - (i as f32) / (nsubdivs as f32);
+        let bump = rng.gen::<f32>() * 0.05;
+        descent + bump
+    });
+    let heightfield = HeightField::new(heights, Vector2::new(slope_width, slope_height));
+
+    ColliderDesc::new(ShapeHandle::new(heightfield))
+        .build(&mut world);
+
+    /*
+     * Create the balls at the top of the slope.
+     */
+    let width = 10;
+    let height = 5;
+    let rad = 0.2;
+
+    let ball = ShapeHandle::new(Ball::new(rad));
+    let collider_desc = ColliderDesc::new(ball)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = 2.0 * (rad + collider_desc.get_margin());
+    let startx = -slope_width / 2.0 + 1.0;
+    let starty = slope_height + 1.0;
+
+    for i in 0usize..height {
+        for j in 0usize..width {
+            let x = j as f32 * shift + startx;
+            let y = i as f32 * shift + starty;
+
+            // Create the rigid body and its collider.
+            rb_desc
+                .set_translation(Vector2::new(x, y))
+                .build(&mut world);
+        }
+    }
+
+    /*
+     * Run the simulation.
+     */
+    let mut testbed = Testbed::new(world);
+    testbed.look_at(Point2::new(0.0, slope_height / 2.0), 20.0);
+    testbed.run();
+}
@@ -0,0 +1,88 @@
+extern crate nalgebra as na;
+extern crate ncollide2d;
+extern crate nphysics2d;
+extern crate nphysics_testbed2d;
+
+use na::{Point2, Unit, Vector2};
+use ncollide2d::shape::{Ball, Cuboid, Polyline, ShapeHandle};
+use nphysics2d::algebra::Velocity2;
+use nphysics2d::object::{ColliderDesc, RigidBodyDesc};
+use nphysics2d::world::World;
+use nphysics_testbed2d::Testbed;
+
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vector2::new(0.0, -9.81));
+
+    /*
+     * Ground
+     */
+    let ground_size = 25.0;
+    let ground_shape =
+        ShapeHandle::new(Cuboid::new(Vector2::new(ground_size, 1.0)));
+
+    ColliderDesc::new(ground_shape)
+        .translation(-Vector2::y())
+        .build(&mut world);
+
+    /*
+     * One-way platforms: the bodies go through them from below and land on them from above.
+     */
+    let platform_width = 4.0;
+
+    for i in 0..3 {
+        let y = 3.0 + i as f32 * 3.0;
+        let x = (i as f32 - 1.0) * 2.0;
+        let vertices = vec![
+            Point2::new(x - platform_width, y),
+            Point2::new(x, y),
+            Point2::new(x + platform_width, y + 1.0),
+        ];
+        let polyline = ShapeHandle::new(Polyline::new(vertices, None));
+
+        ColliderDesc::new(polyline)
+            .one_way_normals(vec![
+                Vector2::y_axis(),
+                Unit::new_normalize(Vector2::new(-1.0, platform_width)),
+            ])
+            .build(&mut world);
+    }
+
+    /*
+     * Create the balls thrown upward through the platforms.
+     */
+    let num = 15;
+    let rad = 0.2;
+
+    let ball = ShapeHandle::new(Ball::new(rad));
+    let collider_desc = ColliderDesc::new(ball)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = (rad + collider_desc.get_margin()) * 2.0 + 0.1;
+    let centerx = shift * (num as f32) / 2.0;
+
+    for i in 0usize..num {
+        let x = i as f32 * shift - centerx;
+        let speed = 10.0 + (i % 5) as f32 * 2.0;
+
+        // Build the rigid body and its collider.
+        rb_desc
+            .set_translation(Vector2::new(x, rad))
+            .set_velocity(Velocity2::linear(0.0, speed))
+            .build(&mut world);
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+    testbed.look_at(Point2::new(0.0, 5.0), 40.0);
+    testbed.run();
+}
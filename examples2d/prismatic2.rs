@@ -0,0 +1,82 @@
+extern crate nalgebra as na;
+extern crate ncollide2d;
+extern crate nphysics2d;
+extern crate nphysics_testbed2d;
+
+use na::{Point2, Vector2};
+use ncollide2d::shape::{Cuboid, ShapeHandle};
+use nphysics2d::joint::PrismaticConstraint;
+use nphysics2d::object::{BodyPartHandle, ColliderDesc, RigidBodyDesc};
+use nphysics2d::world::World;
+use nphysics_testbed2d::Testbed;
+
+
+fn main() {
+    /*
+     * World
+     */
+    let mut world = World::new();
+    world.set_gravity(Vector2::new(0.0, -9.81));
+
+    /*
+     * Ground.
+     */
+    let ground_size = 25.0;
+    let ground_shape =
+        ShapeHandle::new(Cuboid::new(Vector2::new(ground_size, 1.0)));
+
+    ColliderDesc::new(ground_shape)
+        .translation(-Vector2::y() * 2.0)
+        .build(&mut world);
+
+    /*
+     * Stacked sliders.
+     */
+    let num = 5;
+    let rad = 0.5;
+    let gap = 3.0 * rad;
+
+    let geom = ShapeHandle::new(Cuboid::new(Vector2::new(rad * 2.0, rad)));
+    let collider_desc = ColliderDesc::new(geom)
+        .density(1.0);
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let mut parent = BodyPartHandle::ground();
+    // Each slider is anchored `gap` above its parent.
+    let anchor = Point2::new(0.0, gap);
+
+    for j in 0usize..num {
+        /*
+         * Create the rigid body.
+         */
+        let rb_handle = rb_desc
+            .set_translation(Vector2::y() * (j + 1) as f32 * gap)
+            .build(&mut world)
+            .part_handle();
+
+        let mut constraint = PrismaticConstraint::new(
+            parent,
+            rb_handle,
+            anchor,
+            Vector2::y_axis(),
+            Point2::origin(),
+        );
+
+        // Each slider may move only between -rad and +rad around its rest position.
+        constraint.set_limits(-rad, rad);
+        world.add_constraint(constraint);
+
+        /*
+         * Parent for the next constraint.
+         */
+        parent = rb_handle;
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    let mut testbed = Testbed::new(world);
+    testbed.look_at(Point2::new(0.0, 4.0), 60.0);
+    testbed.run();
+}
@@ -0,0 +1,121 @@
+extern crate nalgebra as na;
+extern crate ncollide2d;
+extern crate nphysics2d;
+extern crate nphysics_testbed2d;
+
+use na::{Point2, Vector2};
+use ncollide2d::shape::{Ball, Cuboid, ShapeHandle};
+use nphysics2d::object::{ColliderDesc, RigidBodyDesc};
+use nphysics2d::world::World;
+use nphysics_testbed2d::{GraphicsManager, Testbed};
+
+
+fn ground(world: &mut World<f32>) {
+    world.set_gravity(Vector2::new(0.0, -9.81));
+
+    let ground_size = 25.0;
+    let ground_shape =
+        ShapeHandle::new(Cuboid::new(Vector2::new(ground_size, 1.0)));
+
+    ColliderDesc::new(ground_shape)
+        .translation(-Vector2::y())
+        .build(world);
+}
+
+fn balls(world: &mut World<f32>, _: &mut GraphicsManager) {
+    ground(world);
+
+    let num = 20;
+    let rad = 0.1;
+
+    let ball = ShapeHandle::new(Ball::new(rad));
+    let collider_desc = ColliderDesc::new(ball)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = (rad + collider_desc.get_margin()) * 2.0 + 0.002;
+    let centerx = shift * (num as f32) / 2.0;
+    let centery = shift / 2.0;
+
+    for i in 0usize..num {
+        for j in 0..num {
+            let x = i as f32 * shift - centerx;
+            let y = j as f32 * shift + centery;
+
+            rb_desc
+                .set_translation(Vector2::new(x, y))
+                .build(world);
+        }
+    }
+}
+
+fn pyramid(world: &mut World<f32>, _: &mut GraphicsManager) {
+    ground(world);
+
+    let num = 20;
+    let rad = 0.1;
+
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector2::repeat(rad)));
+    let collider_desc = ColliderDesc::new(cuboid)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = 2.0 * (rad + collider_desc.get_margin());
+    let centerx = shift * (num as f32) / 2.0;
+    let centery = rad + collider_desc.get_margin() * 2.0;
+
+    for i in 0usize..num {
+        for j in i..num {
+            let fj = j as f32;
+            let fi = i as f32;
+            let x = (fi * shift / 2.0) + (fj - fi) * shift - centerx;
+            let y = fi * shift + centery;
+
+            rb_desc
+                .set_translation(Vector2::new(x, y))
+                .build(world);
+        }
+    }
+}
+
+fn tower(world: &mut World<f32>, graphics: &mut GraphicsManager) {
+    ground(world);
+
+    let height = 30;
+    let rad = 0.2;
+
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector2::new(rad * 2.0, rad)));
+    let collider_desc = ColliderDesc::new(cuboid)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = 2.0 * (rad + collider_desc.get_margin());
+
+    for i in 0usize..height {
+        rb_desc
+            .set_translation(Vector2::new(0.0, (i as f32 + 0.5) * shift))
+            .build(world);
+    }
+
+    // This scene is taller than the others so it sets up its own camera.
+    graphics.look_at(Point2::new(0.0, 5.0), 40.0);
+}
+
+
+fn main() {
+    /*
+     * Set up the testbed with all the scenes, switched with the page up and page down keys.
+     */
+    let mut testbed = Testbed::new_empty();
+    testbed.add_scene("Balls", balls);
+    testbed.add_scene("Pyramid", pyramid);
+    testbed.add_scene("Tower", tower);
+    testbed.look_at(Point2::new(0.0, -2.5), 95.0);
+    testbed.run();
+}
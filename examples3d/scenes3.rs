@@ -0,0 +1,123 @@
+extern crate nalgebra as na;
+extern crate ncollide3d;
+extern crate nphysics3d;
+extern crate nphysics_testbed3d;
+
+use na::{Point3, Vector3};
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+use nphysics3d::object::{ColliderDesc, RigidBodyDesc};
+use nphysics3d::world::World;
+use nphysics_testbed3d::{GraphicsManager, Testbed};
+
+
+fn ground(world: &mut World<f32>) {
+    world.set_gravity(Vector3::new(0.0, -9.81, 0.0));
+
+    let ground_size = 15.0;
+    let ground_shape =
+        ShapeHandle::new(Cuboid::new(Vector3::repeat(ground_size)));
+
+    ColliderDesc::new(ground_shape)
+        .translation(Vector3::y() * -ground_size)
+        .build(world);
+}
+
+fn balls(world: &mut World<f32>, _: &mut GraphicsManager) {
+    ground(world);
+
+    let num = 8;
+    let rad = 0.1;
+
+    let ball = ShapeHandle::new(Ball::new(rad));
+    let collider_desc = ColliderDesc::new(ball)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = (rad + collider_desc.get_margin()) * 2.0 + 0.002;
+    let center = shift * (num as f32) / 2.0;
+
+    for i in 0usize..num {
+        for j in 0usize..num {
+            for k in 0usize..num {
+                let x = i as f32 * shift - center;
+                let y = j as f32 * shift + shift / 2.0;
+                let z = k as f32 * shift - center;
+
+                rb_desc
+                    .set_translation(Vector3::new(x, y, z))
+                    .build(world);
+            }
+        }
+    }
+}
+
+fn pyramid(world: &mut World<f32>, _: &mut GraphicsManager) {
+    ground(world);
+
+    let num = 20;
+    let rad = 0.1;
+
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::repeat(rad)));
+    let collider_desc = ColliderDesc::new(cuboid)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = (rad + collider_desc.get_margin()) * 2.0;
+    let centerx = shift * (num as f32) / 2.0;
+    let centery = shift / 2.0;
+
+    for i in 0usize..num {
+        for j in i..num {
+            let fi = i as f32;
+            let fj = (j - i) as f32;
+            let x = (fi * shift / 2.0) + fj * shift - centerx;
+            let y = fi * shift + centery;
+
+            rb_desc
+                .set_translation(Vector3::new(x, y, 0.0))
+                .build(world);
+        }
+    }
+}
+
+fn tower(world: &mut World<f32>, graphics: &mut GraphicsManager) {
+    ground(world);
+
+    let height = 30;
+    let rad = 0.2;
+
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(rad * 2.0, rad, rad * 2.0)));
+    let collider_desc = ColliderDesc::new(cuboid)
+        .density(1.0);
+
+    let mut rb_desc = RigidBodyDesc::new()
+        .collider(&collider_desc);
+
+    let shift = 2.0 * (rad + collider_desc.get_margin());
+
+    for i in 0usize..height {
+        rb_desc
+            .set_translation(Vector3::new(0.0, (i as f32 + 0.5) * shift, 0.0))
+            .build(world);
+    }
+
+    // This scene is taller than the others so it sets up its own camera.
+    graphics.look_at(Point3::new(-15.0, 10.0, -15.0), Point3::new(0.0, 5.0, 0.0));
+}
+
+
+fn main() {
+    /*
+     * Set up the testbed with all the scenes, switched with the page up and page down keys.
+     */
+    let mut testbed = Testbed::new_empty();
+    testbed.add_scene("Balls", balls);
+    testbed.add_scene("Pyramid", pyramid);
+    testbed.add_scene("Tower", tower);
+    testbed.look_at(Point3::new(-10.0, 10.0, -10.0), Point3::origin());
+    testbed.run();
+}
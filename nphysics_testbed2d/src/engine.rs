@@ -1,12 +1,13 @@
 use kiss3d::planar_camera::{PlanarCamera, Sidescroll};
 use kiss3d::scene::PlanarSceneNode;
+use kiss3d::text::Font;
 use kiss3d::window::Window;
 use na;
-use na::{Isometry2, Point2, Point3};
+use na::{Isometry2, Point2, Point3, Vector2};
 use ncollide2d::shape::{self, Compound, ConvexPolygon, Cuboid, Shape};
 use ncollide2d::query::Ray;
 use ncollide2d::world::CollisionGroups;
-use nphysics2d::object::{BodyHandle, BodyPartHandle, ColliderHandle, ColliderAnchor};
+use nphysics2d::object::{Body, BodyHandle, BodyPart, BodyPartHandle, ColliderHandle, ColliderAnchor};
 use nphysics2d::world::World;
 use crate::objects::ball::Ball;
 use crate::objects::box_node::Box;
@@ -18,22 +19,135 @@ use crate::objects::heightfield::HeightField;
 use crate::objects::node::Node;
 use crate::objects::plane::Plane;
 use rand::{Rng, SeedableRng, XorShiftRng};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// The way the rigid bodies are colored by the `GraphicsManager`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Each body is drawn with its own color.
+    Default,
+    /// Awake bodies are drawn in orange and sleeping bodies in blue.
+    SleepState,
+    /// Rigid bodies are drawn with a gradient from blue to red as their linear speed increases.
+    Speed,
+}
+
+/// The colors used to draw the scene and the overlays.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// The color the window is cleared with.
+    pub background: Point3<f32>,
+    /// The color of the texts drawn on top of the scene.
+    pub text: Point3<f32>,
+    /// The color of the colliders attached to the ground.
+    pub ground: Point3<f32>,
+    /// The colors randomly assigned to the bodies. If empty, random bright colors are used.
+    pub palette: Vec<Point3<f32>>,
+}
+
+impl Theme {
+    /// A light gray background with random bright body colors.
+    pub fn light() -> Self {
+        Theme {
+            background: Point3::new(0.9, 0.9, 0.9),
+            text: Point3::new(0.0, 0.0, 0.0),
+            ground: Point3::new(0.5, 0.5, 0.5),
+            palette: Vec::new(),
+        }
+    }
+
+    /// A dark gray background with random bright body colors.
+    pub fn dark() -> Self {
+        Theme {
+            background: Point3::new(0.15, 0.15, 0.15),
+            text: Point3::new(0.9, 0.9, 0.9),
+            ground: Point3::new(0.4, 0.4, 0.4),
+            palette: Vec::new(),
+        }
+    }
+
+    /// A white background with a palette of muted colors, suited for printed figures.
+    pub fn paper() -> Self {
+        Theme {
+            background: Point3::new(1.0, 1.0, 1.0),
+            text: Point3::new(0.0, 0.0, 0.0),
+            ground: Point3::new(0.7, 0.7, 0.7),
+            palette: vec![
+                Point3::new(0.12, 0.47, 0.71),
+                Point3::new(1.0, 0.5, 0.05),
+                Point3::new(0.17, 0.63, 0.17),
+                Point3::new(0.84, 0.15, 0.16),
+                Point3::new(0.58, 0.4, 0.74),
+                Point3::new(0.55, 0.34, 0.29),
+                Point3::new(0.89, 0.47, 0.76),
+                Point3::new(0.74, 0.74, 0.13),
+                Point3::new(0.09, 0.75, 0.81),
+            ],
+        }
+    }
+
+    /// The preset following this one when cycling through `light`, `dark`, and `paper`.
+    ///
+    /// Custom themes are followed by the light theme.
+    pub fn next_preset(&self) -> Self {
+        if *self == Theme::light() {
+            Theme::dark()
+        } else if *self == Theme::dark() {
+            Theme::paper()
+        } else {
+            Theme::light()
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
 
 pub struct GraphicsManager {
     rand: XorShiftRng,
     b2sn: HashMap<BodyHandle, Vec<Node>>,
     b2color: HashMap<BodyHandle, Point3<f32>>,
     c2color: HashMap<ColliderHandle, Point3<f32>>,
+    custom_body_colors: HashSet<BodyHandle>,
+    theme: Theme,
     rays: Vec<Ray<f32>>,
     camera: Sidescroll,
     aabbs: Vec<PlanarSceneNode>,
+    draw_contacts: bool,
+    draw_aabbs: bool,
+    draw_velocities: bool,
+    velocity_scale: f32,
+    followed_body: Option<BodyHandle>,
+    follow_smoothing: f32,
+    color_mode: ColorMode,
+    max_color_speed: f32,
+    draw_grid: bool,
+    grid_spacing: f32,
+    grid_major_every: usize,
+    b2label: HashMap<BodyHandle, String>,
+    draw_labels: bool,
+    draw_joints: bool,
+    font: Rc<Font>,
 }
 
 impl GraphicsManager {
     pub fn new() -> GraphicsManager {
-        let rng: XorShiftRng = SeedableRng::from_seed([0; 16]);
+        Self::with_rng(SeedableRng::from_seed([0; 16]))
+    }
 
+    /// Creates a graphics manager assigning the body colors from a random generator with the given seed.
+    ///
+    /// Identical seeds, together with an identical order of addition of the bodies, result in
+    /// identical colors.
+    pub fn with_seed(seed: u64) -> GraphicsManager {
+        Self::with_rng(XorShiftRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: XorShiftRng) -> GraphicsManager {
         let mut camera = Sidescroll::new();
         camera.set_zoom(50.0);
 
@@ -43,8 +157,25 @@ impl GraphicsManager {
             b2sn: HashMap::new(),
             b2color: HashMap::new(),
             c2color: HashMap::new(),
+            custom_body_colors: HashSet::new(),
+            theme: Theme::default(),
             rays: Vec::new(),
             aabbs: Vec::new(),
+            draw_contacts: false,
+            draw_aabbs: false,
+            draw_velocities: false,
+            velocity_scale: 0.1,
+            followed_body: None,
+            follow_smoothing: 1.0,
+            color_mode: ColorMode::Default,
+            max_color_speed: 10.0,
+            draw_grid: false,
+            grid_spacing: 1.0,
+            grid_major_every: 5,
+            b2label: HashMap::new(),
+            draw_labels: true,
+            draw_joints: false,
+            font: Font::default(),
         }
     }
 
@@ -70,6 +201,17 @@ impl GraphicsManager {
         self.rays.clear();
     }
 
+    /// Forgets the colors and labels assigned to the bodies and colliders, and stops following any body.
+    ///
+    /// This should be called once the world is cleared since its new bodies may reuse the same handles.
+    pub fn clear_body_settings(&mut self) {
+        self.b2color.clear();
+        self.c2color.clear();
+        self.custom_body_colors.clear();
+        self.b2label.clear();
+        self.followed_body = None;
+    }
+
     pub fn remove_body_nodes(&mut self, window: &mut Window, body: BodyHandle) {
         if let Some(sns) = self.b2sn.get(&body) {
             for sn in sns.iter() {
@@ -80,6 +222,7 @@ impl GraphicsManager {
         }
 
         self.b2sn.remove(&body);
+        self.b2label.remove(&body);
     }
 
     pub fn remove_body_part_nodes(
@@ -134,6 +277,11 @@ impl GraphicsManager {
     }
 
     pub fn set_body_color(&mut self, b: BodyHandle, color: Point3<f32>) {
+        let _ = self.custom_body_colors.insert(b);
+        self.apply_body_color(b, color);
+    }
+
+    fn apply_body_color(&mut self, b: BodyHandle, color: Point3<f32>) {
         self.b2color.insert(b, color);
 
         if let Some(ns) = self.b2sn.get_mut(&b) {
@@ -143,27 +291,65 @@ impl GraphicsManager {
         }
     }
 
+    /// Reseeds the random generator the colors of the bodies added from now on are drawn from.
+    ///
+    /// See `GraphicsManager::with_seed` for details.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rand = XorShiftRng::seed_from_u64(seed);
+    }
+
+    /// The colors used to draw the scene.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Sets the colors used to draw the scene.
+    ///
+    /// The bodies whose color was not set explicitly with `self.set_body_color(...)` are given a new
+    /// color from the palette of the theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+
+        let handles: Vec<_> = self
+            .b2sn
+            .keys()
+            .filter(|h| !self.custom_body_colors.contains(*h))
+            .cloned()
+            .collect();
+
+        for handle in handles {
+            let color = self.palette_color(handle);
+            self.apply_body_color(handle, color);
+        }
+    }
+
+    fn palette_color(&mut self, handle: BodyHandle) -> Point3<f32> {
+        if handle.is_ground() {
+            self.theme.ground
+        } else if !self.theme.palette.is_empty() {
+            let i = self.rand.gen_range(0, self.theme.palette.len());
+            self.theme.palette[i]
+        } else {
+            let mut color: Point3<f32> = self.rand.gen();
+            color *= 1.5;
+            color.x = color.x.min(1.0);
+            color.y = color.y.min(1.0);
+            color.z = color.z.min(1.0);
+            color
+        }
+    }
+
     pub fn set_collider_color(&mut self, handle: ColliderHandle, color: Point3<f32>) {
         self.c2color.insert(handle, color);
     }
 
     fn alloc_color(&mut self, handle: BodyHandle) -> Point3<f32> {
-        let mut color = Point3::new(0.5, 0.5, 0.5);
-
-        match self.b2color.get(&handle) {
-            Some(c) => color = *c,
-            None => {
-                if !handle.is_ground() {
-                    color = self.rand.gen();
-                    color *= 1.5;
-                    color.x = color.x.min(1.0);
-                    color.y = color.y.min(1.0);
-                    color.z = color.z.min(1.0);
-                }
-            }
-        }
+        let color = match self.b2color.get(&handle) {
+            Some(c) => *c,
+            None => self.palette_color(handle),
+        };
 
-        self.set_body_color(handle, color);
+        self.apply_body_color(handle, color);
 
         color
     }
@@ -172,7 +358,8 @@ impl GraphicsManager {
         self.rays.push(ray)
     }
 
-    pub fn add(&mut self, window: &mut Window, id: ColliderHandle, world: &World<f32>) {
+    /// Adds the scene nodes of the given collider, returning the number of geometries that cannot be displayed.
+    pub fn add(&mut self, window: &mut Window, id: ColliderHandle, world: &World<f32>) -> usize {
         let collider = world.collider(id).unwrap();
 
         let color = if let Some(c) = self.c2color.get(&id).cloned() {
@@ -186,25 +373,31 @@ impl GraphicsManager {
         self.add_with_color(window, id, world, color)
     }
 
+    /// Adds the scene nodes of the given collider with a custom color.
+    ///
+    /// Geometries that are not supported by the testbed are skipped with a warning. Their
+    /// collider is still simulated. Returns the number of skipped geometries.
     pub fn add_with_color(
         &mut self,
         window: &mut Window,
         id: ColliderHandle,
         world: &World<f32>,
         color: Point3<f32>,
-    ) {
+    ) -> usize {
         let collider = world.collider(id).unwrap();
         let key = collider.body();
         let shape = collider.shape().as_ref();
 
         // NOTE: not optimal allocation-wise, but it is not critical here.
         let mut new_nodes = Vec::new();
-        self.add_shape(window, id, world, na::one(), shape, color, &mut new_nodes);
+        let nskipped = self.add_shape(window, id, world, na::one(), shape, color, &mut new_nodes);
 
         {
             let nodes = self.b2sn.entry(key).or_insert_with(Vec::new);
             nodes.append(&mut new_nodes);
         }
+
+        nskipped
     }
 
     fn add_shape(
@@ -216,7 +409,7 @@ impl GraphicsManager {
         shape: &Shape<f32>,
         color: Point3<f32>,
         out: &mut Vec<Node>,
-    ) {
+    ) -> usize {
         if let Some(s) = shape.as_shape::<shape::Plane<f32>>() {
             self.add_plane(window, object, world, s, color, out)
         } else if let Some(s) = shape.as_shape::<shape::Ball<f32>>() {
@@ -230,14 +423,24 @@ impl GraphicsManager {
         } else if let Some(s) = shape.as_shape::<shape::HeightField<f32>>() {
             self.add_heightfield(window, object, world, delta, s, color, out)
         } else if let Some(s) = shape.as_shape::<Compound<f32>>() {
+            let mut nskipped = 0;
+
             for &(t, ref s) in s.shapes().iter() {
-                self.add_shape(window, object, world, delta * t, s.as_ref(), color, out)
+                nskipped += self.add_shape(window, object, world, delta * t, s.as_ref(), color, out)
             }
+
+            return nskipped;
         } else if let Some(s) = shape.as_shape::<shape::Polyline<f32>>() {
             self.add_polyline(window, object, world, delta, s, color, out);
         } else {
-            println!("Unknown shapes found. It won't be displayed.");
+            #[cfg(not(feature = "log"))]
+            println!("Skipping a geometry of the collider {:?}: its shape cannot be displayed by the testbed.", object);
+            #[cfg(feature = "log")]
+            warn!("Skipping a geometry of the collider {:?}: its shape cannot be displayed by the testbed.", object);
+            return 1;
         }
+
+        0
     }
 
     fn add_plane(
@@ -384,12 +587,25 @@ impl GraphicsManager {
     }
 
     pub fn draw(&mut self, world: &World<f32>, window: &mut Window) {
+        self.update_followed_body(world);
+
+        let bg = self.theme.background;
+        window.set_background_color(bg.x, bg.y, bg.z);
+
+        if self.draw_grid {
+            self.draw_world_grid(window);
+        }
+
         for (_, ns) in self.b2sn.iter_mut() {
             for n in ns.iter_mut() {
                 n.update(world)
             }
         }
 
+        if self.color_mode != ColorMode::Default {
+            self.apply_color_mode(world);
+        }
+
         for (_, ns) in self.b2sn.iter_mut() {
             for n in ns.iter_mut() {
                 n.draw(window)
@@ -404,6 +620,397 @@ impl GraphicsManager {
             let p2 = ray.origin + ray.dir * hit;
             window.draw_planar_line(&p1, &p2, &Point3::new(1.0, 0.0, 0.0));
         }
+
+        if self.draw_contacts {
+            self.draw_contact_points(world, window);
+        }
+
+        if self.draw_aabbs {
+            self.draw_broad_phase_aabbs(world, window);
+        }
+
+        if self.draw_velocities {
+            self.draw_rigid_body_velocities(world, window);
+        }
+
+        if self.draw_labels {
+            self.draw_body_labels(world, window);
+        }
+
+        if self.draw_joints {
+            self.draw_joint_anchors(world, window);
+        }
+    }
+
+    /// The text label drawn next to the given body, if any.
+    pub fn body_label(&self, body: BodyHandle) -> Option<&str> {
+        self.b2label.get(&body).map(|l| &l[..])
+    }
+
+    /// Sets the text label drawn next to the given body by `self.draw(...)`.
+    pub fn set_body_label(&mut self, body: BodyHandle, label: &str) {
+        let _ = self.b2label.insert(body, label.to_string());
+    }
+
+    /// Removes the text label of the given body, returning it if it existed.
+    pub fn remove_body_label(&mut self, body: BodyHandle) -> Option<String> {
+        self.b2label.remove(&body)
+    }
+
+    /// Whether the body labels are drawn by `self.draw(...)`.
+    pub fn draw_labels(&self) -> bool {
+        self.draw_labels
+    }
+
+    /// Enables or disables the drawing of the body labels by `self.draw(...)`.
+    pub fn set_draw_labels(&mut self, enabled: bool) {
+        self.draw_labels = enabled
+    }
+
+    /// Whether the joint constraints are drawn by `self.draw(...)`.
+    pub fn draw_joints(&self) -> bool {
+        self.draw_joints
+    }
+
+    /// Enables or disables the drawing of the joint constraints by `self.draw(...)`.
+    pub fn set_draw_joints(&mut self, enabled: bool) {
+        self.draw_joints = enabled
+    }
+
+    /// The way the rigid bodies are colored by `self.draw(...)`.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Sets the way the rigid bodies are colored by `self.draw(...)`.
+    ///
+    /// Modes other than `ColorMode::Default` do not modify the colors of the bodies, which are
+    /// restored as soon as the default mode is set back.
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+    }
+
+    /// The linear speed drawn in red when the color mode is `ColorMode::Speed`.
+    pub fn max_color_speed(&self) -> f32 {
+        self.max_color_speed
+    }
+
+    /// Sets the linear speed drawn in red when the color mode is `ColorMode::Speed`.
+    ///
+    /// Faster bodies are drawn in red too.
+    pub fn set_max_color_speed(&mut self, speed: f32) {
+        self.max_color_speed = speed;
+    }
+
+    // Overrides the colors set by the nodes update with the ones of the current color mode.
+    fn apply_color_mode(&mut self, world: &World<f32>) {
+        let awake_color = Point3::new(1.0, 0.6, 0.0);
+        let sleeping_color = Point3::new(0.0, 0.3, 1.0);
+
+        for (handle, ns) in self.b2sn.iter_mut() {
+            let body = match world.body(*handle) {
+                Some(body) if body.is_dynamic() => body,
+                _ => continue,
+            };
+
+            let color = match self.color_mode {
+                ColorMode::Default => continue,
+                ColorMode::SleepState => {
+                    if body.is_active() {
+                        awake_color
+                    } else {
+                        sleeping_color
+                    }
+                }
+                ColorMode::Speed => match world.rigid_body(*handle) {
+                    Some(rb) => speed_color(rb.velocity().linear.norm() / self.max_color_speed),
+                    None => continue,
+                },
+            };
+
+            for n in ns.iter_mut() {
+                if let Some(node) = n.scene_node_mut() {
+                    node.set_color(color.x, color.y, color.z)
+                }
+            }
+        }
+    }
+
+    /// Whether the broad phase bounding boxes are drawn by `self.draw(...)`.
+    pub fn draw_aabbs(&self) -> bool {
+        self.draw_aabbs
+    }
+
+    /// Enables or disables the drawing of the broad phase bounding boxes by `self.draw(...)`.
+    pub fn set_draw_aabbs(&mut self, enabled: bool) {
+        self.draw_aabbs = enabled
+    }
+
+    /// Whether the centers of mass and velocities of rigid bodies are drawn by `self.draw(...)`.
+    pub fn draw_velocities(&self) -> bool {
+        self.draw_velocities
+    }
+
+    /// Enables or disables the drawing of the centers of mass and velocities of rigid bodies by `self.draw(...)`.
+    pub fn set_draw_velocities(&mut self, enabled: bool) {
+        self.draw_velocities = enabled
+    }
+
+    /// The factor by which velocities are multiplied to obtain the length of the drawn arrows.
+    pub fn velocity_scale(&self) -> f32 {
+        self.velocity_scale
+    }
+
+    /// Sets the factor by which velocities are multiplied to obtain the length of the drawn arrows.
+    pub fn set_velocity_scale(&mut self, scale: f32) {
+        self.velocity_scale = scale
+    }
+
+    /// Whether the contact points and normals are drawn by `self.draw(...)`.
+    pub fn draw_contacts(&self) -> bool {
+        self.draw_contacts
+    }
+
+    /// Enables or disables the drawing of the contact points and normals by `self.draw(...)`.
+    pub fn set_draw_contacts(&mut self, enabled: bool) {
+        self.draw_contacts = enabled
+    }
+
+    /// Whether the world-space grid is drawn behind the bodies by `self.draw(...)`.
+    pub fn draw_grid(&self) -> bool {
+        self.draw_grid
+    }
+
+    /// Enables or disables the drawing of the world-space grid behind the bodies by `self.draw(...)`.
+    pub fn set_draw_grid(&mut self, enabled: bool) {
+        self.draw_grid = enabled
+    }
+
+    /// The world-space distance between two consecutive lines of the grid.
+    pub fn grid_spacing(&self) -> f32 {
+        self.grid_spacing
+    }
+
+    /// Sets the world-space distance between two consecutive lines of the grid, and the number of
+    /// cells between two labeled major lines.
+    pub fn set_grid_spacing(&mut self, spacing: f32, major_every: usize) {
+        assert!(spacing > 0.0, "The grid spacing must be positive.");
+        self.grid_spacing = spacing;
+        self.grid_major_every = major_every.max(1);
+    }
+
+    // Lines too close to each other on screen are skipped so the grid never becomes a solid fill,
+    // and the major lines are labeled only when there is enough room for their labels.
+    fn draw_world_grid(&self, window: &mut Window) {
+        // The grid colors are shades between the background and text colors of the theme.
+        let bg = self.theme.background;
+        let shade = |t: f32| bg + (self.theme.text - bg) * t;
+        let minor_color = shade(0.1);
+        let major_color = shade(0.3);
+        let label_color = shade(0.7);
+
+        let size: Vector2<f32> = na::convert(window.size());
+        let corner1 = self.camera.unproject(&Point2::origin(), &size);
+        let corner2 = self.camera.unproject(&Point2::new(size.x, size.y), &size);
+        let mins = Point2::new(corner1.x.min(corner2.x), corner1.y.min(corner2.y));
+        let maxs = Point2::new(corner1.x.max(corner2.x), corner1.y.max(corner2.y));
+
+        // The camera does not rotate, so world coordinates map linearly to pixels.
+        let pixels_per_unit = size.x / (maxs.x - mins.x);
+        let major_spacing = self.grid_spacing * self.grid_major_every as f32;
+        let to_screen = |p: Point2<f32>| {
+            Point2::new(
+                (p.x - corner1.x) / (corner2.x - corner1.x) * size.x,
+                (p.y - corner1.y) / (corner2.y - corner1.y) * size.y,
+            )
+        };
+
+        for (axis, spacing, color) in &[
+            (0, self.grid_spacing, minor_color),
+            (1, self.grid_spacing, minor_color),
+            (0, major_spacing, major_color),
+            (1, major_spacing, major_color),
+        ] {
+            if spacing * pixels_per_unit < MIN_GRID_LINE_GAP {
+                continue;
+            }
+
+            let first = (mins[*axis] / spacing).ceil() as i64;
+            let last = (maxs[*axis] / spacing).floor() as i64;
+
+            for i in first..=last {
+                let coord = i as f32 * spacing;
+                let (p1, p2) = if *axis == 0 {
+                    (Point2::new(coord, mins.y), Point2::new(coord, maxs.y))
+                } else {
+                    (Point2::new(mins.x, coord), Point2::new(maxs.x, coord))
+                };
+
+                window.draw_planar_line(&p1, &p2, color);
+            }
+        }
+
+        if major_spacing * pixels_per_unit < MIN_GRID_LABEL_GAP {
+            return;
+        }
+
+        // The labels are drawn along the bottom and left borders of the window, with a constant size.
+        for axis in 0..2 {
+            let first = (mins[axis] / major_spacing).ceil() as i64;
+            let last = (maxs[axis] / major_spacing).floor() as i64;
+
+            for i in first..=last {
+                let coord = i as f32 * major_spacing;
+                let pos = if axis == 0 {
+                    let p = to_screen(Point2::new(coord, mins.y));
+                    Point2::new(p.x + 4.0, size.y - GRID_LABEL_SIZE)
+                } else {
+                    let p = to_screen(Point2::new(mins.x, coord));
+                    Point2::new(4.0, p.y)
+                };
+
+                window.draw_text(&format!("{}", coord), &pos, GRID_LABEL_SIZE, &self.font, &label_color);
+            }
+        }
+    }
+
+    fn draw_rigid_body_velocities(&self, world: &World<f32>, window: &mut Window) {
+        let com_color = Point3::new(0.0, 0.0, 0.0);
+        let vel_color = Point3::new(0.0, 0.8, 0.0);
+        let angvel_color = Point3::new(1.0, 0.5, 0.0);
+
+        for body in world.bodies() {
+            let rb = match world.rigid_body(body.handle()) {
+                Some(rb) => rb,
+                None => continue,
+            };
+
+            let com = rb.center_of_mass();
+            let dx = Vector2::x() * 0.1;
+            let dy = Vector2::y() * 0.1;
+            window.draw_planar_line(&(com - dx), &(com + dx), &com_color);
+            window.draw_planar_line(&(com - dy), &(com + dy), &com_color);
+
+            // Sleeping bodies have no velocity arrow.
+            if !rb.is_active() {
+                continue;
+            }
+
+            let vel = rb.velocity();
+            let end = com + vel.linear * self.velocity_scale;
+            window.draw_planar_line(&com, &end, &vel_color);
+
+            if let Some(dir) = (end - com).try_normalize(1.0e-6) {
+                let ortho = Vector2::new(-dir.y, dir.x);
+                let head = 0.1;
+                window.draw_planar_line(&end, &(end + (ortho - dir) * head), &vel_color);
+                window.draw_planar_line(&end, &(end - (ortho + dir) * head), &vel_color);
+            }
+
+            // The angular velocity is hinted by an arc around the center of mass.
+            let nsegs = 8;
+            let radius = 0.2;
+            let angle = vel.angular * self.velocity_scale;
+            let point_at = |i: usize| {
+                let a = angle * (i as f32) / (nsegs as f32);
+                com + Vector2::new(a.cos(), a.sin()) * radius
+            };
+
+            for i in 0..nsegs {
+                window.draw_planar_line(&point_at(i), &point_at(i + 1), &angvel_color);
+            }
+        }
+    }
+
+    // The labels are drawn in screen space so they keep the same size and orientation whatever the
+    // zoom level and the rotation of their body.
+    fn draw_body_labels(&self, world: &World<f32>, window: &mut Window) {
+        let size: Vector2<f32> = na::convert(window.size());
+        let corner1 = self.camera.unproject(&Point2::origin(), &size);
+        let corner2 = self.camera.unproject(&Point2::new(size.x, size.y), &size);
+
+        for (handle, label) in &self.b2label {
+            let center = match world.body(*handle).and_then(|b| b.part(0)) {
+                Some(part) => part.center_of_mass(),
+                None => continue,
+            };
+
+            let pos = Point2::new(
+                (center.x - corner1.x) / (corner2.x - corner1.x) * size.x,
+                (center.y - corner1.y) / (corner2.y - corner1.y) * size.y,
+            );
+
+            if pos.x < 0.0 || pos.y < 0.0 || pos.x > size.x || pos.y > size.y {
+                continue;
+            }
+
+            let pos = pos + Vector2::new(LABEL_OFFSET, -LABEL_SIZE - LABEL_OFFSET);
+            window.draw_text(label, &pos, LABEL_SIZE, &self.font, &self.theme.text);
+        }
+    }
+
+    // Each joint is drawn as a line between its two anchors, each linked to the center of mass of
+    // its body part. The color only depends on the joint handle so it does not change between frames.
+    fn draw_joint_anchors(&self, world: &World<f32>, window: &mut Window) {
+        for joint in world.joints() {
+            let (r, g, b) = JOINT_COLORS[joint.handle % JOINT_COLORS.len()];
+            let color = Point3::new(r, g, b);
+
+            window.draw_planar_line(&joint.anchor1, &joint.anchor2, &color);
+
+            for (part, anchor) in &[(joint.body_part1, joint.anchor1), (joint.body_part2, joint.anchor2)] {
+                if part.is_ground() {
+                    continue;
+                }
+
+                if let Some(part) = world.body(part.0).and_then(|b| b.part(part.1)) {
+                    window.draw_planar_line(&part.center_of_mass(), anchor, &color);
+                }
+            }
+        }
+    }
+
+    // Bounding boxes are drawn in magenta to be easily distinguished from the bodies.
+    fn draw_broad_phase_aabbs(&self, world: &World<f32>, window: &mut Window) {
+        let color = Point3::new(1.0, 0.0, 1.0);
+
+        for co in world.colliders() {
+            if let Some(aabb) = world.collider_world().broad_phase_aabb(co.handle()) {
+                let mins = aabb.mins();
+                let maxs = aabb.maxs();
+                let corners = [
+                    *mins,
+                    Point2::new(maxs.x, mins.y),
+                    *maxs,
+                    Point2::new(mins.x, maxs.y),
+                ];
+
+                for i in 0..4 {
+                    window.draw_planar_line(&corners[i], &corners[(i + 1) % 4], &color);
+                }
+            }
+        }
+    }
+
+    // Contacts that received a non-zero impulse during the last step are red, others are blue.
+    fn draw_contact_points(&self, world: &World<f32>, window: &mut Window) {
+        for manifold in world.contacts() {
+            for c in manifold.contacts() {
+                let color = if c.normal_impulse != 0.0 {
+                    Point3::new(1.0, 0.0, 0.0)
+                } else {
+                    Point3::new(0.0, 0.0, 1.0)
+                };
+
+                let center = na::center(&c.world1, &c.world2);
+                let end = center + *c.normal * 0.4;
+                let dx = Vector2::x() * 0.05;
+                let dy = Vector2::y() * 0.05;
+                window.draw_planar_line(&(center - dx), &(center + dx), &color);
+                window.draw_planar_line(&(center - dy), &(center + dy), &color);
+                window.draw_planar_line(&center, &end, &Point3::new(0.0, 1.0, 1.0));
+            }
+        }
     }
 
     // pub fn draw_positions(&mut self, window: &mut Window, rbs: &RigidBodies<f32>) {
@@ -433,6 +1040,43 @@ impl GraphicsManager {
         &mut self.camera as &'a mut PlanarCamera
     }
 
+    /// Makes the camera follow the given body, or navigate freely if `None`.
+    pub fn follow(&mut self, body: Option<BodyHandle>) {
+        self.followed_body = body
+    }
+
+    /// The body followed by the camera, if any.
+    pub fn followed_body(&self) -> Option<BodyHandle> {
+        self.followed_body
+    }
+
+    /// Sets the fraction in `]0, 1]` of the distance to the followed body covered by the camera at each frame.
+    ///
+    /// A value of `1.0` keeps the followed body exactly at the center of the view.
+    pub fn set_follow_smoothing(&mut self, smoothing: f32) {
+        self.follow_smoothing = smoothing
+    }
+
+    fn update_followed_body(&mut self, world: &World<f32>) {
+        let handle = match self.followed_body {
+            Some(handle) => handle,
+            None => return,
+        };
+
+        let target = match world.body(handle).and_then(|b| b.part(0)) {
+            Some(part) => part.center_of_mass(),
+            None => {
+                // The body has been removed.
+                self.followed_body = None;
+                return;
+            }
+        };
+
+        let at = self.camera.at();
+        let zoom = self.camera.zoom();
+        self.camera.look_at(at + (target - at) * self.follow_smoothing, zoom);
+    }
+
     pub fn look_at(&mut self, at: Point2<f32>, zoom: f32) {
         self.camera.look_at(at, zoom);
     }
@@ -454,3 +1098,32 @@ impl Default for GraphicsManager {
         Self::new()
     }
 }
+
+// Minimal distance in pixels between two drawn grid lines.
+const MIN_GRID_LINE_GAP: f32 = 4.0;
+// Minimal distance in pixels between two labeled major grid lines.
+const MIN_GRID_LABEL_GAP: f32 = 80.0;
+const GRID_LABEL_SIZE: f32 = 30.0;
+// Size of the body labels, and their distance in pixels to the center of mass of their body.
+const LABEL_SIZE: f32 = 30.0;
+const LABEL_OFFSET: f32 = 4.0;
+// Colors of the joints, picked from the joint handles.
+const JOINT_COLORS: [(f32, f32, f32); 6] = [
+    (1.0, 0.5, 0.0),
+    (0.0, 0.7, 0.7),
+    (0.7, 0.0, 0.7),
+    (0.5, 0.8, 0.0),
+    (0.9, 0.1, 0.3),
+    (0.2, 0.3, 1.0),
+];
+
+// Maps `t` in `[0, 1]` to a gradient going from blue to green to red.
+fn speed_color(t: f32) -> Point3<f32> {
+    let t = t.max(0.0).min(1.0);
+
+    if t < 0.5 {
+        Point3::new(0.0, t * 2.0, 1.0 - t * 2.0)
+    } else {
+        Point3::new(t * 2.0 - 1.0, 2.0 - t * 2.0, 0.0)
+    }
+}
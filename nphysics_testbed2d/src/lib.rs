@@ -10,11 +10,15 @@ extern crate time;
 #[macro_use]
 extern crate log;
 
-pub use crate::engine::GraphicsManager;
+pub use crate::engine::{ColorMode, GraphicsManager, Theme};
+pub use crate::replay::Replay;
+pub use crate::scene::TestbedScene;
 pub use crate::testbed::Testbed;
 pub use crate::world_owner::WorldOwner;
 
 mod engine;
 pub mod objects;
+mod replay;
+mod scene;
 mod testbed;
 mod world_owner;
@@ -9,13 +9,15 @@ pub struct HeightField {
     base_color: Point3<f32>,
     vertices: Vec<Point2<f32>>,
     collider: ColliderHandle,
+    delta: Isometry2<f32>,
+    pos: Isometry2<f32>,
 }
 
 impl HeightField {
     pub fn new(
         collider: ColliderHandle,
         world: &World<f32>,
-        _: Isometry2<f32>,
+        delta: Isometry2<f32>,
         heightfield: &shape::HeightField<f32>,
         color: Point3<f32>,
         _: &mut Window,
@@ -35,6 +37,8 @@ impl HeightField {
             base_color: color,
             vertices,
             collider,
+            delta,
+            pos: Isometry2::identity(),
         };
 
         res.update(world);
@@ -54,7 +58,10 @@ impl HeightField {
         self.base_color = color;
     }
 
-    pub fn update(&mut self, _: &World<f32>) {
+    pub fn update(&mut self, world: &World<f32>) {
+        if let Some(c) = world.collider(self.collider) {
+            self.pos = c.position() * self.delta;
+        }
     }
 
     pub fn object(&self) -> ColliderHandle {
@@ -63,7 +70,9 @@ impl HeightField {
 
     pub fn draw(&mut self, window: &mut Window) {
         for vtx in self.vertices.chunks(2) {
-            window.draw_planar_line(&vtx[0], &vtx[1], &self.color)
+            let p1 = self.pos * vtx[0];
+            let p2 = self.pos * vtx[1];
+            window.draw_planar_line(&p1, &p2, &self.color)
         }
     }
 }
@@ -10,6 +10,7 @@ pub struct Polyline {
     vertices: Vec<Point2<f32>>,
     indices: Vec<Point2<usize>>,
     collider: ColliderHandle,
+    delta: Isometry2<f32>,
     pos: Isometry2<f32>
 }
 
@@ -17,7 +18,7 @@ impl Polyline {
     pub fn new(
         collider: ColliderHandle,
         world: &World<f32>,
-        _: Isometry2<f32>,
+        delta: Isometry2<f32>,
         vertices: Vec<Point2<f32>>,
         indices: Vec<Point2<usize>>,
         color: Point3<f32>,
@@ -30,6 +31,7 @@ impl Polyline {
             vertices,
             indices,
             collider,
+            delta,
         };
 
         res.update(world);
@@ -53,7 +55,7 @@ impl Polyline {
         // Update if some deformation occurred.
         // FIXME: don't update if it did not move.
         if let Some(c) = world.collider(self.collider) {
-            self.pos = *c.position();
+            self.pos = c.position() * self.delta;
             if let ColliderAnchor::OnDeformableBody { .. } = c.anchor() {
                 let shape = c.shape().as_shape::<shape::Polyline<f32>>().unwrap();
                 self.vertices = shape.points().to_vec();
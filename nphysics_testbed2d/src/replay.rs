@@ -0,0 +1,120 @@
+use na::{Isometry2, Vector2};
+use nphysics2d::world::World;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+const HEADER: &str = "nphysics2d-replay 1";
+
+/// A timeline of the positions of the rigid bodies of a world.
+///
+/// Bodies are identified by the unique identifier of their handle so that a recording can be
+/// played back on a world rebuilt from the same scene.
+pub struct Replay {
+    frames: Vec<Vec<(u64, Isometry2<f32>)>>,
+}
+
+impl Replay {
+    /// Creates an empty timeline.
+    pub fn new() -> Self {
+        Replay { frames: Vec::new() }
+    }
+
+    /// The number of frames recorded.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frame has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Removes all the recorded frames.
+    pub fn clear(&mut self) {
+        self.frames.clear()
+    }
+
+    /// Appends the current positions of all the rigid bodies of `world` to the timeline.
+    pub fn record(&mut self, world: &World<f32>) {
+        let frame = world
+            .bodies()
+            .filter_map(|b| world.rigid_body(b.handle()))
+            .map(|rb| (rb.handle().uid(), *rb.position()))
+            .collect();
+        self.frames.push(frame)
+    }
+
+    /// Moves the rigid bodies of `world` to the positions they had at the given frame.
+    ///
+    /// Bodies that do not exist in `world` are ignored.
+    pub fn apply(&self, frame: usize, world: &mut World<f32>) {
+        if let Some(frame) = self.frames.get(frame) {
+            let handles: HashMap<_, _> = world.bodies().map(|b| (b.handle().uid(), b.handle())).collect();
+
+            for (uid, pos) in frame {
+                if let Some(handle) = handles.get(uid) {
+                    world.teleport_rigid_body(*handle, *pos);
+                }
+            }
+        }
+    }
+
+    /// Writes this timeline to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{}", HEADER)?;
+
+        for frame in &self.frames {
+            writeln!(out, "frame")?;
+
+            for (uid, pos) in frame {
+                let t = pos.translation.vector;
+                writeln!(out, "{} {} {} {}", uid, t.x, t.y, pos.rotation.angle())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a timeline from a file written by `Replay::save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let input = BufReader::new(File::open(path)?);
+        let mut lines = input.lines();
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        match lines.next() {
+            Some(Ok(ref header)) if header == HEADER => {}
+            _ => return Err(invalid("invalid replay header")),
+        }
+
+        let mut frames = Vec::new();
+
+        for line in lines {
+            let line = line?;
+
+            if line == "frame" {
+                frames.push(Vec::new());
+                continue;
+            }
+
+            let frame: &mut Vec<_> = frames.last_mut().ok_or_else(|| invalid("missing frame marker"))?;
+            let mut words = line.split_whitespace();
+            let uid = words
+                .next()
+                .and_then(|w| w.parse().ok())
+                .ok_or_else(|| invalid("invalid body identifier"))?;
+            let coords: Vec<f32> = words.filter_map(|w| w.parse().ok()).collect();
+
+            if coords.len() != 3 {
+                return Err(invalid("invalid body position"));
+            }
+
+            let pos = Isometry2::new(Vector2::new(coords[0], coords[1]), coords[2]);
+            frame.push((uid, pos));
+        }
+
+        Ok(Replay { frames })
+    }
+}
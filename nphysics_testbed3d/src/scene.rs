@@ -0,0 +1,18 @@
+use crate::engine::GraphicsManager;
+use nphysics3d::world::World;
+
+/// A demo scene the testbed can build, and build again, at runtime.
+pub trait TestbedScene {
+    /// Adds the bodies, colliders and joints of this scene to an empty world.
+    ///
+    /// The graphics of the colliders are created by the testbed once this returns. The graphics
+    /// manager can be used to customize them, or to move the camera with `GraphicsManager::look_at`.
+    /// The camera is left as is otherwise.
+    fn build(&self, world: &mut World<f32>, graphics: &mut GraphicsManager);
+}
+
+impl<F: Fn(&mut World<f32>, &mut GraphicsManager)> TestbedScene for F {
+    fn build(&self, world: &mut World<f32>, graphics: &mut GraphicsManager) {
+        self(world, graphics)
+    }
+}
@@ -1,12 +1,12 @@
 use num::Bounded;
-use std::collections::HashMap;
 use std::env;
 use std::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use crate::engine::GraphicsManager;
+use crate::engine::{ColorMode, GraphicsManager, Theme};
 use kiss3d::camera::Camera;
 use kiss3d::event::{Action, Key, Modifiers, WindowEvent};
 use kiss3d::light::Light;
@@ -16,13 +16,16 @@ use kiss3d::post_processing::PostProcessingEffect;
 use kiss3d::text::Font;
 use kiss3d::window::{State, Window};
 use na::{self, Point2, Point3, Vector3};
+use rand::Rng;
 use ncollide3d::query::{self, Ray};
-use ncollide3d::utils::GenerationalId;
+use ncollide3d::shape::{Ball, Capsule, Cuboid, ShapeHandle};
 use ncollide3d::world::CollisionGroups;
 use nphysics3d::joint::{ConstraintHandle, MouseConstraint};
-use nphysics3d::object::{BodyHandle, BodyPartHandle, ColliderHandle};
+use nphysics3d::object::{BodyHandle, BodyPartHandle, ColliderDesc, ColliderHandle, RigidBodyDesc};
 use nphysics3d::world::World;
-use nphysics3d::math::ForceType;
+use nphysics3d::math::{ForceType, Vector};
+use crate::replay::Replay;
+use crate::scene::TestbedScene;
 use crate::world_owner::WorldOwner;
 
 #[derive(PartialEq)]
@@ -42,10 +45,11 @@ fn usage(exe_name: &str) {
     println!();
     println!("The following keyboard commands are supported:");
     println!("    t      - pause/continue the simulation.");
-    println!("    s      - pause then execute only one simulation step.");
-    println!("    1      - launch a ball.");
-    println!("    2      - launch a cube.");
-    println!("    3      - launch a fast cube using continuous collision detection.");
+    println!("    s, right - pause then execute only one simulation step.");
+    println!("    +/-    - double/halve the simulation speed.");
+    println!("    1      - spawn a ball at the cursor.");
+    println!("    2      - spawn a box at the cursor.");
+    println!("    3      - spawn a capsule at the cursor.");
     println!("    TAB    - switch camera mode (first-person or arc-ball).");
     println!("    SHIFT + right click - launch a fast cube using continuous collision detection.");
     println!(
@@ -55,6 +59,18 @@ fn usage(exe_name: &str) {
     println!("    arrows - move around when in first-person camera mode.");
     println!("    space  - switch wireframe mode. When ON, the contacts points and normals are displayed.");
     println!("    b      - draw the bounding boxes.");
+    println!("    p      - save a screenshot.");
+    println!("    r      - start/stop recording the positions of the bodies.");
+    println!("    home   - enter/leave the playback of the recording. Use left/right to scrub it.");
+    println!("    F5/F9  - save/load the recording.");
+    println!("    v      - draw the centers of mass and velocities of the rigid bodies.");
+    println!("    i      - show/hide the FPS, step time and active body count.");
+    println!("    c      - cycle through the default colors, the coloring by sleep state, and by speed.");
+    println!("    l      - cycle through the light, dark, and paper color themes.");
+    println!("    n      - show/hide the labels of the bodies.");
+    println!("    j      - show/hide the joints and their anchors.");
+    println!("    delete - remove all the bodies, joints and colliders.");
+    println!("    page up/page down - rebuild the world with the previous/next scene.");
 }
 
 #[cfg(feature = "log")]
@@ -67,10 +83,11 @@ fn usage(exe_name: &str) {
     info!("");
     info!("The following keyboard commands are supported:");
     info!("    t      - pause/continue the simulation.");
-    info!("    s      - pause then execute only one simulation step.");
-    info!("    1      - launch a ball.");
-    info!("    2      - launch a cube.");
-    info!("    3      - launch a fast cube using continuous collision detection.");
+    info!("    s, right - pause then execute only one simulation step.");
+    info!("    +/-    - double/halve the simulation speed.");
+    info!("    1      - spawn a ball at the cursor.");
+    info!("    2      - spawn a box at the cursor.");
+    info!("    3      - spawn a capsule at the cursor.");
     info!("    TAB    - switch camera mode (first-person or arc-ball).");
     info!("    SHIFT + right click - launch a fast cube using continuous collision detection.");
     info!("    CTRL + left click + drag - select and drag an object using a ball-in-socket joint.");
@@ -78,6 +95,18 @@ fn usage(exe_name: &str) {
     info!("    arrows - move around when in first-person camera mode.");
     info!("    space  - switch wireframe mode. When ON, the contacts points and normals are displayed.");
     info!("    b      - draw the bounding boxes.");
+    info!("    p      - save a screenshot.");
+    info!("    r      - start/stop recording the positions of the bodies.");
+    info!("    home   - enter/leave the playback of the recording. Use left/right to scrub it.");
+    info!("    F5/F9  - save/load the recording.");
+    info!("    v      - draw the centers of mass and velocities of the rigid bodies.");
+    info!("    i      - show/hide the FPS, step time and active body count.");
+    info!("    c      - cycle through the default colors, the coloring by sleep state, and by speed.");
+    info!("    l      - cycle through the light, dark, and paper color themes.");
+    info!("    n      - show/hide the labels of the bodies.");
+    info!("    j      - show/hide the joints and their anchors.");
+    info!("    delete - remove all the bodies, joints and colliders.");
+    info!("    page up/page down - rebuild the world with the previous/next scene.");
 }
 
 pub struct Testbed {
@@ -85,10 +114,22 @@ pub struct Testbed {
     window: Option<Box<Window>>,
     graphics: GraphicsManager,
     nsteps: usize,
+    speed: f32,
+    screenshot_dir: PathBuf,
+    replay: Replay,
+    replay_path: PathBuf,
+    recording: bool,
+    playback_frame: Option<usize>,
+    scenes: Vec<(String, Box<TestbedScene>)>,
+    scene: Option<usize>,
+    last_frame: Instant,
     callbacks: Callbacks,
     time: f32,
     hide_counters: bool,
-    persistant_contacts: HashMap<GenerationalId, bool>,
+    show_stats: bool,
+    stats_text: String,
+    stats_nframes: usize,
+    stats_elapsed: f32,
 
     font: Rc<Font>,
     running: RunMode,
@@ -96,6 +137,7 @@ pub struct Testbed {
     cursor_pos: Point2<f32>,
     grabbed_object: Option<BodyPartHandle>,
     grabbed_object_constraint: Option<ConstraintHandle>,
+    grab_strength: f32,
     grabbed_object_plane: (Point3<f32>, Vector3<f32>),
 }
 
@@ -107,7 +149,8 @@ impl Testbed {
         let world = World::new();
 
         let mut window = Box::new(Window::new("nphysics: 3d demo"));
-        window.set_background_color(0.9, 0.9, 0.9);
+        let bg = graphics.theme().background;
+        window.set_background_color(bg.x, bg.y, bg.z);
         window.set_framerate_limit(Some(60));
         window.set_light(Light::StickToCamera);
 
@@ -117,15 +160,28 @@ impl Testbed {
             window: Some(window),
             graphics,
             nsteps: 1,
+            speed: 1.0,
+            screenshot_dir: PathBuf::from("."),
+            replay: Replay::new(),
+            replay_path: PathBuf::from("replay.txt"),
+            recording: false,
+            playback_frame: None,
+            scenes: Vec::new(),
+            scene: None,
+            last_frame: Instant::now(),
             time: 0.0,
             hide_counters: true,
-            persistant_contacts: HashMap::new(),
+            show_stats: true,
+            stats_text: String::new(),
+            stats_nframes: 0,
+            stats_elapsed: 0.0,
             font: Font::default(),
             running: RunMode::Running,
             draw_colls: false,
             cursor_pos: Point2::new(0.0f32, 0.0),
             grabbed_object: None,
             grabbed_object_constraint: None,
+            grab_strength: 1.0,
             grabbed_object_plane: (Point3::origin(), na::zero()),
         }
     }
@@ -141,10 +197,121 @@ impl Testbed {
         res
     }
 
+    /// Sets the factor by which the simulated time is multiplied at each frame.
+    ///
+    /// The world is stepped with a fixed timestep, so this amounts to running this number of
+    /// times more steps per frame.
     pub fn set_number_of_steps_per_frame(&mut self, nsteps: usize) {
         self.nsteps = nsteps
     }
 
+    /// Sets the maximum impulse applied at each step to drag the body grabbed with the mouse.
+    ///
+    /// Smaller values make heavy bodies follow the cursor less tightly.
+    pub fn set_grab_strength(&mut self, strength: f32) {
+        self.grab_strength = strength
+    }
+
+    /// Sets the factor by which the elapsed real time is multiplied before being simulated.
+    pub fn set_simulation_speed(&mut self, speed: f32) {
+        self.speed = speed
+    }
+
+    /// Sets the directory where the screenshots taken with the `p` key are saved.
+    pub fn set_screenshot_dir<P: Into<PathBuf>>(&mut self, dir: P) {
+        self.screenshot_dir = dir.into()
+    }
+
+    fn take_screenshot(&self, window: &Window) {
+        let elapsed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let name = format!(
+            "nphysics_{}_{:03}.png",
+            elapsed.as_secs(),
+            elapsed.subsec_millis()
+        );
+        let path = self.screenshot_dir.join(name);
+
+        match window.snap_image().save(&path) {
+            Ok(_) => println!("Screenshot saved to {}", path.display()),
+            Err(e) => println!("Failed to save the screenshot to {}: {}", path.display(), e),
+        }
+    }
+
+    /// Adds a dynamic rigid body with the given shape and a unit density under the cursor.
+    ///
+    /// The body is placed slightly in front of the first collider hit by the ray cast from the cursor,
+    /// or at a fixed distance from the camera if there is none.
+    fn spawn_at_cursor(&mut self, window: &mut Window, shape: ShapeHandle<f32>) {
+        let mut world = self.world.get_mut();
+        let size = window.size();
+        let (pos, dir) = self
+            .graphics
+            .camera()
+            .unproject(&self.cursor_pos, &na::convert(size));
+        let ray = Ray::new(pos, dir);
+
+        let all_groups = CollisionGroups::new();
+        let mut mintoi = SPAWN_DISTANCE;
+        for (co, inter) in world.collider_world().interferences_with_ray(&ray, &all_groups) {
+            if !co.query_type().is_proximity_query() && inter.toi < mintoi {
+                mintoi = inter.toi;
+            }
+        }
+
+        let radius = shape.bounding_sphere(&na::one()).radius();
+        let position = ray.point_at(mintoi) - dir.normalize() * radius;
+        let collider_desc = ColliderDesc::new(shape).density(1.0);
+        let body = RigidBodyDesc::new()
+            .translation(position.coords)
+            .collider(&collider_desc)
+            .build(&mut world)
+            .handle();
+
+        for co in world.collider_world().body_colliders(body) {
+            self.graphics.add(window, co.handle(), &world);
+        }
+    }
+
+    /// Sets the file the replay timeline is saved to with the `F5` key and loaded from with the `F9` key.
+    pub fn set_replay_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.replay_path = path.into()
+    }
+
+    /// Starts recording the positions of the rigid bodies after each frame, discarding any previous recording.
+    pub fn start_recording(&mut self) {
+        self.replay.clear();
+        self.recording = true;
+    }
+
+    /// Stops recording the positions of the rigid bodies.
+    pub fn stop_recording(&mut self) {
+        self.recording = false;
+    }
+
+    /// The recorded timeline.
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Replaces the recorded timeline.
+    pub fn set_replay(&mut self, replay: Replay) {
+        self.replay = replay;
+        self.playback_frame = None;
+    }
+
+    fn set_playback_frame(&mut self, frame: usize) {
+        if self.replay.is_empty() {
+            self.playback_frame = None;
+            return;
+        }
+
+        let frame = frame.min(self.replay.len() - 1);
+        self.replay.apply(frame, &mut self.world.get_mut());
+        self.playback_frame = Some(frame);
+    }
+
     pub fn hide_performance_counters(&mut self) {
         self.hide_counters = true;
     }
@@ -153,6 +320,30 @@ impl Testbed {
         self.hide_counters = false;
     }
 
+    /// Shows or hides the overlay displaying the FPS, the last step time, and the number of active bodies.
+    pub fn set_show_stats(&mut self, show: bool) {
+        self.show_stats = show;
+    }
+
+    fn update_stats(&mut self, frame_time: f32) {
+        self.stats_nframes += 1;
+        self.stats_elapsed += frame_time;
+
+        if self.stats_elapsed >= STATS_REFRESH_INTERVAL {
+            let world = self.world.get();
+            let counters = world.performance_counters();
+
+            self.stats_text = format!(
+                "FPS: {:.0} - Step time: {:.2}ms - Active bodies: {}",
+                self.stats_nframes as f32 / self.stats_elapsed,
+                counters.step_time() * 1000.0,
+                counters.solver().nactive_bodies
+            );
+            self.stats_nframes = 0;
+            self.stats_elapsed = 0.0;
+        }
+    }
+
     pub fn set_world(&mut self, world: World<f32>) {
         self.set_world_owner(Box::new(world))
     }
@@ -170,10 +361,107 @@ impl Testbed {
         }
     }
 
+    /// Removes all the bodies, joints and colliders of the world, as well as their graphics.
+    ///
+    /// The recording is discarded since it refers to the removed bodies.
+    pub fn clear_world(&mut self) {
+        let mut window = self.window.take().unwrap();
+        self.clear_world_in_window(&mut window);
+        self.window = Some(window);
+    }
+
+    fn clear_world_in_window(&mut self, window: &mut Window) {
+        self.world.get_mut().clear();
+        self.graphics.clear(window);
+        self.graphics.clear_body_settings();
+        self.grabbed_object = None;
+        self.grabbed_object_constraint = None;
+        self.stop_recording();
+        self.replay.clear();
+        self.playback_frame = None;
+    }
+
+    /// Adds a scene that can be selected at runtime with the page up and page down keys.
+    ///
+    /// The first scene added replaces the current content of the world right away.
+    pub fn add_scene<S: TestbedScene + 'static>(&mut self, name: &str, scene: S) {
+        self.scenes.push((name.to_string(), Box::new(scene)));
+
+        if self.scene.is_none() {
+            self.set_scene(0)
+        }
+    }
+
+    /// Clears the world and builds the `i`-th scene added with `add_scene`.
+    ///
+    /// The callbacks and the camera are kept, unless the scene moves the camera itself.
+    pub fn set_scene(&mut self, i: usize) {
+        let mut window = self.window.take().unwrap();
+        self.set_scene_in_window(&mut window, i);
+        self.window = Some(window);
+    }
+
+    fn set_scene_in_window(&mut self, window: &mut Window, i: usize) {
+        self.clear_world_in_window(window);
+
+        {
+            let mut world = self.world.get_mut();
+            let (ref name, ref scene) = self.scenes[i];
+
+            scene.build(&mut world, &mut self.graphics);
+
+            for co in world.colliders() {
+                self.graphics.add(window, co.handle(), &world);
+            }
+
+            println!("Scene {}/{}: {}", i + 1, self.scenes.len(), name);
+        }
+
+        self.scene = Some(i);
+    }
+
     pub fn look_at(&mut self, eye: Point3<f32>, at: Point3<f32>) {
         self.graphics.look_at(eye, at);
     }
 
+    /// Sets the linear speed drawn in red when coloring the bodies by speed.
+    pub fn set_max_color_speed(&mut self, speed: f32) {
+        self.graphics.set_max_color_speed(speed)
+    }
+
+    /// Sets the text label drawn next to the given body.
+    pub fn set_body_label(&mut self, body: BodyHandle, label: &str) {
+        self.graphics.set_body_label(body, label)
+    }
+
+    /// Shows or hides the labels of the bodies.
+    pub fn set_show_labels(&mut self, show: bool) {
+        self.graphics.set_draw_labels(show)
+    }
+
+    /// Shows or hides the joints and their anchors.
+    pub fn set_show_joints(&mut self, show: bool) {
+        self.graphics.set_draw_joints(show)
+    }
+
+    /// Reseeds the random generator the body colors are drawn from.
+    ///
+    /// Only the bodies added afterwards, e.g., with `self.set_world(...)`, are affected. Identical seeds,
+    /// together with an identical order of addition of the bodies, result in identical colors.
+    pub fn set_color_seed(&mut self, seed: u64) {
+        self.graphics.set_seed(seed)
+    }
+
+    /// Sets the background, text, and body colors.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.graphics.set_theme(theme)
+    }
+
+    /// Makes the camera follow the given body, or navigate freely if `None`.
+    pub fn follow(&mut self, body: Option<BodyHandle>) {
+        self.graphics.follow(body)
+    }
+
     pub fn set_body_color(&mut self, body: BodyHandle, color: Point3<f32>) {
         self.graphics.set_body_color(body, color);
     }
@@ -317,7 +605,7 @@ impl State for Testbed {
                             if modifier.contains(Modifiers::Control) {
                                 if !body_part.is_ground() {
                                     self.graphics.remove_body_nodes(window, body_part.0);
-                                    physics_world.remove_bodies(&[body_part.0]);
+                                    physics_world.remove_body(body_part.0);
                                 }
                             } else {
                                 physics_world.body_mut(body_part.0)
@@ -372,32 +660,34 @@ impl State for Testbed {
                         if let Some(body_part_handle) = minb {
                             if physics_world.body(body_part_handle.0).unwrap().status_dependent_ndofs() != 0 {
                                 self.grabbed_object = minb;
+
+                                if let Some(joint) = self.grabbed_object_constraint {
+                                    let _ = physics_world.remove_constraint(joint);
+                                }
+
+                                let attach1 = ray.origin + ray.dir * mintoi;
+                                let attach2 = {
+                                    let body = physics_world.body(body_part_handle.0).unwrap();
+                                    let part = body.part(body_part_handle.1).unwrap();
+                                    body.material_point_at_world_point(part, &attach1)
+                                };
+                                let constraint = MouseConstraint::new(
+                                    BodyPartHandle::ground(),
+                                    body_part_handle,
+                                    attach1,
+                                    attach2,
+                                    self.grab_strength,
+                                );
+                                self.grabbed_object_plane = (attach1, -ray.dir);
+                                self.grabbed_object_constraint =
+                                    Some(physics_world.add_constraint(constraint));
+
                                 for n in self
                                     .graphics
                                     .body_nodes_mut(body_part_handle.0)
                                     .unwrap()
                                     .iter_mut()
                                     {
-                                        if let Some(joint) = self.grabbed_object_constraint {
-                                            physics_world.remove_constraint(joint);
-                                        }
-
-                                        let attach1 = ray.origin + ray.dir * mintoi;
-                                        let attach2 = {
-                                            let body = physics_world.body(body_part_handle.0).unwrap();
-                                            let part = body.part(body_part_handle.1).unwrap();
-                                            body.material_point_at_world_point(part, &attach1)
-                                        };
-                                        let constraint = MouseConstraint::new(
-                                            BodyPartHandle::ground(),
-                                            body_part_handle,
-                                            attach1,
-                                            attach2,
-                                            1.0,
-                                        );
-                                        self.grabbed_object_plane = (attach1, -ray.dir);
-                                        self.grabbed_object_constraint =
-                                            Some(physics_world.add_constraint(constraint));
                                         n.select()
                                     }
                             }
@@ -456,6 +746,62 @@ impl State for Testbed {
                         || modifiers.contains(Modifiers::Shift);
                 }
                 //         WindowEvent::Key(Key::Tab, Action::Release, _) => self.graphics.switch_cameras(),
+                WindowEvent::Key(Key::R, Action::Release, _) => {
+                    if self.recording {
+                        self.stop_recording();
+                        println!("Recording stopped: {} frames recorded.", self.replay.len());
+                    } else {
+                        self.start_recording();
+                        println!("Recording started.");
+                    }
+                }
+                WindowEvent::Key(Key::Home, Action::Release, _) => {
+                    if self.playback_frame.is_some() {
+                        self.playback_frame = None;
+                    } else {
+                        self.recording = false;
+                        self.running = RunMode::Stop;
+                        self.set_playback_frame(0);
+                    }
+                }
+                WindowEvent::Key(Key::Left, Action::Press, _) => {
+                    if let Some(frame) = self.playback_frame {
+                        self.set_playback_frame(frame.saturating_sub(1));
+                    }
+                }
+                WindowEvent::Key(Key::Right, Action::Press, _) => {
+                    if let Some(frame) = self.playback_frame {
+                        self.set_playback_frame(frame + 1);
+                    }
+                }
+                WindowEvent::Key(Key::F5, Action::Release, _) => {
+                    match self.replay.save(&self.replay_path) {
+                        Ok(_) => println!("Replay saved to {}", self.replay_path.display()),
+                        Err(e) => println!("Failed to save the replay to {}: {}", self.replay_path.display(), e),
+                    }
+                }
+                WindowEvent::Key(Key::Delete, Action::Release, _) => self.clear_world_in_window(window),
+                WindowEvent::Key(Key::PageUp, Action::Release, _) => {
+                    if let Some(i) = self.scene {
+                        let nscenes = self.scenes.len();
+                        self.set_scene_in_window(window, (i + nscenes - 1) % nscenes);
+                    }
+                }
+                WindowEvent::Key(Key::PageDown, Action::Release, _) => {
+                    if let Some(i) = self.scene {
+                        let nscenes = self.scenes.len();
+                        self.set_scene_in_window(window, (i + 1) % nscenes);
+                    }
+                }
+                WindowEvent::Key(Key::F9, Action::Release, _) => {
+                    match Replay::load(&self.replay_path) {
+                        Ok(replay) => {
+                            println!("Replay loaded from {}: {} frames.", self.replay_path.display(), replay.len());
+                            self.set_replay(replay);
+                        }
+                        Err(e) => println!("Failed to load the replay from {}: {}", self.replay_path.display(), e),
+                    }
+                }
                 WindowEvent::Key(Key::T, Action::Release, _) => {
                     if self.running == RunMode::Stop {
                         self.running = RunMode::Running;
@@ -464,20 +810,64 @@ impl State for Testbed {
                     }
                 }
                 WindowEvent::Key(Key::S, Action::Release, _) => self.running = RunMode::Step,
-                //         WindowEvent::Key(Key::B, _, Action::Release, _) => {
-                //             // XXX: there is a bug on kiss3d with the removal of objects.
-                //             // draw_aabbs = !draw_aabbs;
-                //             // if draw_aabbs {
-                //             //     graphics.enable_aabb_draw(window);
-                //             // }
-                //             // else {
-                //             //     graphics.disable_aabb_draw(window);
-                //             // }
-                //         },
+                WindowEvent::Key(Key::Right, Action::Release, _) if self.playback_frame.is_none() => {
+                    self.running = RunMode::Step
+                }
+                WindowEvent::Key(Key::Equals, Action::Release, _) |
+                WindowEvent::Key(Key::Add, Action::Release, _) => {
+                    self.speed = (self.speed * 2.0).min(MAX_SIMULATION_SPEED)
+                }
+                WindowEvent::Key(Key::Minus, Action::Release, _) |
+                WindowEvent::Key(Key::Subtract, Action::Release, _) => {
+                    self.speed = (self.speed / 2.0).max(MIN_SIMULATION_SPEED)
+                }
+                WindowEvent::Key(Key::P, Action::Release, _) => self.take_screenshot(window),
+                WindowEvent::Key(Key::B, Action::Release, _) => {
+                    let draw_aabbs = !self.graphics.draw_aabbs();
+                    self.graphics.set_draw_aabbs(draw_aabbs);
+                }
+                WindowEvent::Key(Key::Key1, Action::Release, _) => {
+                    let shape = ShapeHandle::new(Ball::new(random_spawn_size()));
+                    self.spawn_at_cursor(window, shape);
+                }
+                WindowEvent::Key(Key::Key2, Action::Release, _) => {
+                    let shape = ShapeHandle::new(Cuboid::new(Vector::repeat(random_spawn_size())));
+                    self.spawn_at_cursor(window, shape);
+                }
+                WindowEvent::Key(Key::Key3, Action::Release, _) => {
+                    let shape = ShapeHandle::new(Capsule::new(random_spawn_size(), random_spawn_size() * 0.5));
+                    self.spawn_at_cursor(window, shape);
+                }
+                WindowEvent::Key(Key::C, Action::Release, _) => {
+                    let mode = match self.graphics.color_mode() {
+                        ColorMode::Default => ColorMode::SleepState,
+                        ColorMode::SleepState => ColorMode::Speed,
+                        ColorMode::Speed => ColorMode::Default,
+                    };
+                    self.graphics.set_color_mode(mode);
+                }
+                WindowEvent::Key(Key::I, Action::Release, _) => self.show_stats = !self.show_stats,
+                WindowEvent::Key(Key::L, Action::Release, _) => {
+                    let theme = self.graphics.theme().next_preset();
+                    self.graphics.set_theme(theme);
+                }
+                WindowEvent::Key(Key::N, Action::Release, _) => {
+                    let draw_labels = !self.graphics.draw_labels();
+                    self.graphics.set_draw_labels(draw_labels);
+                }
+                WindowEvent::Key(Key::J, Action::Release, _) => {
+                    let draw_joints = !self.graphics.draw_joints();
+                    self.graphics.set_draw_joints(draw_joints);
+                }
+                WindowEvent::Key(Key::V, Action::Release, _) => {
+                    let draw_velocities = !self.graphics.draw_velocities();
+                    self.graphics.set_draw_velocities(draw_velocities);
+                }
                 WindowEvent::Key(Key::Space, Action::Release, _) => {
                     let physics_world = &mut self.world.get_mut();
 
                     self.draw_colls = !self.draw_colls;
+                    self.graphics.set_draw_contacts(self.draw_colls);
                     for co in physics_world.colliders() {
                         // FIXME: ugly clone.
                         if let Some(ns) =
@@ -541,94 +931,82 @@ impl State for Testbed {
             }
         }
 
-        if self.running != RunMode::Stop {
-            // let before = time::precise_time_s();
-            for _ in 0..self.nsteps {
-                for f in &self.callbacks {
-                    f(&mut *self.world, &mut self.graphics, self.time)
-                }
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_frame);
+        let frame_time = elapsed.as_secs() as f32 + elapsed.subsec_nanos() as f32 * 1.0e-9;
+        self.last_frame = now;
+
+        // The physics is not stepped while playing a recording back.
+        if self.running != RunMode::Stop && self.playback_frame.is_none() {
+            for f in &self.callbacks {
+                f(&mut *self.world, &mut self.graphics, self.time)
+            }
 
-                let mut world = self.world.get_mut();
+            // A single step is executed at a time when stepping manually. Otherwise, the accumulator
+            // runs as many fixed timesteps as needed to catch up with the elapsed time.
+            let mut world = self.world.get_mut();
+            let nsteps = if self.running == RunMode::Step {
                 world.step();
-                if !self.hide_counters {
-                    #[cfg(not(feature = "log"))]
-                    println!("{}", world.performance_counters());
-                    #[cfg(feature = "log")]
-                    debug!("{}", world.performance_counters());
-                }
-                self.time += world.timestep();
+                1
+            } else {
+                world.step_with_accumulator(frame_time * self.speed * self.nsteps as f32)
+            };
+            if !self.hide_counters {
+                #[cfg(not(feature = "log"))]
+                println!("{}", world.performance_counters());
+                #[cfg(feature = "log")]
+                debug!("{}", world.performance_counters());
             }
+            self.time += world.timestep() * nsteps as f32;
 
-            self.graphics.draw(&self.world.get(), window);
+            if self.recording && nsteps != 0 {
+                self.replay.record(&world);
+            }
         }
 
-        if self.draw_colls {
-            draw_collisions(
-                window,
-                &self.world.get(),
-                &mut self.persistant_contacts,
-                self.running != RunMode::Stop,
-            );
-        }
+        self.graphics.draw(&self.world.get(), window);
 
         if self.running == RunMode::Step {
             self.running = RunMode::Stop;
         }
 
-        let color = Point3::new(0.0, 0.0, 0.0);
-
-        if true {
-            //running != RunMode::Stop {
-            window.draw_text(
-                &format!(
-                    "Simulation time: {:.*}sec.",
-                    4,
-                    self.world.get().performance_counters().step_time()
-                )[..],
-                &Point2::origin(),
-                60.0,
-                &self.font,
-                &color,
-            );
-        } else {
-            window.draw_text("Paused", &Point2::origin(), 60.0, &self.font, &color);
+        let color = self.graphics.theme().text;
+
+        self.update_stats(frame_time);
+
+        if self.show_stats {
+            window.draw_text(&self.stats_text, &Point2::origin(), 60.0, &self.font, &color);
         }
         window.draw_text(CONTROLS, &Point2::new(0.0, 75.0), 40.0, &self.font, &color);
     }
 }
 
+const MIN_SIMULATION_SPEED: f32 = 1.0 / 16.0;
+const MAX_SIMULATION_SPEED: f32 = 16.0;
+// Range of the random sizes of the bodies spawned at the cursor.
+const MIN_SPAWN_SIZE: f32 = 0.2;
+const MAX_SPAWN_SIZE: f32 = 0.6;
+// Distance from the camera of the bodies spawned when the cursor does not point at any collider.
+const SPAWN_DISTANCE: f32 = 10.0;
+// Interval in seconds between two refreshes of the stats overlay.
+const STATS_REFRESH_INTERVAL: f32 = 0.25;
+
+fn random_spawn_size() -> f32 {
+    rand::thread_rng().gen_range(MIN_SPAWN_SIZE, MAX_SPAWN_SIZE)
+}
+
 const CONTROLS: &str = "Controls:
     Ctrl + click + drag: select and move a solid.
     Left click + drag: rotate the camera.
     Right click + drag: pan the camera.
     Mouse wheel: zoom in/zoom out.
     T: pause/resume simulation.
-    S: step simulation.";
-
-fn draw_collisions(
-    window: &mut Window,
-    world: &World<f32>,
-    existing: &mut HashMap<GenerationalId, bool>,
-    running: bool,
-) {
-    for (_, _, _, manifold) in world.collider_world().contact_pairs(false) {
-        for c in manifold.contacts() {
-            existing
-                .entry(c.id)
-                .and_modify(|value| {
-                    if running {
-                        *value = true
-                    }
-                })
-                .or_insert(false);
-
-            let color = if c.contact.depth < 0.0 { // existing[&c.id] {
-                Point3::new(0.0, 0.0, 1.0)
-            } else {
-                Point3::new(1.0, 0.0, 0.0)
-            };
-
-            window.draw_line(&(c.contact.world1), &c.contact.world2, &color);
-        }
-    }
-}
+    S: step simulation.
+    +/-: double/halve the simulation speed.
+    1/2/3: spawn a ball/box/capsule at the cursor.
+    I: show/hide the stats.
+    N: show/hide the body labels.
+    J: show/hide the joints.
+    Delete: remove all the bodies.
+    PgUp/PgDn: switch to the previous/next scene.
+    L: cycle the color themes.";
@@ -6,10 +6,16 @@ use std::fmt::{Display, Formatter, Result};
 pub struct CollisionDetectionCounters {
     /// Number of contact pairs detected.
     pub ncontact_pairs: usize,
+    /// Number of potentially interacting pairs found by the broad phase.
+    pub ninteraction_pairs: usize,
     /// Time spent for the broad-phase of the collision detection.
     pub broad_phase_time: Timer,
     /// Time spent for the narrow-phase of the collision detection.
     pub narrow_phase_time: Timer,
+    /// Largest penetration depth of the contacts at the end of the timestep.
+    pub max_penetration: f64,
+    /// Average penetration depth of the penetrating contacts at the end of the timestep.
+    pub avg_penetration: f64,
 }
 
 impl CollisionDetectionCounters {
@@ -17,8 +23,11 @@ impl CollisionDetectionCounters {
     pub fn new() -> Self {
         CollisionDetectionCounters {
             ncontact_pairs: 0,
+            ninteraction_pairs: 0,
             broad_phase_time: Timer::new(),
             narrow_phase_time: Timer::new(),
+            max_penetration: 0.0,
+            avg_penetration: 0.0,
         }
     }
 }
@@ -26,7 +35,10 @@ impl CollisionDetectionCounters {
 impl Display for CollisionDetectionCounters {
     fn fmt(&self, f: &mut Formatter) -> Result {
         writeln!(f, "Number of contact pairs: {}", self.ncontact_pairs)?;
+        writeln!(f, "Number of interaction pairs: {}", self.ninteraction_pairs)?;
         writeln!(f, "Broad-phase time: {}", self.broad_phase_time)?;
-        writeln!(f, "Narrow-phase time: {}", self.narrow_phase_time)
+        writeln!(f, "Narrow-phase time: {}", self.narrow_phase_time)?;
+        writeln!(f, "Max penetration: {}", self.max_penetration)?;
+        writeln!(f, "Average penetration: {}", self.avg_penetration)
     }
 }
@@ -102,10 +102,57 @@ impl Counters {
     pub fn set_ncontact_pairs(&mut self, n: usize) {
         self.cd.ncontact_pairs = n;
     }
+
+    /// Set the number of interaction pairs found by the broad phase.
+    pub fn set_ninteraction_pairs(&mut self, n: usize) {
+        self.cd.ninteraction_pairs = n;
+    }
+
+    /// Set the largest and average penetration depths of the contacts at the end of the timestep.
+    pub fn set_penetrations(&mut self, max: f64, avg: f64) {
+        self.cd.max_penetration = max;
+        self.cd.avg_penetration = avg;
+    }
+
+    /// Set the number of active bodies involved in the resolution.
+    pub fn set_nactive_bodies(&mut self, n: usize) {
+        self.solver.nactive_bodies = n;
+    }
+
+    /// Set the number of joint constraints solved.
+    pub fn set_njoints(&mut self, n: usize) {
+        self.solver.njoints = n;
+    }
+
+    /// Set the number of iterations run by the velocity and position-based constraints solvers.
+    pub fn set_niterations(&mut self, nvelocity_iterations: usize, nposition_iterations: usize) {
+        self.solver.nvelocity_iterations = nvelocity_iterations;
+        self.solver.nposition_iterations = nposition_iterations;
+    }
+
+    /// Resets the timers of the constraints solver.
+    ///
+    /// The solver then resumes them for each island so that they measure the time spent on all the islands.
+    pub fn reset_solver_timers(&mut self) {
+        self.solver.assembly_time = Timer::new();
+        self.solver.velocity_resolution_time = Timer::new();
+        self.solver.velocity_update_time = Timer::new();
+        self.solver.position_resolution_time = Timer::new();
+    }
+
+    /// Statistics about the constraints resolution of the last timestep.
+    pub fn solver(&self) -> &SolverCounters {
+        &self.solver
+    }
+
+    /// Statistics about the collision detection of the last timestep.
+    pub fn collision_detection(&self) -> &CollisionDetectionCounters {
+        &self.cd
+    }
 }
 
 macro_rules! measure_method {
-    ($started:ident, $stopped:ident, $time:ident, $info:ident. $timer:ident) => {
+    ($started:ident, $resumed:ident, $stopped:ident, $time:ident, $info:ident. $timer:ident) => {
         impl Counters {
             /// Start this timer.
             pub fn $started(&mut self) {
@@ -114,6 +161,13 @@ macro_rules! measure_method {
                 }
             }
 
+            /// Resume this timer without resetting the time it already measured.
+            pub fn $resumed(&mut self) {
+                if self.enabled {
+                    self.$info.$timer.resume()
+                }
+            }
+
             /// Stop this timer.
             pub fn $stopped(&mut self) {
                 if self.enabled {
@@ -135,24 +189,28 @@ macro_rules! measure_method {
 
 measure_method!(
     update_started,
+    update_resumed,
     update_completed,
     update_time,
     stages.update_time
 );
 measure_method!(
     collision_detection_started,
+    collision_detection_resumed,
     collision_detection_completed,
     collision_detection_time,
     stages.collision_detection_time
 );
 measure_method!(
     island_construction_started,
+    island_construction_resumed,
     island_construction_completed,
     island_construction_time,
     stages.island_construction_time
 );
 measure_method!(
     solver_started,
+    solver_resumed,
     solver_completed,
     solver_time,
     stages.solver_time
@@ -160,36 +218,42 @@ measure_method!(
 
 measure_method!(
     assembly_started,
+    assembly_resumed,
     assembly_completed,
     assembly_time,
     solver.assembly_time
 );
 measure_method!(
     velocity_resolution_started,
+    velocity_resolution_resumed,
     velocity_resolution_completed,
     velocity_resolution_time,
     solver.velocity_resolution_time
 );
 measure_method!(
     velocity_update_started,
+    velocity_update_resumed,
     velocity_update_completed,
     velocity_update_time,
     solver.velocity_update_time
 );
 measure_method!(
     position_resolution_started,
+    position_resolution_resumed,
     position_resolution_completed,
     position_resolution_time,
     solver.position_resolution_time
 );
 measure_method!(
     broad_phase_started,
+    broad_phase_resumed,
     broad_phase_completed,
     broad_phase_time,
     cd.broad_phase_time
 );
 measure_method!(
     narrow_phase_started,
+    narrow_phase_resumed,
     narrow_phase_completed,
     narrow_phase_time,
     cd.narrow_phase_time
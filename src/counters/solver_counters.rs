@@ -8,6 +8,14 @@ pub struct SolverCounters {
     pub nconstraints: usize,
     /// Number of contacts found.
     pub ncontacts: usize,
+    /// Number of active bodies involved in the resolution.
+    pub nactive_bodies: usize,
+    /// Number of joint constraints solved.
+    pub njoints: usize,
+    /// Number of iterations run by the velocity constraints solver.
+    pub nvelocity_iterations: usize,
+    /// Number of iterations run by the position-based constraints solver.
+    pub nposition_iterations: usize,
     /// Time spent for the resolution of the constraints (force computation).
     pub velocity_resolution_time: Timer,
     /// Time spent for the assembly of all the constraints into a linear complentarity problem.
@@ -24,6 +32,10 @@ impl SolverCounters {
         SolverCounters {
             nconstraints: 0,
             ncontacts: 0,
+            nactive_bodies: 0,
+            njoints: 0,
+            nvelocity_iterations: 0,
+            nposition_iterations: 0,
             assembly_time: Timer::new(),
             velocity_resolution_time: Timer::new(),
             velocity_update_time: Timer::new(),
@@ -36,6 +48,10 @@ impl Display for SolverCounters {
     fn fmt(&self, f: &mut Formatter) -> Result {
         writeln!(f, "Number of contacts: {}", self.ncontacts)?;
         writeln!(f, "Number of constraints: {}", self.nconstraints)?;
+        writeln!(f, "Number of active bodies: {}", self.nactive_bodies)?;
+        writeln!(f, "Number of joints: {}", self.njoints)?;
+        writeln!(f, "Number of velocity iterations: {}", self.nvelocity_iterations)?;
+        writeln!(f, "Number of position iterations: {}", self.nposition_iterations)?;
         writeln!(f, "Assembly time: {}", self.assembly_time)?;
         writeln!(
             f,
@@ -0,0 +1,16 @@
+use na::Real;
+
+use crate::object::BodyHandle;
+use crate::world::World;
+
+/// Trait implemented by listeners notified whenever a dynamic body falls asleep or wakes up.
+///
+/// Each handler method is called exactly once per body and per transition, at the end of `World::step`.
+/// The world can be queried by the handler but not modified. While the handler is called, it is
+/// temporarily removed from the world.
+pub trait ActivationHandler<N: Real>: Send + Sync {
+    /// Called when a dynamic body that was active at the previous timestep falls asleep.
+    fn handle_sleep(&mut self, world: &World<N>, body: BodyHandle);
+    /// Called when a dynamic body that was asleep at the previous timestep wakes up.
+    fn handle_wake(&mut self, world: &World<N>, body: BodyHandle);
+}
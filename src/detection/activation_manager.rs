@@ -1,9 +1,11 @@
 use slab::Slab;
+use std::collections::HashMap;
 
 use na::{self, Real};
 use crate::world::ColliderWorld;
-use crate::object::{BodyHandle, Body, BodySet};
+use crate::object::{BodyHandle, Body, BodySet, SleepThreshold};
 use crate::joint::JointConstraint;
+use crate::solver::IntegrationParameters;
 use crate::utils::union_find::UnionFindSet;
 use crate::utils::union_find;
 
@@ -16,6 +18,9 @@ pub struct ActivationManager<N: Real> {
     can_deactivate: Vec<bool>,
     to_activate: Vec<BodyHandle>,
     id_to_body: Vec<BodyHandle>,
+    // Time spent at rest by the active bodies with a `SleepThreshold`.
+    rest_times: HashMap<BodyHandle, N>,
+    sleep_threshold: Option<SleepThreshold<N>>,
 }
 
 impl<N: Real> ActivationManager<N> {
@@ -36,9 +41,44 @@ impl<N: Real> ActivationManager<N> {
             can_deactivate: Vec::new(),
             to_activate: Vec::new(),
             id_to_body: Vec::new(),
+            rest_times: HashMap::new(),
+            sleep_threshold: None,
         }
     }
 
+    /// The ratio of the current kinetic energy of a body mixed into its averaged energy at each update.
+    ///
+    /// The larger this factor, the faster a body at rest will fall asleep.
+    pub fn mix_factor(&self) -> N {
+        self.mix_factor
+    }
+
+    /// Sets the ratio of the current kinetic energy of a body mixed into its averaged energy at each update.
+    pub fn set_mix_factor(&mut self, mix_factor: N) {
+        assert!(
+            mix_factor >= na::zero() && mix_factor <= na::one(),
+            "The energy mixing factor must be between 0.0 and 1.0."
+        );
+        self.mix_factor = mix_factor
+    }
+
+    /// The velocity thresholds used by the bodies that do not have their own, if any.
+    pub fn sleep_threshold(&self) -> Option<&SleepThreshold<N>> {
+        self.sleep_threshold.as_ref()
+    }
+
+    /// Sets the velocity thresholds used by the bodies that do not have their own.
+    ///
+    /// A value of `None` (the default) lets those bodies rely on their energy-based deactivation threshold.
+    pub fn set_sleep_threshold(&mut self, threshold: Option<SleepThreshold<N>>) {
+        self.sleep_threshold = threshold
+    }
+
+    // The velocity thresholds applicable to the given body, if any.
+    fn body_sleep_threshold(&self, body: &Body<N>) -> Option<SleepThreshold<N>> {
+        body.activation_status().sleep_threshold().or(self.sleep_threshold.as_ref()).cloned()
+    }
+
     /// Notify the `ActivationManager2` that is has to activate an object at the next update.
     // FIXME: this is not a very good name
     pub fn deferred_activate(&mut self, handle: BodyHandle) {
@@ -58,7 +98,31 @@ impl<N: Real> ActivationManager<N> {
         }
     }
 
+    // Accumulates the time spent by the body with all its parts under the given velocity thresholds.
+    fn update_rest_time(&mut self, body: &Body<N>, threshold: &SleepThreshold<N>, dt: N) {
+        let mut at_rest = true;
+
+        for i in 0..body.num_parts() {
+            let velocity = try_continue!(body.part(i)).velocity();
+            at_rest = at_rest
+                && velocity.linear.norm() <= threshold.linear
+                && velocity.angular_vector().norm() <= threshold.angular;
+        }
+
+        let rest_time = self.rest_times.entry(body.handle()).or_insert_with(N::zero);
+
+        if at_rest {
+            *rest_time += dt;
+        } else {
+            *rest_time = N::zero();
+        }
+    }
+
     /// Update the activation manager, activating and deactivating objects when needed.
+    ///
+    /// The time spent at rest by the bodies with a `SleepThreshold` is measured with the default timestep
+    /// length, whatever the actual timestep length is.
+    #[deprecated(note = "use `update_with_params` which measures the rest time with the actual timestep length")]
     pub fn update(
         &mut self,
         bodies: &mut BodySet<N>,
@@ -66,17 +130,45 @@ impl<N: Real> ActivationManager<N> {
         constraints: &Slab<Box<JointConstraint<N>>>,
         active_bodies: &mut Vec<BodyHandle>,
     ) {
+        self.update_with_params(&IntegrationParameters::default(), bodies, cworld, constraints, active_bodies)
+    }
+
+    /// Update the activation manager, activating and deactivating objects when needed.
+    ///
+    /// The timestep length `params.dt` is used to measure the time spent at rest by the bodies with
+    /// a `SleepThreshold`.
+    pub fn update_with_params(
+        &mut self,
+        params: &IntegrationParameters<N>,
+        bodies: &mut BodySet<N>,
+        cworld: &ColliderWorld<N>,
+        constraints: &Slab<Box<JointConstraint<N>>>,
+        active_bodies: &mut Vec<BodyHandle>,
+    ) {
+        let dt = params.dt;
+
         /*
          *
          * Update bodies energy
          *
          */
         self.id_to_body.clear();
+        let default_threshold = self.sleep_threshold.is_some();
+        self.rest_times.retain(|handle, _| {
+            bodies
+                .body(*handle)
+                .map(|b| b.is_active() && (default_threshold || b.activation_status().sleep_threshold().is_some()))
+                .unwrap_or(false)
+        });
 
         for body in bodies.bodies_mut() {
             if body.status_dependent_ndofs() != 0 {
                 if body.is_active() {
                     self.update_energy(body);
+
+                    if let Some(threshold) = self.body_sleep_threshold(body) {
+                        self.update_rest_time(body, &threshold, dt);
+                    }
                 }
 
                 body.set_companion_id(self.id_to_body.len());
@@ -84,6 +176,17 @@ impl<N: Real> ActivationManager<N> {
             }
 
             if body.is_kinematic() {
+                // A moving kinematic body must never fall asleep on its own.
+                if !body.generalized_velocity().norm_squared().is_zero() {
+                    body.activate();
+                } else if body.is_active() {
+                    self.update_energy(body);
+
+                    if let Some(threshold) = self.body_sleep_threshold(body) {
+                        self.update_rest_time(body, &threshold, dt);
+                    }
+                }
+
                 body.set_companion_id(self.id_to_body.len());
                 self.id_to_body.push(body.handle());
             }
@@ -164,10 +267,16 @@ impl<N: Real> ActivationManager<N> {
             // FIXME: avoid the Copy when NLL lands ?
             let status = *body.activation_status();
 
-            self.can_deactivate[root] = match status.deactivation_threshold() {
-                Some(threshold) => self.can_deactivate[root] && status.energy() < threshold,
-                None => false,
+            // The island can only sleep if each of its bodies can, so the strictest threshold prevails.
+            let can_deactivate = match (status.deactivation_threshold(), self.body_sleep_threshold(body)) {
+                (None, _) => false,
+                (Some(_), Some(threshold)) => {
+                    !status.is_active() || self.rest_times.get(&handle).map(|t| *t >= threshold.time).unwrap_or(false)
+                }
+                (Some(threshold), None) => status.energy() < threshold,
             };
+
+            self.can_deactivate[root] = self.can_deactivate[root] && can_deactivate;
         }
 
         // Activate/deactivate islands.
@@ -180,6 +289,7 @@ impl<N: Real> ActivationManager<N> {
                 // Everybody in this set can be deactivacted.
                 if body.is_active() {
                     body.deactivate();
+                    let _ = self.rest_times.remove(&handle);
                 }
             } else if !body.is_kinematic() {
                 // Everybody in this set must be reactivated.
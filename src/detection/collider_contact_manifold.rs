@@ -2,7 +2,8 @@ use na::Real;
 use ncollide::query::{ContactManifold, TrackedContact};
 use ncollide::shape::FeatureId;
 
-use crate::object::{BodyHandle, BodyPartHandle, Collider, ColliderAnchor};
+use crate::object::{BodyHandle, BodyPartHandle, Collider, ColliderAnchor, ColliderHandle};
+use crate::world::ColliderWorld;
 
 /// A contact manifold between two bodies.
 #[derive(Clone)]
@@ -98,3 +99,62 @@ impl<'a, N: Real> ColliderContactManifold<'a, N> {
         self.collider2.anchor()
     }
 }
+
+/// The handles of the colliders and bodies involved in a contact manifold.
+///
+/// Unlike a `ColliderContactManifold`, this does not borrow the collider world so it can be kept
+/// from one timestep to the next.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ColliderContactManifoldHandles {
+    /// The first collider involved in the contact.
+    pub collider1: ColliderHandle,
+    /// The second collider involved in the contact.
+    pub collider2: ColliderHandle,
+    /// The body the first collider is attached to.
+    pub body1: BodyHandle,
+    /// The body the second collider is attached to.
+    pub body2: BodyHandle,
+}
+
+impl ColliderContactManifoldHandles {
+    /// The handles of the colliders and bodies involved in the given contact manifold.
+    pub fn new<N: Real>(manifold: &ColliderContactManifold<N>) -> Self {
+        ColliderContactManifoldHandles {
+            collider1: manifold.collider1.handle(),
+            collider2: manifold.collider2.handle(),
+            body1: manifold.body1(),
+            body2: manifold.body2(),
+        }
+    }
+}
+
+/// A set of contact manifolds identified by their handles and retrieved from the collider world.
+#[derive(Copy, Clone)]
+pub struct ColliderContactManifolds<'a, N: Real> {
+    cworld: &'a ColliderWorld<N>,
+    handles: &'a [ColliderContactManifoldHandles],
+}
+
+impl<'a, N: Real> ColliderContactManifolds<'a, N> {
+    /// Initialize a set with the contact manifolds of the given collider world identified by `handles`.
+    pub fn new(cworld: &'a ColliderWorld<N>, handles: &'a [ColliderContactManifoldHandles]) -> Self {
+        ColliderContactManifolds { cworld, handles }
+    }
+
+    /// The handles of the contact manifolds of this set.
+    pub fn handles(&self) -> &'a [ColliderContactManifoldHandles] {
+        self.handles
+    }
+
+    /// Iterates through the contact manifolds of this set.
+    ///
+    /// The manifolds that no longer exist in the collider world are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = ColliderContactManifold<'a, N>> {
+        let cworld = self.cworld;
+
+        self.handles.iter().filter_map(move |h| {
+            let (c1, c2, _, manifold) = cworld.contact_pair(h.collider1, h.collider2, false)?;
+            Some(ColliderContactManifold::new(c1, c2, manifold))
+        })
+    }
+}
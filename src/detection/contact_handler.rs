@@ -0,0 +1,70 @@
+use na::Real;
+use ncollide::query::Contact;
+
+use crate::object::{BodyHandle, ColliderHandle};
+
+/// A pair of colliders that started or stopped being in contact.
+#[derive(Clone, Debug)]
+pub struct ContactPair<N: Real> {
+    /// The handle of the first collider involved in the contact.
+    pub collider1: ColliderHandle,
+    /// The handle of the second collider involved in the contact.
+    pub collider2: ColliderHandle,
+    /// The handle of the body the first collider is attached to.
+    pub body1: BodyHandle,
+    /// The handle of the body the second collider is attached to.
+    pub body2: BodyHandle,
+    /// The contacts, expressed in world-space, between the two colliders at the end of the timestep.
+    ///
+    /// This is empty when the two colliders stopped being in contact.
+    pub contacts: Vec<Contact<N>>,
+}
+
+/// The collision between two colliders that started being in contact.
+#[derive(Copy, Clone, Debug)]
+pub struct ContactImpact<N: Real> {
+    /// The handle of the first collider involved in the collision.
+    pub collider1: ColliderHandle,
+    /// The handle of the second collider involved in the collision.
+    pub collider2: ColliderHandle,
+    /// The handle of the body the first collider is attached to.
+    pub body1: BodyHandle,
+    /// The handle of the body the second collider is attached to.
+    pub body2: BodyHandle,
+    /// The sum of the normal impulses applied at all the contacts between the two colliders to resolve the collision.
+    pub normal_impulse: N,
+}
+
+/// A pair of colliders, at least one of them being a sensor, that started or stopped intersecting.
+#[derive(Copy, Clone, Debug)]
+pub struct ProximityPair {
+    /// The handle of the first collider involved in the proximity.
+    pub collider1: ColliderHandle,
+    /// The handle of the second collider involved in the proximity.
+    pub collider2: ColliderHandle,
+    /// The handle of the body the first collider is attached to.
+    pub body1: BodyHandle,
+    /// The handle of the body the second collider is attached to.
+    pub body2: BodyHandle,
+}
+
+/// Trait implemented by listeners notified whenever two colliders start or stop being in contact.
+///
+/// Each handler method is called exactly once per transition, at the end of `World::step`.
+pub trait ContactHandler<N: Real>: Send + Sync {
+    /// Called when two colliders that were not in contact at the previous timestep start touching.
+    fn handle_contact_started(&mut self, pair: &ContactPair<N>);
+    /// Called when two colliders that were in contact at the previous timestep stop touching.
+    fn handle_contact_stopped(&mut self, pair: &ContactPair<N>);
+    /// Called once when the solver first pushes apart two colliders that started touching.
+    ///
+    /// This happens at the end of the first timestep during which a non-zero normal impulse was applied
+    /// between the two colliders since `self.handle_contact_started(...)` was called for them. Thus, the
+    /// impulse measures the strength of the impact, and colliders resting on each other afterwards do not
+    /// trigger this again until they separate and collide anew.
+    fn handle_contact_impact(&mut self, _impact: &ContactImpact<N>) {}
+    /// Called when a sensor starts intersecting another collider.
+    fn handle_proximity_started(&mut self, _pair: &ProximityPair) {}
+    /// Called when a sensor stops intersecting another collider.
+    fn handle_proximity_stopped(&mut self, _pair: &ProximityPair) {}
+}
@@ -0,0 +1,98 @@
+use na::{Real, Unit};
+use ncollide::utils::GenerationalId;
+
+use crate::detection::ColliderContactManifold;
+use crate::math::{Point, Vector};
+use crate::object::{BodyHandle, ColliderHandle};
+use crate::solver::ContactModel;
+
+/// A read-only view of a contact manifold between two colliders, with the impulses applied by the solver.
+///
+/// This is the type of the items returned by `World::contacts`. It borrows the world so it
+/// cannot outlive the next timestep.
+pub struct ContactManifoldInfo<'a, N: Real> {
+    manifold: ColliderContactManifold<'a, N>,
+    contact_model: &'a ContactModel<N>,
+}
+
+impl<'a, N: Real> ContactManifoldInfo<'a, N> {
+    pub(crate) fn new(manifold: ColliderContactManifold<'a, N>, contact_model: &'a ContactModel<N>) -> Self {
+        ContactManifoldInfo {
+            manifold,
+            contact_model,
+        }
+    }
+
+    /// The handle of the first collider involved in the contact.
+    pub fn collider1(&self) -> ColliderHandle {
+        self.manifold.collider1.handle()
+    }
+
+    /// The handle of the second collider involved in the contact.
+    pub fn collider2(&self) -> ColliderHandle {
+        self.manifold.collider2.handle()
+    }
+
+    /// The handle of the first body involved in the contact.
+    pub fn body1(&self) -> BodyHandle {
+        self.manifold.body1()
+    }
+
+    /// The handle of the second body involved in the contact.
+    pub fn body2(&self) -> BodyHandle {
+        self.manifold.body2()
+    }
+
+    /// The number of contacts on the manifold.
+    pub fn len(&self) -> usize {
+        self.manifold.len()
+    }
+
+    /// The underlying contact manifold.
+    pub fn manifold(&self) -> &ColliderContactManifold<'a, N> {
+        &self.manifold
+    }
+
+    /// All the contacts of the manifold.
+    pub fn contacts(&self) -> impl Iterator<Item = ContactInfo<N>> + 'a {
+        let manifold = self.manifold.manifold;
+        let contact_model = self.contact_model;
+
+        manifold.contacts().map(move |c| ContactInfo {
+            id: c.id,
+            world1: c.contact.world1,
+            world2: c.contact.world2,
+            normal: c.contact.normal,
+            depth: c.contact.depth,
+            normal_impulse: contact_model.normal_impulse(c.id),
+            tangent_impulse: contact_model.tangent_impulse(c.id),
+        })
+    }
+
+    /// The contact with the largest penetration depth, if any.
+    pub fn deepest_contact(&self) -> Option<ContactInfo<N>> {
+        self.contacts().fold(None, |deepest: Option<ContactInfo<N>>, c| match deepest {
+            Some(d) if d.depth >= c.depth => Some(d),
+            _ => Some(c),
+        })
+    }
+}
+
+/// A contact point of a `ContactManifoldInfo`.
+#[derive(Copy, Clone, Debug)]
+pub struct ContactInfo<N: Real> {
+    /// The identifier of this contact, persistent as long as the contact exists.
+    pub id: GenerationalId,
+    /// The contact point, in world-space, on the first collider.
+    pub world1: Point<N>,
+    /// The contact point, in world-space, on the second collider.
+    pub world2: Point<N>,
+    /// The contact normal, pointing from the first collider toward the second one.
+    pub normal: Unit<Vector<N>>,
+    /// The penetration depth of this contact. It is negative if the colliders are not touching yet.
+    pub depth: N,
+    /// The normal impulse applied at this contact during the last timestep.
+    pub normal_impulse: N,
+    /// The norm of the friction impulse applied at this contact during the last timestep.
+    pub tangent_impulse: N,
+}
@@ -0,0 +1,188 @@
+use slab::Slab;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use na::Real;
+use crate::detection::ColliderContactManifoldHandles;
+use crate::joint::{ConstraintHandle, JointConstraint};
+use crate::object::{BodyHandle, BodySet};
+use crate::utils::union_find::UnionFindSet;
+use crate::utils::union_find;
+
+/// A group of active bodies interacting with each other through contacts or joints.
+///
+/// The constraints of an island only involve its own bodies, and bodies that are not
+/// dynamic. Thus, each island can be solved independently from the others.
+#[derive(Clone, Debug)]
+pub struct Island {
+    bodies: Vec<BodyHandle>,
+    joints: Vec<ConstraintHandle>,
+    manifolds: Range<usize>,
+}
+
+impl Island {
+    fn new() -> Self {
+        Island {
+            bodies: Vec::new(),
+            joints: Vec::new(),
+            manifolds: 0..0,
+        }
+    }
+
+    /// The bodies of this island.
+    pub fn bodies(&self) -> &[BodyHandle] {
+        &self.bodies[..]
+    }
+
+    /// The joint constraints attached to the bodies of this island.
+    pub fn joints(&self) -> &[ConstraintHandle] {
+        &self.joints[..]
+    }
+
+    /// The range of the contact manifolds of this island on the manifolds sorted by `IslandSet::sort_manifolds`.
+    pub fn manifolds(&self) -> Range<usize> {
+        self.manifolds.clone()
+    }
+}
+
+/// The set of islands built from the contacts and joints of the world at each step.
+///
+/// Only dynamic bodies propagate the connectivity: two bodies touching the same static or
+/// kinematic body, but not each other, belong to different islands.
+pub struct IslandSet {
+    // The islands past `nislands` are kept to reuse their buffers.
+    islands: Vec<Island>,
+    nislands: usize,
+    body_islands: HashMap<BodyHandle, usize>,
+    root_islands: Vec<usize>,
+    ufind: Vec<UnionFindSet>,
+}
+
+impl IslandSet {
+    /// Creates an empty set of islands.
+    pub fn new() -> Self {
+        IslandSet {
+            islands: Vec::new(),
+            nislands: 0,
+            body_islands: HashMap::new(),
+            root_islands: Vec::new(),
+            ufind: Vec::new(),
+        }
+    }
+
+    /// The islands built by the last call to `self.build(...)`.
+    pub fn islands(&self) -> &[Island] {
+        &self.islands[..self.nislands]
+    }
+
+    /// The index of the island containing the given body, if it is active and dynamic.
+    pub fn body_island(&self, handle: BodyHandle) -> Option<usize> {
+        self.body_islands.get(&handle).cloned()
+    }
+
+    /// Splits the given active bodies into islands connected by the given contact manifolds and joints.
+    ///
+    /// The bodies of each island are kept in the same order as `active_bodies`.
+    pub fn build<N: Real>(
+        &mut self,
+        bodies: &BodySet<N>,
+        joints: &Slab<Box<JointConstraint<N>>>,
+        manifolds: &[ColliderContactManifoldHandles],
+        active_bodies: &[BodyHandle],
+    ) {
+        for island in &mut self.islands {
+            island.bodies.clear();
+            island.joints.clear();
+            island.manifolds = 0..0;
+        }
+
+        self.nislands = 0;
+        self.body_islands.clear();
+        self.ufind.clear();
+
+        for (i, handle) in active_bodies.iter().enumerate() {
+            let _ = self.body_islands.insert(*handle, i);
+            self.ufind.push(UnionFindSet::new(i));
+        }
+
+        for m in manifolds {
+            self.union(m.body1, m.body2);
+        }
+
+        for (_, j) in joints.iter() {
+            if j.is_active(bodies) {
+                let (b1, b2) = j.anchors();
+                self.union(b1.0, b2.0);
+            }
+        }
+
+        // Number the islands following the order of the active bodies.
+        self.root_islands.clear();
+        self.root_islands.resize(active_bodies.len(), usize::max_value());
+
+        for (i, handle) in active_bodies.iter().enumerate() {
+            let root = union_find::find(i, &mut self.ufind[..]);
+
+            if self.root_islands[root] == usize::max_value() {
+                self.root_islands[root] = self.nislands;
+
+                if self.nislands == self.islands.len() {
+                    self.islands.push(Island::new());
+                }
+
+                self.nislands += 1;
+            }
+
+            let island = self.root_islands[root];
+            self.islands[island].bodies.push(*handle);
+            let _ = self.body_islands.insert(*handle, island);
+        }
+
+        for (handle, j) in joints.iter() {
+            if j.is_active(bodies) {
+                let (b1, b2) = j.anchors();
+
+                if let Some(island) = self.body_island(b1.0).or_else(|| self.body_island(b2.0)) {
+                    self.islands[island].joints.push(handle);
+                }
+            }
+        }
+    }
+
+    /// Sorts the given contact manifolds by island and sets the range of manifolds of each island.
+    ///
+    /// Manifolds that do not involve any body of the islands are removed. The resulting order only
+    /// depends on the order of the input manifolds. This does not allocate any memory.
+    pub fn sort_manifolds(&mut self, manifolds: &mut Vec<ColliderContactManifoldHandles>) {
+        manifolds.retain(|m| self.manifold_island(m).is_some());
+        manifolds.sort_unstable_by_key(|m| self.manifold_island(m));
+
+        let mut start = 0;
+
+        for i in 0..self.nislands {
+            let len = manifolds[start..]
+                .iter()
+                .take_while(|m| self.manifold_island(m) == Some(i))
+                .count();
+
+            self.islands[i].manifolds = start..start + len;
+            start += len;
+        }
+    }
+
+    fn manifold_island(&self, manifold: &ColliderContactManifoldHandles) -> Option<usize> {
+        self.body_island(manifold.body1).or_else(|| self.body_island(manifold.body2))
+    }
+
+    fn union(&mut self, b1: BodyHandle, b2: BodyHandle) {
+        if let (Some(i1), Some(i2)) = (self.body_islands.get(&b1), self.body_islands.get(&b2)) {
+            union_find::union(*i1, *i2, &mut self.ufind[..])
+        }
+    }
+}
+
+impl Default for IslandSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
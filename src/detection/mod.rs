@@ -1,7 +1,16 @@
 //! Collision detection information.
 
+pub use self::activation_handler::ActivationHandler;
 pub use self::activation_manager::ActivationManager;
-pub use self::collider_contact_manifold::ColliderContactManifold;
+pub use self::collider_contact_manifold::{ColliderContactManifold, ColliderContactManifoldHandles,
+                                         ColliderContactManifolds};
+pub use self::contact_handler::{ContactHandler, ContactImpact, ContactPair, ProximityPair};
+pub use self::contact_manifold_info::{ContactInfo, ContactManifoldInfo};
+pub use self::island_set::{Island, IslandSet};
 
 mod collider_contact_manifold;
+mod contact_handler;
+mod contact_manifold_info;
 mod activation_manager;
+mod activation_handler;
+mod island_set;
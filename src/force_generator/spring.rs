@@ -4,8 +4,15 @@ use crate::force_generator::ForceGenerator;
 use crate::math::{ForceType, Point, Vector};
 use crate::object::{BodyPartHandle, BodySet};
 use crate::solver::IntegrationParameters;
+use crate::utils::GeneralizedCross;
 
 /// Generator of a force proportional to the distance separating two bodies.
+///
+/// The spring force is `-stiffness * (length - rest_length) - damping * relative_velocity` where
+/// `relative_velocity` is the relative velocity of the anchors along the spring axis. Equal and opposite
+/// forces are applied at both anchors, so the bodies are subject to a torque if the anchors are not
+/// at their centers of mass. This force is applied at the beginning of each step, like any other external
+/// force, so it is not solved by the constraints solver.
 pub struct Spring<N: Real> {
     b1: BodyPartHandle,
     b2: BodyPartHandle,
@@ -13,6 +20,7 @@ pub struct Spring<N: Real> {
     anchor2: Point<N>,
     length: N,
     stiffness: N,
+    damping: N,
 }
 
 impl<N: Real> Spring<N> {
@@ -35,9 +43,56 @@ impl<N: Real> Spring<N> {
             anchor2,
             length,
             stiffness,
+            damping: N::zero(),
         }
     }
 
+    /// Initialize a spring attached to the body part `b` at the point `anchor` and to the fixed world-space point `world_point`.
+    ///
+    /// The anchor is expressed in the local coordinates of the body.
+    /// The spring has a rest length of `length` and a stiffness of `stiffness`.
+    pub fn new_tethered(
+        b: BodyPartHandle,
+        anchor: Point<N>,
+        world_point: Point<N>,
+        length: N,
+        stiffness: N,
+    ) -> Self {
+        Self::new(BodyPartHandle::ground(), b, world_point, anchor, length, stiffness)
+    }
+
+    /// Sets the damping coefficient of this spring.
+    ///
+    /// The damping force is proportional to the relative velocity of the anchors along the spring axis.
+    pub fn set_damping(&mut self, damping: N) {
+        self.damping = damping;
+    }
+
+    /// The damping coefficient of this spring.
+    pub fn damping(&self) -> N {
+        self.damping
+    }
+
+    /// Sets the stiffness of this spring.
+    pub fn set_stiffness(&mut self, stiffness: N) {
+        self.stiffness = stiffness;
+    }
+
+    /// The stiffness of this spring.
+    pub fn stiffness(&self) -> N {
+        self.stiffness
+    }
+
+    /// Sets the rest length of this spring.
+    pub fn set_rest_length(&mut self, length: N) {
+        self.length = length;
+    }
+
+    /// The rest length of this spring.
+    pub fn rest_length(&self) -> N {
+        self.length
+    }
+
     /// Sets the attach point to the first body.
     /// 
     /// The anchor is expressed in the local coordinatse of the first body.
@@ -78,7 +133,14 @@ impl<N: Real> ForceGenerator<N> for Spring<N> {
             delta_length = -self.length;
         }
 
-        let force = force_dir.as_ref() * delta_length * self.stiffness;
+        // Velocities of the anchors.
+        let vel1 = part1.velocity();
+        let vel2 = part2.velocity();
+        let anchor_vel1 = vel1.linear + vel1.angular_vector().gcross(&(anchor1 - part1.center_of_mass()));
+        let anchor_vel2 = vel2.linear + vel2.angular_vector().gcross(&(anchor2 - part2.center_of_mass()));
+        let relative_vel = (anchor_vel2 - anchor_vel1).dot(&force_dir);
+
+        let force = force_dir.as_ref() * (delta_length * self.stiffness + relative_vel * self.damping);
         bodies.body_mut(self.b1.0).unwrap().apply_force_at_local_point(self.b1.1, &force, &self.anchor1, ForceType::Force, false);
         bodies.body_mut(self.b2.0).unwrap().apply_force_at_local_point(self.b2.1, &-force, &self.anchor2, ForceType::Force, false);
 
@@ -56,6 +56,14 @@ impl<N: Real> JointConstraint<N> for BallConstraint<N> {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.impulses.norm(), N::zero())
+    }
+
     fn velocity_constraints(
         &mut self,
         _: &IntegrationParameters<N>,
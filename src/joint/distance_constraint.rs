@@ -0,0 +1,246 @@
+use na::{DVector, Real, Unit};
+use std::ops::Range;
+
+use crate::joint::JointConstraint;
+use crate::math::Point;
+use crate::object::{BodyPartHandle, BodySet};
+use crate::solver::{helper, BilateralConstraint, BilateralGroundConstraint, ForceDirection, ImpulseLimits};
+use crate::solver::{ConstraintSet, GenericNonlinearConstraint, IntegrationParameters,
+             NonlinearConstraintGenerator};
+
+/// A constraint that keeps two anchors at a fixed distance from each other.
+///
+/// By default, this constraint behaves like a rigid rod. If a stiffness is
+/// set, the impulse it applies is limited to the one of a damped spring which
+/// makes the constraint behave like a soft spring instead.
+pub struct DistanceConstraint<N: Real> {
+    b1: BodyPartHandle,
+    b2: BodyPartHandle,
+    anchor1: Point<N>,
+    anchor2: Point<N>,
+    rest_length: N,
+    stiffness: Option<N>,
+    damping: N,
+    impulse: N,
+    bilateral_ground_rng: Range<usize>,
+    bilateral_rng: Range<usize>,
+}
+
+impl<N: Real> DistanceConstraint<N> {
+    /// Creates a distance constraint between two body parts.
+    ///
+    /// This will ensure the two points identified by `anchor1` and `anchor2` stay
+    /// `rest_length` apart. Both are given in the local-space of their corresponding body part.
+    pub fn new(
+        b1: BodyPartHandle,
+        b2: BodyPartHandle,
+        anchor1: Point<N>,
+        anchor2: Point<N>,
+        rest_length: N,
+    ) -> Self {
+        assert!(rest_length >= N::zero(), "The rest length of a distance constraint must be positive.");
+
+        DistanceConstraint {
+            b1,
+            b2,
+            anchor1,
+            anchor2,
+            rest_length,
+            stiffness: None,
+            damping: N::zero(),
+            impulse: N::zero(),
+            bilateral_ground_rng: 0..0,
+            bilateral_rng: 0..0,
+        }
+    }
+
+    /// Change the first anchor, expressed in the local space of the first body part.
+    pub fn set_anchor_1(&mut self, anchor1: Point<N>) {
+        self.anchor1 = anchor1;
+    }
+
+    /// Change the second anchor, expressed in the local space of the second body part.
+    pub fn set_anchor_2(&mut self, anchor2: Point<N>) {
+        self.anchor2 = anchor2;
+    }
+
+    /// The distance this constraint tries to maintain between its two anchors.
+    pub fn rest_length(&self) -> N {
+        self.rest_length
+    }
+
+    /// Change the distance this constraint tries to maintain between its two anchors.
+    pub fn set_rest_length(&mut self, rest_length: N) {
+        assert!(rest_length >= N::zero(), "The rest length of a distance constraint must be positive.");
+        self.rest_length = rest_length;
+    }
+
+    /// The stiffness and damping of this constraint, if it behaves like a soft spring.
+    pub fn spring_coefficients(&self) -> Option<(N, N)> {
+        self.stiffness.map(|stiffness| (stiffness, self.damping))
+    }
+
+    /// Makes this constraint behave like a damped spring with the given stiffness and damping coefficients.
+    pub fn enable_spring(&mut self, stiffness: N, damping: N) {
+        assert!(
+            stiffness >= N::zero() && damping >= N::zero(),
+            "The stiffness and damping of a distance constraint must be positive."
+        );
+        self.stiffness = Some(stiffness);
+        self.damping = damping;
+    }
+
+    /// Makes this constraint behave like a rigid rod.
+    pub fn disable_spring(&mut self) {
+        self.stiffness = None;
+        self.damping = N::zero();
+    }
+
+    /// The impulse applied by this constraint during the last timestep.
+    pub fn impulse(&self) -> N {
+        self.impulse
+    }
+}
+
+impl<N: Real> JointConstraint<N> for DistanceConstraint<N> {
+    fn num_velocity_constraints(&self) -> usize {
+        1
+    }
+
+    fn anchors(&self) -> (BodyPartHandle, BodyPartHandle) {
+        (self.b1, self.b2)
+    }
+
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.impulse.abs(), N::zero())
+    }
+
+    fn velocity_constraints(
+        &mut self,
+        params: &IntegrationParameters<N>,
+        bodies: &BodySet<N>,
+        ext_vels: &DVector<N>,
+        ground_j_id: &mut usize,
+        j_id: &mut usize,
+        jacobians: &mut [N],
+        constraints: &mut ConstraintSet<N>,
+    ) {
+        self.bilateral_ground_rng = 0..0;
+        self.bilateral_rng = 0..0;
+
+        let body1 = try_ret!(bodies.body(self.b1.0));
+        let body2 = try_ret!(bodies.body(self.b2.0));
+        let part1 = try_ret!(body1.part(self.b1.1));
+        let part2 = try_ret!(body2.part(self.b2.1));
+
+        let anchor1 = body1.world_point_at_material_point(part1, &self.anchor1);
+        let anchor2 = body2.world_point_at_material_point(part2, &self.anchor2);
+
+        // The constraint direction is undefined when both anchors coincide.
+        let (dir, dist) = try_ret!(Unit::try_new_and_get(anchor2 - anchor1, N::default_epsilon()));
+
+        let assembly_id1 = body1.companion_id();
+        let assembly_id2 = body2.companion_id();
+
+        let first_bilateral_ground = constraints.velocity.bilateral_ground.len();
+        let first_bilateral = constraints.velocity.bilateral.len();
+
+        let error = dist - self.rest_length;
+        let (ext_vels1, ext_vels2) = helper::split_ext_vels(body1, body2, assembly_id1, assembly_id2, ext_vels);
+        let mut rel_vel = N::zero();
+        let geom = helper::constraint_pair_geometry(
+            body1,
+            part1,
+            body2,
+            part2,
+            &anchor1,
+            &anchor2,
+            &ForceDirection::Linear(dir),
+            ground_j_id,
+            j_id,
+            jacobians,
+            Some(&ext_vels1),
+            Some(&ext_vels2),
+            Some(&mut rel_vel)
+        );
+
+        // Baumgarte stabilization of the positional drift.
+        let rhs = rel_vel - error * params.erp / params.dt;
+
+        let limits = match self.stiffness {
+            Some(stiffness) => {
+                let max = (stiffness * error.abs() + self.damping * rel_vel.abs()) * params.dt;
+                ImpulseLimits::Independent { min: -max, max }
+            }
+            None => ImpulseLimits::Independent {
+                min: -N::max_value(),
+                max: N::max_value(),
+            },
+        };
+
+        if geom.ndofs1 == 0 || geom.ndofs2 == 0 {
+            constraints
+                .velocity
+                .bilateral_ground
+                .push(BilateralGroundConstraint::new(
+                    geom,
+                    assembly_id1,
+                    assembly_id2,
+                    limits,
+                    rhs,
+                    self.impulse,
+                    0,
+                ));
+        } else {
+            constraints
+                .velocity
+                .bilateral
+                .push(BilateralConstraint::new(
+                    geom,
+                    assembly_id1,
+                    assembly_id2,
+                    limits,
+                    rhs,
+                    self.impulse,
+                    0,
+                ));
+        }
+
+        self.bilateral_ground_rng =
+            first_bilateral_ground..constraints.velocity.bilateral_ground.len();
+        self.bilateral_rng = first_bilateral..constraints.velocity.bilateral.len();
+    }
+
+    fn cache_impulses(&mut self, constraints: &ConstraintSet<N>) {
+        self.impulse = N::zero();
+
+        for c in &constraints.velocity.bilateral_ground[self.bilateral_ground_rng.clone()] {
+            self.impulse = c.impulse;
+        }
+
+        for c in &constraints.velocity.bilateral[self.bilateral_rng.clone()] {
+            self.impulse = c.impulse;
+        }
+    }
+}
+
+impl<N: Real> NonlinearConstraintGenerator<N> for DistanceConstraint<N> {
+    fn num_position_constraints(&self, _: &BodySet<N>) -> usize {
+        // The positional drift is already corrected at the velocity level.
+        0
+    }
+
+    fn position_constraint(
+        &self,
+        _: &IntegrationParameters<N>,
+        _: usize,
+        _: &mut BodySet<N>,
+        _: &mut [N],
+    ) -> Option<GenericNonlinearConstraint<N>> {
+        None
+    }
+}
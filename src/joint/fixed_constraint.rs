@@ -79,6 +79,14 @@ impl<N: Real> JointConstraint<N> for FixedConstraint<N> {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.lin_impulses.norm(), self.ang_impulses.norm())
+    }
+
     fn velocity_constraints(
         &mut self,
         _: &IntegrationParameters<N>,
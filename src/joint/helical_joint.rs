@@ -1,6 +1,6 @@
 use na::{self, DVectorSliceMut, Isometry3, Real, Translation3, Unit, Vector3};
 
-use crate::joint::{Joint, JointMotor, RevoluteJoint, UnitJoint};
+use crate::joint::{Joint, JointMotor, RevoluteJoint, SoftLimit, UnitJoint};
 use crate::math::{JacobianSliceMut, Velocity};
 use crate::object::{Multibody, MultibodyLink};
 use crate::solver::{ConstraintSet, GenericNonlinearConstraint, IntegrationParameters};
@@ -164,6 +164,10 @@ impl<N: Real> UnitJoint<N> for HelicalJoint<N> {
     fn max_position(&self) -> Option<N> {
         self.revo.max_angle()
     }
+
+    fn soft_limit(&self) -> Option<SoftLimit<N>> {
+        self.revo.soft_limit()
+    }
 }
 
 revolute_motor_limit_methods!(HelicalJoint, revo);
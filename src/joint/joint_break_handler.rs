@@ -0,0 +1,14 @@
+use na::Real;
+
+use crate::joint::{ConstraintHandle, JointConstraint};
+
+/// Trait implemented by listeners notified whenever a joint constraint breaks.
+///
+/// See `World::set_joint_breaking_force` and `World::set_joint_breaking_torque`.
+pub trait JointBreakHandler<N: Real>: Send + Sync {
+    /// Called when the joint with the given handle broke and was removed from the world.
+    ///
+    /// The force and torque applied by the joint during the timestep it broke are given. The removed
+    /// joint is given back so that it can be inspected or re-added to the world.
+    fn handle_joint_broken(&mut self, handle: ConstraintHandle, joint: Box<JointConstraint<N>>, force: N, torque: N);
+}
@@ -3,6 +3,7 @@
 use downcast_rs::Downcast;
 use na::{DVector, Real};
 
+use crate::math::Point;
 use crate::object::{BodyPartHandle, BodySet};
 use crate::solver::{ConstraintSet, IntegrationParameters, NonlinearConstraintGenerator};
 
@@ -29,6 +30,32 @@ pub trait JointConstraint<N: Real>: NonlinearConstraintGenerator<N> + Downcast +
     fn num_velocity_constraints(&self) -> usize;
     /// The two body parts affected by this joint.
     fn anchors(&self) -> (BodyPartHandle, BodyPartHandle);
+    /// The attachment points of this joint, expressed in the local-space of each body part.
+    ///
+    /// The default implementation returns the origin of each body part.
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (Point::origin(), Point::origin())
+    }
+    /// The current value of the free coordinate of this joint, if it has exactly one.
+    ///
+    /// This is the relative angle of the body parts for revolute joints, and their relative offset along the
+    /// axis for prismatic joints. Returns `None` for joints without a single free coordinate, or if one
+    /// of the body parts does not exist.
+    fn coordinate(&self, _bodies: &BodySet<N>) -> Option<N> {
+        None
+    }
+    /// The impulse applied by the motor of this joint during the last timestep (zero if it has no motor).
+    fn motor_impulse(&self) -> N {
+        N::zero()
+    }
+    /// The norms of the linear and angular impulses applied by this joint during the last timestep.
+    ///
+    /// Those are the impulses needed to enforce the joint once the velocity solver converged. They
+    /// do not include the impulses of joint limits and motors. Joints that do not override this
+    /// method (the default returns zeros) are never broken by `World::set_joint_breaking_force`.
+    fn applied_impulses(&self) -> (N, N) {
+        (N::zero(), N::zero())
+    }
     /// Initialize and retrieve all the constraints appied to the bodies attached to this joint.
     fn velocity_constraints(
         &mut self,
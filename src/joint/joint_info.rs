@@ -0,0 +1,26 @@
+use na::Real;
+
+use crate::joint::ConstraintHandle;
+use crate::math::Point;
+use crate::object::BodyPartHandle;
+
+/// A read-only view of an active joint constraint, as returned by `World::joints`.
+#[derive(Copy, Clone, Debug)]
+pub struct JointInfo<N: Real> {
+    /// The handle of the joint constraint.
+    pub handle: ConstraintHandle,
+    /// The first body part attached to the joint.
+    pub body_part1: BodyPartHandle,
+    /// The second body part attached to the joint.
+    pub body_part2: BodyPartHandle,
+    /// The attachment point of the joint on the first body part, in world-space.
+    pub anchor1: Point<N>,
+    /// The attachment point of the joint on the second body part, in world-space.
+    pub anchor2: Point<N>,
+    /// The current value of the free coordinate of the joint, if it has exactly one.
+    ///
+    /// See `JointConstraint::coordinate` for details.
+    pub coordinate: Option<N>,
+    /// The impulse applied by the motor of the joint during the last timestep.
+    pub motor_impulse: N,
+}
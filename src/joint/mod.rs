@@ -25,12 +25,16 @@ pub use self::rectangular_joint::RectangularJoint;
 pub use self::universal_joint::UniversalJoint;
 
 pub use self::cartesian_constraint::CartesianConstraint;
+pub use self::distance_constraint::DistanceConstraint;
 pub use self::fixed_constraint::FixedConstraint;
+pub use self::joint_break_handler::JointBreakHandler;
 pub use self::joint_constraint::{ConstraintHandle, JointConstraint};
+pub use self::joint_info::JointInfo;
 pub use self::joint_motor::JointMotor;
 pub use self::mouse_constraint::MouseConstraint;
 pub use self::prismatic_constraint::PrismaticConstraint;
 pub use self::revolute_constraint::RevoluteConstraint;
+pub use self::soft_limit::SoftLimit;
 
 #[cfg(feature = "dim3")]
 pub use self::ball_constraint::BallConstraint;
@@ -69,12 +73,16 @@ mod rectangular_joint;
 mod universal_joint;
 
 mod cartesian_constraint;
+mod distance_constraint;
 mod fixed_constraint;
+mod joint_break_handler;
 mod joint_constraint;
+mod joint_info;
 mod joint_motor;
 mod mouse_constraint;
 mod prismatic_constraint;
 mod revolute_constraint;
+mod soft_limit;
 mod unit_constraint;
 
 #[cfg(feature = "dim3")]
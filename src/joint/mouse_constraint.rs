@@ -58,6 +58,10 @@ impl<N: Real> JointConstraint<N> for MouseConstraint<N> {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
     fn velocity_constraints(
         &mut self,
         params: &IntegrationParameters<N>,
@@ -60,6 +60,14 @@ impl<N: Real> JointConstraint<N> for PlanarConstraint<N> {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.lin_impulse.abs(), (self.ang_impulses[0] * self.ang_impulses[0] + self.ang_impulses[1] * self.ang_impulses[1]).sqrt())
+    }
+
     fn velocity_constraints(
         &mut self,
         _: &IntegrationParameters<N>,
@@ -1,10 +1,10 @@
 use na::{DVector, Real, Unit};
 use std::ops::Range;
 
-use crate::joint::{unit_constraint, JointConstraint};
+use crate::joint::{unit_constraint, JointConstraint, JointMotor};
 use crate::math::{AngularVector, Point, Vector, DIM, SPATIAL_DIM};
 use crate::object::{BodyPartHandle, BodySet};
-use crate::solver::helper;
+use crate::solver::{helper, ForceDirection};
 use crate::solver::{ConstraintSet, GenericNonlinearConstraint, IntegrationParameters,
              NonlinearConstraintGenerator};
 
@@ -18,6 +18,8 @@ pub struct PrismaticConstraint<N: Real> {
     lin_impulses: Vector<N>,
     ang_impulses: AngularVector<N>,
     limit_impulse: N,
+    motor: JointMotor<N, N>,
+    motor_impulse: N,
     bilateral_ground_rng: Range<usize>,
     bilateral_rng: Range<usize>,
 
@@ -48,6 +50,8 @@ impl<N: Real> PrismaticConstraint<N> {
             lin_impulses: Vector::zeros(),
             ang_impulses: AngularVector::zeros(),
             limit_impulse: N::zero(),
+            motor: JointMotor::new(),
+            motor_impulse: N::zero(),
             bilateral_ground_rng: 0..0,
             bilateral_rng: 0..0,
             min_offset,
@@ -87,6 +91,40 @@ impl<N: Real> PrismaticConstraint<N> {
         self.assert_limits();
     }
 
+    /// Enables both the lower and upper limits of the relative translational motion along the joint axis.
+    pub fn set_limits(&mut self, min: N, max: N) {
+        self.min_offset = Some(min);
+        self.max_offset = Some(max);
+        self.assert_limits();
+    }
+
+    /// The linear motor of this constraint.
+    pub fn motor(&self) -> &JointMotor<N, N> {
+        &self.motor
+    }
+
+    /// Enable the linear motor of this constraint.
+    ///
+    /// The motor will attempt to reach the relative linear velocity `target_velocity`
+    /// (of the second body part wrt. the first one, along the joint axis) by applying
+    /// at most `max_force`.
+    pub fn enable_motor(&mut self, target_velocity: N, max_force: N) {
+        self.motor.desired_velocity = target_velocity;
+        self.motor.max_force = max_force;
+        self.motor.enabled = true;
+    }
+
+    /// Disable the linear motor of this constraint.
+    pub fn disable_motor(&mut self) {
+        self.motor.enabled = false;
+        self.motor_impulse = N::zero();
+    }
+
+    /// The impulse applied by the motor during the last timestep.
+    pub fn motor_impulse(&self) -> N {
+        self.motor_impulse
+    }
+
     fn assert_limits(&self) {
         if let (Some(min_offset), Some(max_offset)) = (self.min_offset, self.max_offset) {
             assert!(
@@ -98,16 +136,38 @@ impl<N: Real> PrismaticConstraint<N> {
 
 impl<N: Real> JointConstraint<N> for PrismaticConstraint<N> {
     fn num_velocity_constraints(&self) -> usize {
-        (SPATIAL_DIM - 1) + 2
+        (SPATIAL_DIM - 1) + 3
     }
 
     fn anchors(&self) -> (BodyPartHandle, BodyPartHandle) {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn coordinate(&self, bodies: &BodySet<N>) -> Option<N> {
+        let body1 = bodies.body(self.b1.0)?;
+        let body2 = bodies.body(self.b2.0)?;
+        let pos1 = body1.position_at_material_point(body1.part(self.b1.1)?, &self.anchor1);
+        let pos2 = body2.position_at_material_point(body2.part(self.b2.1)?, &self.anchor2);
+        let axis = pos1 * self.axis1;
+
+        Some(axis.dot(&(pos2.translation.vector - pos1.translation.vector)))
+    }
+
+    fn motor_impulse(&self) -> N {
+        self.motor_impulse
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.lin_impulses.norm(), self.ang_impulses.norm())
+    }
+
     fn velocity_constraints(
         &mut self,
-        _: &IntegrationParameters<N>,
+        params: &IntegrationParameters<N>,
         bodies: &BodySet<N>,
         ext_vels: &DVector<N>,
         ground_j_id: &mut usize,
@@ -202,19 +262,49 @@ impl<N: Real> JointConstraint<N> for PrismaticConstraint<N> {
             constraints,
         );
 
+        /*
+         *
+         * Motor constraint.
+         *
+         */
+        unit_constraint::build_motor_velocity_constraint(
+            params,
+            body1,
+            part1,
+            body2,
+            part2,
+            assembly_id1,
+            assembly_id2,
+            &anchor1,
+            &anchor2,
+            &ForceDirection::Linear(axis),
+            &self.motor,
+            ext_vels,
+            self.motor_impulse,
+            SPATIAL_DIM,
+            ground_j_id,
+            j_id,
+            jacobians,
+            constraints,
+        );
+
         self.bilateral_ground_rng =
             first_bilateral_ground..constraints.velocity.bilateral_ground.len();
         self.bilateral_rng = first_bilateral..constraints.velocity.bilateral.len();
     }
 
     fn cache_impulses(&mut self, constraints: &ConstraintSet<N>) {
+        self.motor_impulse = N::zero();
+
         for c in &constraints.velocity.bilateral_ground[self.bilateral_ground_rng.clone()] {
             if c.impulse_id < DIM - 1 {
                 self.lin_impulses[c.impulse_id] = c.impulse;
             } else if c.impulse_id < SPATIAL_DIM - 1 {
                 self.ang_impulses[c.impulse_id - DIM + 1] = c.impulse;
-            } else {
+            } else if c.impulse_id == SPATIAL_DIM - 1 {
                 self.limit_impulse = c.impulse
+            } else {
+                self.motor_impulse = c.impulse
             }
         }
 
@@ -223,8 +313,10 @@ impl<N: Real> JointConstraint<N> for PrismaticConstraint<N> {
                 self.lin_impulses[c.impulse_id] = c.impulse;
             } else if c.impulse_id < SPATIAL_DIM - 1 {
                 self.ang_impulses[c.impulse_id - DIM + 1] = c.impulse;
-            } else {
+            } else if c.impulse_id == SPATIAL_DIM - 1 {
                 self.limit_impulse = c.impulse
+            } else {
+                self.motor_impulse = c.impulse
             }
         }
     }
@@ -2,7 +2,7 @@
 
 use na::{self, DVectorSliceMut, Real, Unit};
 
-use crate::joint::{self, Joint, JointMotor, UnitJoint};
+use crate::joint::{self, Joint, JointMotor, SoftLimit, UnitJoint};
 use crate::math::{Dim, Isometry, JacobianSliceMut, Rotation, Translation, Vector, Velocity};
 use crate::object::{MultibodyLink, Multibody};
 use crate::solver::{ConstraintSet, GenericNonlinearConstraint, IntegrationParameters};
@@ -17,6 +17,7 @@ pub struct PrismaticJoint<N: Real> {
 
     min_offset: Option<N>,
     max_offset: Option<N>,
+    soft_limit: Option<SoftLimit<N>>,
     motor: JointMotor<N, N>,
 }
 
@@ -32,6 +33,7 @@ impl<N: Real> PrismaticJoint<N> {
             offset: offset,
             min_offset: None,
             max_offset: None,
+            soft_limit: None,
             motor: JointMotor::new(),
         }
     }
@@ -47,6 +49,7 @@ impl<N: Real> PrismaticJoint<N> {
             offset: offset,
             min_offset: None,
             max_offset: None,
+            soft_limit: None,
             motor: JointMotor::new(),
         }
     }
@@ -93,6 +96,34 @@ impl<N: Real> PrismaticJoint<N> {
         self.assert_limits();
     }
 
+    /// Returns `true` if the relative displacement along the joint axis has reached or exceeded its lower limit.
+    pub fn is_min_offset_active(&self) -> bool {
+        self.min_offset.map_or(false, |limit| self.offset <= limit)
+    }
+
+    /// Returns `true` if the relative displacement along the joint axis has reached or exceeded its upper limit.
+    pub fn is_max_offset_active(&self) -> bool {
+        self.max_offset.map_or(false, |limit| self.offset >= limit)
+    }
+
+    /// The spring-damper applied when the relative displacement along the joint axis exceeds its limits, if they are soft.
+    pub fn soft_limit(&self) -> Option<SoftLimit<N>> {
+        self.soft_limit
+    }
+
+    /// Makes the limits of the relative displacement along the joint axis soft.
+    ///
+    /// Instead of being stopped at its limits, the relative displacement along the joint axis is pushed back by a spring-damper
+    /// with the given stiffness and damping once it exceeds them.
+    pub fn enable_soft_limits(&mut self, stiffness: N, damping: N) {
+        self.soft_limit = Some(SoftLimit::new(stiffness, damping));
+    }
+
+    /// Makes the limits of the relative displacement along the joint axis infinitely stiff again.
+    pub fn disable_soft_limits(&mut self) {
+        self.soft_limit = None;
+    }
+
     /// Returns `true` if the joint motor is enabled.
     pub fn is_linear_motor_enabled(&self) -> bool {
         self.motor.enabled
@@ -263,6 +294,10 @@ impl<N: Real> UnitJoint<N> for PrismaticJoint<N> {
     fn max_position(&self) -> Option<N> {
         self.max_offset
     }
+
+    fn soft_limit(&self) -> Option<SoftLimit<N>> {
+        self.soft_limit
+    }
 }
 
 #[cfg(feature = "dim3")]
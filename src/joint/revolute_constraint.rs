@@ -1,12 +1,10 @@
-#[cfg(feature = "dim3")]
-use na::Unit;
-use na::{DVector, Real};
+use na::{DVector, Real, Unit};
 use std::ops::Range;
 
-use crate::joint::JointConstraint;
+use crate::joint::{unit_constraint, JointConstraint, JointMotor};
 use crate::math::{AngularVector, Point, Vector, DIM, SPATIAL_DIM};
 use crate::object::{BodyPartHandle, BodySet};
-use crate::solver::helper;
+use crate::solver::{helper, ForceDirection};
 use crate::solver::{ConstraintSet, GenericNonlinearConstraint, IntegrationParameters,
              NonlinearConstraintGenerator};
 
@@ -19,6 +17,8 @@ pub struct RevoluteConstraint<N: Real> {
     anchor2: Point<N>,
     lin_impulses: Vector<N>,
     ang_impulses: AngularVector<N>,
+    motor: JointMotor<N, N>,
+    motor_impulse: N,
     // FIXME: not actually needed in 2D.
     bilateral_ground_rng: Range<usize>,
     bilateral_rng: Range<usize>,
@@ -37,6 +37,8 @@ pub struct RevoluteConstraint<N: Real> {
     axis2: Unit<AngularVector<N>>,
     lin_impulses: Vector<N>,
     ang_impulses: AngularVector<N>,
+    motor: JointMotor<N, N>,
+    motor_impulse: N,
     bilateral_ground_rng: Range<usize>,
     bilateral_rng: Range<usize>,
     // min_angle: Option<N>,
@@ -67,6 +69,8 @@ impl<N: Real> RevoluteConstraint<N> {
             axis2,
             lin_impulses: Vector::zeros(),
             ang_impulses: AngularVector::zeros(),
+            motor: JointMotor::new(),
+            motor_impulse: N::zero(),
             bilateral_ground_rng: 0..0,
             bilateral_rng: 0..0,
             // min_angle,
@@ -89,6 +93,8 @@ impl<N: Real> RevoluteConstraint<N> {
             anchor2,
             lin_impulses: Vector::zeros(),
             ang_impulses: AngularVector::zeros(),
+            motor: JointMotor::new(),
+            motor_impulse: N::zero(),
             bilateral_ground_rng: 0..0,
             bilateral_rng: 0..0,
             // min_angle,
@@ -96,6 +102,55 @@ impl<N: Real> RevoluteConstraint<N> {
         }
     }
 
+    /// Change the first anchor, expressed in the local space of the first body part.
+    pub fn set_anchor_1(&mut self, anchor1: Point<N>) {
+        self.anchor1 = anchor1;
+    }
+
+    /// Change the second anchor, expressed in the local space of the second body part.
+    pub fn set_anchor_2(&mut self, anchor2: Point<N>) {
+        self.anchor2 = anchor2;
+    }
+
+    /// Change the first rotation axis, expressed in the local space of the first body part.
+    #[cfg(feature = "dim3")]
+    pub fn set_axis_1(&mut self, axis1: Unit<AngularVector<N>>) {
+        self.axis1 = axis1;
+    }
+
+    /// Change the second rotation axis, expressed in the local space of the second body part.
+    #[cfg(feature = "dim3")]
+    pub fn set_axis_2(&mut self, axis2: Unit<AngularVector<N>>) {
+        self.axis2 = axis2;
+    }
+
+    /// The angular motor of this constraint.
+    pub fn motor(&self) -> &JointMotor<N, N> {
+        &self.motor
+    }
+
+    /// Enable the angular motor of this constraint.
+    ///
+    /// The motor will attempt to reach the relative angular velocity `target_velocity`
+    /// (of the second body part wrt. the first one, along the joint axis) by applying
+    /// at most `max_force`.
+    pub fn enable_motor(&mut self, target_velocity: N, max_force: N) {
+        self.motor.desired_velocity = target_velocity;
+        self.motor.max_force = max_force;
+        self.motor.enabled = true;
+    }
+
+    /// Disable the angular motor of this constraint.
+    pub fn disable_motor(&mut self) {
+        self.motor.enabled = false;
+        self.motor_impulse = N::zero();
+    }
+
+    /// The impulse applied by the motor during the last timestep.
+    pub fn motor_impulse(&self) -> N {
+        self.motor_impulse
+    }
+
     // pub fn min_angle(&self) -> Option<N> {
     //     self.min_angle
     // }
@@ -133,16 +188,43 @@ impl<N: Real> RevoluteConstraint<N> {
 
 impl<N: Real> JointConstraint<N> for RevoluteConstraint<N> {
     fn num_velocity_constraints(&self) -> usize {
-        SPATIAL_DIM - 1
+        SPATIAL_DIM
     }
 
     fn anchors(&self) -> (BodyPartHandle, BodyPartHandle) {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn coordinate(&self, bodies: &BodySet<N>) -> Option<N> {
+        let body1 = bodies.body(self.b1.0)?;
+        let body2 = bodies.body(self.b2.0)?;
+        let pos1 = body1.position_at_material_point(body1.part(self.b1.1)?, &self.anchor1);
+        let pos2 = body2.position_at_material_point(body2.part(self.b2.1)?, &self.anchor2);
+        let rot = pos1.rotation.inverse() * pos2.rotation;
+
+        #[cfg(feature = "dim2")]
+        let angle = rot.angle();
+        #[cfg(feature = "dim3")]
+        let angle = self.axis1.dot(&rot.scaled_axis());
+
+        Some(angle)
+    }
+
+    fn motor_impulse(&self) -> N {
+        self.motor_impulse
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.lin_impulses.norm(), self.ang_impulses.norm())
+    }
+
     fn velocity_constraints(
         &mut self,
-        _: &IntegrationParameters<N>,
+        params: &IntegrationParameters<N>,
         bodies: &BodySet<N>,
         ext_vels: &DVector<N>,
         ground_j_id: &mut usize,
@@ -214,6 +296,37 @@ impl<N: Real> JointConstraint<N> for RevoluteConstraint<N> {
                 );
             }
 
+        /*
+         *
+         * Motor constraint.
+         *
+         */
+        #[cfg(feature = "dim2")]
+        let axis = Unit::new_unchecked(AngularVector::x());
+        #[cfg(feature = "dim3")]
+        let axis = pos1 * self.axis1;
+
+        unit_constraint::build_motor_velocity_constraint(
+            params,
+            body1,
+            part1,
+            body2,
+            part2,
+            assembly_id1,
+            assembly_id2,
+            &anchor1,
+            &anchor2,
+            &ForceDirection::Angular(axis),
+            &self.motor,
+            ext_vels,
+            self.motor_impulse,
+            SPATIAL_DIM - 1,
+            ground_j_id,
+            j_id,
+            jacobians,
+            constraints,
+        );
+
         /*
          *
          * Limit constraints.
@@ -226,19 +339,25 @@ impl<N: Real> JointConstraint<N> for RevoluteConstraint<N> {
     }
 
     fn cache_impulses(&mut self, constraints: &ConstraintSet<N>) {
+        self.motor_impulse = N::zero();
+
         for c in &constraints.velocity.bilateral_ground[self.bilateral_ground_rng.clone()] {
             if c.impulse_id < DIM {
                 self.lin_impulses[c.impulse_id] = c.impulse;
-            } else {
+            } else if c.impulse_id < SPATIAL_DIM - 1 {
                 self.ang_impulses[c.impulse_id - DIM] = c.impulse;
+            } else {
+                self.motor_impulse = c.impulse;
             }
         }
 
         for c in &constraints.velocity.bilateral[self.bilateral_rng.clone()] {
             if c.impulse_id < DIM {
                 self.lin_impulses[c.impulse_id] = c.impulse;
-            } else {
+            } else if c.impulse_id < SPATIAL_DIM - 1 {
                 self.ang_impulses[c.impulse_id - DIM] = c.impulse;
+            } else {
+                self.motor_impulse = c.impulse;
             }
         }
     }
@@ -2,7 +2,7 @@
 
 use na::{self, DVectorSliceMut, Real, Unit};
 
-use crate::joint::{self, Joint, JointMotor, UnitJoint};
+use crate::joint::{self, Joint, JointMotor, SoftLimit, UnitJoint};
 use crate::math::{AngularVector, Isometry, JacobianSliceMut, Rotation, Translation, Vector, Velocity};
 use crate::object::{MultibodyLink, Multibody};
 use crate::solver::{ConstraintSet, GenericNonlinearConstraint, IntegrationParameters};
@@ -21,6 +21,7 @@ pub struct RevoluteJoint<N: Real> {
 
     min_angle: Option<N>,
     max_angle: Option<N>,
+    soft_limit: Option<SoftLimit<N>>,
     motor: JointMotor<N, N>,
 }
 
@@ -37,6 +38,7 @@ impl<N: Real> RevoluteJoint<N> {
             angle: angle,
             min_angle: None,
             max_angle: None,
+            soft_limit: None,
             motor: JointMotor::new(),
         }
     }
@@ -56,6 +58,7 @@ impl<N: Real> RevoluteJoint<N> {
             angle: angle,
             min_angle: None,
             max_angle: None,
+            soft_limit: None,
             motor: JointMotor::new(),
         }
     }
@@ -132,6 +135,34 @@ impl<N: Real> RevoluteJoint<N> {
         self.assert_limits();
     }
 
+    /// Returns `true` if the rotation angle has reached or exceeded its lower limit.
+    pub fn is_min_angle_active(&self) -> bool {
+        self.min_angle.map_or(false, |limit| self.angle <= limit)
+    }
+
+    /// Returns `true` if the rotation angle has reached or exceeded its upper limit.
+    pub fn is_max_angle_active(&self) -> bool {
+        self.max_angle.map_or(false, |limit| self.angle >= limit)
+    }
+
+    /// The spring-damper applied when the rotation angle exceeds its limits, if they are soft.
+    pub fn soft_limit(&self) -> Option<SoftLimit<N>> {
+        self.soft_limit
+    }
+
+    /// Makes the limits of the rotation angle soft.
+    ///
+    /// Instead of being stopped at its limits, the rotation angle is pushed back by a spring-damper
+    /// with the given stiffness and damping once it exceeds them.
+    pub fn enable_soft_limits(&mut self, stiffness: N, damping: N) {
+        self.soft_limit = Some(SoftLimit::new(stiffness, damping));
+    }
+
+    /// Makes the limits of the rotation angle infinitely stiff again.
+    pub fn disable_soft_limits(&mut self) {
+        self.soft_limit = None;
+    }
+
     /// Return `true` if the angular motor of this joint is enabled.
     pub fn is_angular_motor_enabled(&self) -> bool {
         self.motor.enabled
@@ -313,6 +344,10 @@ impl<N: Real> UnitJoint<N> for RevoluteJoint<N> {
     fn max_position(&self) -> Option<N> {
         self.max_angle
     }
+
+    fn soft_limit(&self) -> Option<SoftLimit<N>> {
+        self.soft_limit
+    }
 }
 
 #[cfg(feature = "dim3")]
@@ -0,0 +1,30 @@
+use na::{self, Real};
+
+/// Description of the spring-damper pushing the coordinate of a joint back within its limits.
+///
+/// A joint with soft limits lets its coordinate exceed its limits and applies a restoring impulse
+/// proportional to the limit violation and to the velocity moving the coordinate away from the limit,
+/// instead of stopping the coordinate at the limit with an infinitely stiff constraint.
+#[derive(Copy, Clone, Debug)]
+pub struct SoftLimit<N: Real> {
+    /// The stiffness of the spring pulling the coordinate back to the limit it exceeds.
+    pub stiffness: N,
+    /// The damping coefficient opposing the velocity of the coordinate once the limit is exceeded.
+    pub damping: N,
+}
+
+impl<N: Real> SoftLimit<N> {
+    /// Creates a soft limit with the given stiffness and damping.
+    pub fn new(stiffness: N, damping: N) -> Self {
+        SoftLimit { stiffness, damping }
+    }
+
+    /// The impulse pushing the coordinate back within its limits during one timestep.
+    ///
+    /// Here, `violation` is the distance by which the coordinate exceeds its limit, and `velocity` is
+    /// the velocity of the coordinate, positive when it moves back toward its limit.
+    pub fn restoring_impulse(&self, violation: N, velocity: N, dt: N) -> N {
+        let force = self.stiffness * violation - self.damping * velocity;
+        na::sup(&N::zero(), &(force * dt))
+    }
+}
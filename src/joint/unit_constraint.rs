@@ -1,5 +1,6 @@
 use na::{DVector, Real, Unit};
 
+use crate::joint::JointMotor;
 use crate::math::{Point, Vector};
 use crate::object::{Body, BodyPart};
 use crate::solver::{helper, BilateralConstraint, BilateralGroundConstraint, ConstraintSet,
@@ -121,6 +122,86 @@ pub fn build_linear_limits_velocity_constraint<N: Real>(
     }
 }
 
+pub fn build_motor_velocity_constraint<N: Real>(
+    params: &IntegrationParameters<N>,
+    body1: &Body<N>,
+    part1: &BodyPart<N>,
+    body2: &Body<N>,
+    part2: &BodyPart<N>,
+    assembly_id1: usize,
+    assembly_id2: usize,
+    anchor1: &Point<N>,
+    anchor2: &Point<N>,
+    dir: &ForceDirection<N>,
+    motor: &JointMotor<N, N>,
+    ext_vels: &DVector<N>,
+    impulse: N,
+    impulse_id: usize,
+    ground_j_id: &mut usize,
+    j_id: &mut usize,
+    jacobians: &mut [N],
+    constraints: &mut ConstraintSet<N>,
+) {
+    if !motor.enabled {
+        return;
+    }
+
+    let (ext_vels1, ext_vels2) = helper::split_ext_vels(body1, body2, assembly_id1, assembly_id2, ext_vels);
+    let mut rhs = N::zero();
+    let geom = helper::constraint_pair_geometry(
+        body1,
+        part1,
+        body2,
+        part2,
+        anchor1,
+        anchor2,
+        dir,
+        ground_j_id,
+        j_id,
+        jacobians,
+        Some(&ext_vels1),
+        Some(&ext_vels2),
+        Some(&mut rhs)
+    );
+
+    // The jacobian measures the velocity of the first body part relative to the second one.
+    rhs += motor.desired_velocity;
+
+    let max_impulse = motor.max_force * params.dt;
+    let limits = ImpulseLimits::Independent {
+        min: -max_impulse,
+        max: max_impulse,
+    };
+
+    if geom.ndofs1 == 0 || geom.ndofs2 == 0 {
+        constraints
+            .velocity
+            .bilateral_ground
+            .push(BilateralGroundConstraint::new(
+                geom,
+                assembly_id1,
+                assembly_id2,
+                limits,
+                rhs,
+                impulse,
+                impulse_id,
+            ));
+    } else {
+        constraints
+            .velocity
+            .bilateral
+            .push(BilateralConstraint::new(
+                geom,
+                assembly_id1,
+                assembly_id2,
+                limits,
+                rhs,
+                impulse,
+                impulse_id,
+            ));
+    }
+}
+
 pub fn build_linear_limits_position_constraint<N: Real>(
     params: &IntegrationParameters<N>,
     body1: &Body<N>,
@@ -2,9 +2,9 @@
 
 use na::{DVectorSliceMut, Real};
 
-use crate::joint::{Joint, JointMotor};
+use crate::joint::{Joint, JointMotor, SoftLimit};
 use crate::object::{BodyPartHandle, Multibody, MultibodyLink, Body};
-use crate::solver::{BilateralGroundConstraint, ConstraintSet, GenericNonlinearConstraint,
+use crate::solver::{BilateralGroundConstraint, ConstraintSet, GenericNonlinearConstraint, ImpulseLimits,
              IntegrationParameters, UnilateralGroundConstraint};
 
 /// Trait implemented by joints using the reduced-coordinates approach and allowing only one degree of freedom.
@@ -17,6 +17,12 @@ pub trait UnitJoint<N: Real>: Joint<N> {
     fn min_position(&self) -> Option<N>;
     /// The upper limit, if any, set to the generalized coordinate of this unit joint.
     fn max_position(&self) -> Option<N>;
+    /// The spring-damper applied when the generalized coordinate exceeds its limits, if they are soft.
+    ///
+    /// If this is `None`, the limits are enforced by infinitely stiff constraints.
+    fn soft_limit(&self) -> Option<SoftLimit<N>> {
+        None
+    }
 }
 
 impl_downcast!(UnitJoint<N> where N: Real);
@@ -73,7 +79,7 @@ pub fn unit_joint_velocity_constraints<N: Real, J: UnitJoint<N>>(
         let impulse_id = link.impulse_id + dof_id * 3;
 
         let constraint = BilateralGroundConstraint {
-            impulse: impulses[impulse_id] * params.warmstart_coeff,
+            impulse: impulses[impulse_id] * params.effective_warmstart_coeff(),
             r: N::one() / inv_r,
             rhs,
             limits,
@@ -104,18 +110,19 @@ pub fn unit_joint_velocity_constraints<N: Real, J: UnitJoint<N>>(
             let inv_r = jacobians[wj_id + link.assembly_id + dof_id]; // = J^t * M^-1 J
 
             let impulse_id = link.impulse_id + dof_id * 3 + 1;
-            let constraint = UnilateralGroundConstraint {
-                impulse: impulses[impulse_id] * params.warmstart_coeff,
-                r: N::one() / inv_r,
-                rhs: dvel,
+            push_limit_constraint(
+                joint,
+                params,
+                err,
+                dvel,
+                impulses[impulse_id],
+                inv_r,
                 impulse_id,
                 assembly_id,
-                j_id: *ground_j_id,
-                wj_id: *ground_j_id + ndofs,
+                *ground_j_id,
                 ndofs,
-            };
-
-            constraints.velocity.unilateral_ground.push(constraint);
+                constraints,
+            );
             *ground_j_id += 2 * ndofs;
         }
     }
@@ -142,23 +149,75 @@ pub fn unit_joint_velocity_constraints<N: Real, J: UnitJoint<N>>(
             let inv_r = -jacobians[wj_id + link.assembly_id + dof_id]; // = J^t * M^-1 J
 
             let impulse_id = link.impulse_id + dof_id * 3 + 2;
-            let constraint = UnilateralGroundConstraint {
-                impulse: impulses[impulse_id] * params.warmstart_coeff,
-                r: N::one() / inv_r,
-                rhs: dvel,
+            push_limit_constraint(
+                joint,
+                params,
+                err,
+                dvel,
+                impulses[impulse_id],
+                inv_r,
                 impulse_id,
                 assembly_id,
-                j_id: *ground_j_id,
-                wj_id: *ground_j_id + ndofs,
+                *ground_j_id,
                 ndofs,
-            };
-
-            constraints.velocity.unilateral_ground.push(constraint);
+                constraints,
+            );
             *ground_j_id += 2 * ndofs;
         }
     }
 }
 
+// Pushes the velocity constraint enforcing a limit exceeded by `err`.
+//
+// Soft limits result in a constraint applying exactly the restoring impulse of the spring-damper,
+// while hard limits result in a unilateral constraint.
+fn push_limit_constraint<N: Real, J: UnitJoint<N>>(
+    joint: &J,
+    params: &IntegrationParameters<N>,
+    err: N,
+    dvel: N,
+    cached_impulse: N,
+    inv_r: N,
+    impulse_id: usize,
+    assembly_id: usize,
+    j_id: usize,
+    ndofs: usize,
+    constraints: &mut ConstraintSet<N>,
+) {
+    if let Some(soft_limit) = joint.soft_limit() {
+        let impulse = soft_limit.restoring_impulse(err, dvel, params.dt);
+        let constraint = BilateralGroundConstraint {
+            impulse,
+            r: N::one() / inv_r,
+            rhs: dvel,
+            limits: ImpulseLimits::Independent {
+                min: impulse,
+                max: impulse,
+            },
+            impulse_id,
+            assembly_id,
+            j_id,
+            wj_id: j_id + ndofs,
+            ndofs,
+        };
+
+        constraints.velocity.bilateral_ground.push(constraint);
+    } else {
+        let constraint = UnilateralGroundConstraint {
+            impulse: cached_impulse * params.effective_warmstart_coeff(),
+            r: N::one() / inv_r,
+            rhs: dvel,
+            impulse_id,
+            assembly_id,
+            j_id,
+            wj_id: j_id + ndofs,
+            ndofs,
+        };
+
+        constraints.velocity.unilateral_ground.push(constraint);
+    }
+}
+
 /// Initializes and generate the position constraints applicable to the multibody links attached
 /// to this joint.
 pub fn unit_joint_position_constraint<N: Real, J: UnitJoint<N>>(
@@ -169,6 +228,11 @@ pub fn unit_joint_position_constraint<N: Real, J: UnitJoint<N>>(
     is_angular: bool,
     jacobians: &mut [N],
 ) -> Option<GenericNonlinearConstraint<N>> {
+    // Soft limits are only enforced by the velocity solver.
+    if joint.soft_limit().is_some() {
+        return None;
+    }
+
     let mut sign = N::one();
     let mut rhs = None;
 
@@ -61,6 +61,14 @@ impl<N: Real> JointConstraint<N> for UniversalConstraint<N> {
         (self.b1, self.b2)
     }
 
+    fn local_anchors(&self) -> (Point<N>, Point<N>) {
+        (self.anchor1, self.anchor2)
+    }
+
+    fn applied_impulses(&self) -> (N, N) {
+        (self.lin_impulses.norm(), self.ang_impulse.abs())
+    }
+
     fn velocity_constraints(
         &mut self,
         _: &IntegrationParameters<N>,
@@ -88,6 +88,11 @@ extern crate ncollide3d as ncollide;
 extern crate num_traits as num;
 extern crate slab;
 extern crate either;
+#[cfg(feature = "serde-serialize")]
+extern crate serde;
+#[cfg(feature = "serde-serialize")]
+#[macro_use]
+extern crate serde_derive;
 
 /*
  * The two following crates are pulled-in for
@@ -8,9 +8,12 @@ use crate::math::Vector;
 ///
 /// Strictly speaking, the coefficient provided here only exist
 /// when considering a pair of touching surfaces. In practice, nphysics
-/// will average the coefficient of the two surfaces in contact in order
-/// to deduce the restitution/friction coefficient.
+/// will combine the coefficient of the two surfaces in contact in order
+/// to deduce the restitution/friction coefficient. The formula used is given
+/// by the combine mode with the highest precedence among the two materials
+/// (see `MaterialCombineMode`). Both combine modes default to `MaterialCombineMode::Average`.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct BasicMaterial<N: Real> {
     /// The ID of this material for automatic lookup.
     pub id: Option<u32>,
@@ -41,6 +41,7 @@ impl<'a, N: Real> MaterialContext<'a, N> {
 
 /// The way the friction and restitution coefficients of two materials should be combined.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum MaterialCombineMode {
     /// Combination by averaging the coefficients from both materials.
     Average,
@@ -5,12 +5,13 @@ use downcast_rs::Downcast;
 use na::{self, DVectorSlice, DVectorSliceMut, Real};
 use ncollide::shape::DeformationsType;
 
-use crate::math::{Force, ForceType, Inertia, Isometry, Point, Vector, Velocity};
+use crate::math::{AngularVector, Force, ForceType, Inertia, Isometry, Point, Vector, Velocity};
 use crate::object::{BodyPartHandle, BodyHandle};
 use crate::solver::{IntegrationParameters, ForceDirection};
 
 /// The status of a body.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub enum BodyStatus {
     /// The body is disabled and ignored by the physics engine.
     Disabled,
@@ -26,8 +27,10 @@ pub enum BodyStatus {
 ///
 /// This controls whether a body is sleeping or not.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct ActivationStatus<N: Real> {
     threshold: Option<N>,
+    sleep_threshold: Option<SleepThreshold<N>>,
     energy: N,
 }
 
@@ -41,6 +44,7 @@ impl<N: Real> ActivationStatus<N> {
     pub fn new_active() -> Self {
         ActivationStatus {
             threshold: Some(Self::default_threshold()),
+            sleep_threshold: None,
             energy: Self::default_threshold() * na::convert(4.0),
         }
     }
@@ -49,6 +53,7 @@ impl<N: Real> ActivationStatus<N> {
     pub fn new_inactive() -> Self {
         ActivationStatus {
             threshold: Some(Self::default_threshold()),
+            sleep_threshold: None,
             energy: N::zero(),
         }
     }
@@ -75,6 +80,22 @@ impl<N: Real> ActivationStatus<N> {
         self.threshold = threshold
     }
 
+    /// The velocity thresholds overriding the energy-based deactivation threshold, if any.
+    #[inline]
+    pub fn sleep_threshold(&self) -> Option<&SleepThreshold<N>> {
+        self.sleep_threshold.as_ref()
+    }
+
+    /// Sets the velocity thresholds overriding the energy-based deactivation threshold.
+    ///
+    /// A value of `None` restores the sleep threshold of the world, or the energy-based deactivation
+    /// threshold if the world has none. The override has no effect
+    /// if the body cannot sleep, i.e., if its deactivation threshold is `None`.
+    #[inline]
+    pub fn set_sleep_threshold(&mut self, threshold: Option<SleepThreshold<N>>) {
+        self.sleep_threshold = threshold
+    }
+
     /// The current energy averaged through several frames.
     #[inline]
     pub fn energy(&self) -> N {
@@ -88,6 +109,28 @@ impl<N: Real> ActivationStatus<N> {
     }
 }
 
+/// Velocity thresholds a body must stay under for some time before it can be put to sleep.
+///
+/// This overrides the energy-based deactivation threshold of a single body, e.g., to let a slowly
+/// settling machinery sleep later than the debris around it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct SleepThreshold<N: Real> {
+    /// The linear velocity under which each part of the body is considered at rest.
+    pub linear: N,
+    /// The angular velocity under which each part of the body is considered at rest.
+    pub angular: N,
+    /// The time, in seconds, the body has to stay at rest before it can be put to sleep.
+    pub time: N,
+}
+
+impl<N: Real> SleepThreshold<N> {
+    /// Creates new sleep thresholds.
+    pub fn new(linear: N, angular: N, time: N) -> Self {
+        SleepThreshold { linear, angular, time }
+    }
+}
+
 /// Trait implemented by all bodies supported by nphysics.
 pub trait Body<N: Real>: Downcast + Send + Sync {
     /// The name of this body.
@@ -173,6 +216,14 @@ pub trait Body<N: Real>: Downcast + Send + Sync {
     /// Put this body to sleep.
     fn deactivate(&mut self);
 
+    /// The number of parts of this body.
+    ///
+    /// By default, this counts the parts returned by `self.part(i)` until it returns `None`. This must be
+    /// overridden by bodies for which `self.part(i)` never returns `None`.
+    fn num_parts(&self) -> usize {
+        (0..).take_while(|i| self.part(*i).is_some()).count()
+    }
+
     /// A reference to the specified body part.
     fn part(&self, i: usize) -> Option<&BodyPart<N>>;
 
@@ -319,6 +370,18 @@ pub trait Body<N: Real>: Downcast + Send + Sync {
 
     /// Apply a local force at a given local point of a part of this body.
     fn apply_local_force_at_local_point(&mut self, part_id: usize, force: &Vector<N>, point: &Point<N>, force_type: ForceType, auto_wake_up: bool);
+
+    /// Apply a torque at the center of mass of a part of this body.
+    #[inline]
+    fn apply_torque(&mut self, part_id: usize, torque: &AngularVector<N>, force_type: ForceType, auto_wake_up: bool) {
+        self.apply_force(part_id, &Force::torque_from_vector(*torque), force_type, auto_wake_up)
+    }
+
+    /// Apply a local torque at the center of mass of a part of this body.
+    #[inline]
+    fn apply_local_torque(&mut self, part_id: usize, torque: &AngularVector<N>, force_type: ForceType, auto_wake_up: bool) {
+        self.apply_local_force(part_id, &Force::torque_from_vector(*torque), force_type, auto_wake_up)
+    }
 }
 
 /// Trait implemented by each part of a body supported by nphysics.
@@ -8,18 +8,23 @@ use crate::object::{Body, Ground};
 ///
 /// This structure is automatically allocated by the physics world.
 /// It cannot be constructed by the end-user.
+///
+/// Each handle carries a unique identifier that is never reused by the same world, so that
+/// the handle of a removed body fails every lookup instead of aliasing a body added afterwards.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct BodyHandle(usize);
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct BodyHandle(usize, u64);
 
 /// A unique identifier of a body part added to the world.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct BodyPartHandle(pub BodyHandle, pub usize);
 
 impl BodyHandle {
     /// The body handle of the ground.
     #[inline]
     pub fn ground() -> Self {
-        BodyHandle(usize::max_value())
+        BodyHandle(usize::max_value(), 0)
     }
 
     /// Tests if this handle corresponds to the ground.
@@ -27,6 +32,14 @@ impl BodyHandle {
     pub fn is_ground(&self) -> bool {
         self.0 == usize::max_value()
     }
+
+    /// The unique identifier of this handle.
+    ///
+    /// Identifiers are allocated incrementally and never reused by the same world.
+    #[inline]
+    pub fn uid(&self) -> u64 {
+        self.1
+    }
 }
 
 impl BodyPartHandle {
@@ -116,6 +129,7 @@ pub trait BodyDesc<N: Real> {
 pub struct BodySet<N: Real> {
     ground: Ground<N>,
     bodies: Slab<Box<Body<N>>>,
+    next_uid: u64,
 }
 
 impl<N: Real> BodySet<N> {
@@ -124,6 +138,7 @@ impl<N: Real> BodySet<N> {
         BodySet {
             ground: Ground::new(),
             bodies: Slab::new(),
+            next_uid: 1,
         }
     }
 
@@ -136,7 +151,8 @@ impl<N: Real> BodySet<N> {
     pub fn add_body<B: BodyDesc<N>>(&mut self, desc: &B, cworld: &mut ColliderWorld<N>) -> &mut B::Body {
         let b_entry = self.bodies.vacant_entry();
         let b_id = b_entry.key();
-        let handle = BodyHandle(b_id);
+        let handle = BodyHandle(b_id, self.next_uid);
+        self.next_uid += 1;
         let body = desc.build_with_handle(cworld, handle);
         b_entry.insert(Box::new(body)).downcast_mut::<B::Body>().expect("Body construction failed with type mismatch.")
     }
@@ -145,7 +161,7 @@ impl<N: Real> BodySet<N> {
     ///
     /// If `body` identify a mutibody link, the whole multibody is removed.
     pub fn remove_body(&mut self, body: BodyHandle) {
-        if !body.is_ground() {
+        if self.contains(body) && !body.is_ground() {
             let _ = self.bodies.remove(body.0);
         }
     }
@@ -153,7 +169,7 @@ impl<N: Real> BodySet<N> {
     /// Returns `true` if the given body exists.
     #[inline]
     pub fn contains(&self, handle: BodyHandle) -> bool {
-        handle.is_ground() || self.bodies.contains(handle.0)
+        handle.is_ground() || self.bodies.get(handle.0).map(|b| b.handle() == handle).unwrap_or(false)
     }
 
     /// Reference to the body identified by `body`.
@@ -164,7 +180,7 @@ impl<N: Real> BodySet<N> {
         if handle.is_ground() {
             Some(&self.ground)
         } else {
-            self.bodies.get(handle.0).map(|b| &**b)
+            self.bodies.get(handle.0).filter(|b| b.handle() == handle).map(|b| &**b)
         }
     }
 
@@ -176,11 +192,14 @@ impl<N: Real> BodySet<N> {
         if handle.is_ground() {
             Some(&mut self.ground)
         } else {
-            self.bodies.get_mut(handle.0).map(|b| &mut **b)
+            self.bodies.get_mut(handle.0).filter(|b| b.handle() == handle).map(|b| &mut **b)
         }
     }
 
     /// Iterator yielding all the bodies on this set.
+    ///
+    /// Bodies are yielded in the order of their slots in this set, which only depends on
+    /// the sequence of insertions and removals performed.
     #[inline]
     pub fn bodies(&self) -> impl Iterator<Item = &Body<N>> {
         self.bodies.iter().map(|e| &**e.1)
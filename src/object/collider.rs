@@ -2,9 +2,13 @@ use std::sync::Arc;
 use std::f64;
 use std::mem;
 use std::any::Any;
-use na::Real;
+use na::{Real, Unit};
 use ncollide::world::{CollisionObject, CollisionObjectHandle, GeometricQueryType, CollisionGroups};
 use ncollide::shape::{ShapeHandle, Shape};
+#[cfg(feature = "dim2")]
+use ncollide::shape::Polyline;
+#[cfg(feature = "dim3")]
+use ncollide::shape::TriMesh;
 
 use crate::math::{Isometry, Vector, Rotation};
 use crate::object::{BodyPartHandle, BodyHandle, Body};
@@ -61,7 +65,9 @@ pub struct ColliderData<N: Real> {
     next: Option<ColliderHandle>,
     // NOTE: needed for the collision filter.
     body_status_dependent_ndofs: usize,
+    body_disabled: bool,
     material: MaterialHandle<N>,
+    one_way_normals: Option<Arc<Vec<Unit<Vector<N>>>>>,
     user_data: Option<Box<Any + Send + Sync>>,
 }
 
@@ -81,7 +87,9 @@ impl<N: Real> ColliderData<N> {
             prev: None,
             next: None,
             body_status_dependent_ndofs,
+            body_disabled: false,
             material,
+            one_way_normals: None,
             user_data: None
         }
     }
@@ -133,6 +141,12 @@ impl<N: Real> ColliderData<N> {
         &*self.material
     }
 
+    /// The local normals of the solid side of this collider, if it is one-way.
+    #[inline]
+    pub fn one_way_normals(&self) -> Option<&[Unit<Vector<N>>]> {
+        self.one_way_normals.as_ref().map(|normals| &normals[..])
+    }
+
     /// A mutable reference to this collider's material.
     ///
     /// If the material is shared, then an internal clone is performed
@@ -152,6 +166,21 @@ impl<N: Real> ColliderData<N> {
     pub(crate) fn set_body_status_dependent_ndofs(&mut self, ndofs: usize) {
         self.body_status_dependent_ndofs = ndofs
     }
+
+    #[inline]
+    pub(crate) fn body_disabled(&self) -> bool {
+        self.body_disabled
+    }
+
+    #[inline]
+    pub(crate) fn set_body_disabled(&mut self, disabled: bool) {
+        self.body_disabled = disabled
+    }
+
+    #[inline]
+    pub(crate) fn set_margin(&mut self, margin: N) {
+        self.margin = margin
+    }
 }
 
 
@@ -237,6 +266,14 @@ impl<N: Real> Collider<N> {
         self.query_type().is_proximity_query()
     }
 
+    /// The local normals of the solid side of this collider, if it is one-way.
+    ///
+    /// See `ColliderDesc::one_way_normals` for details.
+    #[inline]
+    pub fn one_way_normals(&self) -> Option<&[Unit<Vector<N>>]> {
+        self.0.data().one_way_normals()
+    }
+
     /*
      * Original methods from the CollisionObject.
      */
@@ -274,6 +311,8 @@ impl<N: Real> Collider<N> {
     }
 
     /// The collision groups of the collision object.
+    ///
+    /// By default, a collider is member of every group and interacts with every other collider.
     #[inline]
     pub fn collision_groups(&self) -> &CollisionGroups {
         self.0.collision_groups()
@@ -336,7 +375,8 @@ pub struct ColliderDesc<N: Real> {
     density: N,
     linear_prediction: N,
     angular_prediction: N,
-    is_sensor: bool
+    is_sensor: bool,
+    one_way_normals: Option<Arc<Vec<Unit<Vector<N>>>>>
 }
 
 impl<N: Real> ColliderDesc<N> {
@@ -356,7 +396,8 @@ impl<N: Real> ColliderDesc<N> {
             density: N::zero(),
             linear_prediction,
             angular_prediction,
-            is_sensor: false
+            is_sensor: false,
+            one_way_normals: None
         }
     }
 
@@ -381,8 +422,30 @@ impl<N: Real> ColliderDesc<N> {
     desc_custom_setters!(
         self.translation, set_translation, vector: Vector<N> | { self.position.translation.vector = vector }
         self.material, set_material, material: MaterialHandle<N> | { self.material = Some(material) }
+        self.one_way_normal, set_one_way_normal, normal: Unit<Vector<N>> | { self.one_way_normals = Some(Arc::new(vec![normal])) }
     );
 
+    /// Makes the collider one-way, with one local solid-side normal per segment of its polyline shape.
+    ///
+    /// Contacts are ignored while the other collider is on the side opposite to the normal of the
+    /// touched segment, or moves away from the collider along this normal. This allows bodies to
+    /// go through a platform from below and land on it from above. If a single normal is given,
+    /// it applies to the whole shape, whatever its type. Contact events are still reported.
+    #[inline]
+    pub fn one_way_normals(mut self, normals: Vec<Unit<Vector<N>>>) -> Self {
+        self.one_way_normals = Some(Arc::new(normals));
+        self
+    }
+
+    /// Makes the collider one-way, with one local solid-side normal per segment of its polyline shape.
+    ///
+    /// See `ColliderDesc::one_way_normals` for details.
+    #[inline]
+    pub fn set_one_way_normals(&mut self, normals: Vec<Unit<Vector<N>>>) -> &mut Self {
+        self.one_way_normals = Some(Arc::new(normals));
+        self
+    }
+
     desc_setters!(
         shape, set_shape, shape: ShapeHandle<N>
         margin, set_margin, margin: N
@@ -410,6 +473,7 @@ impl<N: Real> ColliderDesc<N> {
         self.get_name: &str | { &self.name }
         self.get_translation: &Vector<N> | { &self.position.translation.vector }
         self.get_material: Option<&Material<N>> | { self.material.as_ref().map(|m| &**m) }
+        self.get_one_way_normals: Option<&[Unit<Vector<N>>]> | { self.one_way_normals.as_ref().map(|n| &n[..]) }
     );
 
     desc_getters!(
@@ -422,6 +486,18 @@ impl<N: Real> ColliderDesc<N> {
         [ref] get_position -> position: Isometry<N>
     );
 
+    /// Whether the shape of this collider is a triangle mesh (3D) or a polyline (2D).
+    ///
+    /// Those shapes have no volume so they can only be used with a zero density, e.g., for static
+    /// geometries. They may still be attached to dynamic bodies with an explicit mass.
+    pub fn has_mesh_shape(&self) -> bool {
+        #[cfg(feature = "dim2")]
+        let is_mesh = self.shape.as_shape::<Polyline<N>>().is_some();
+        #[cfg(feature = "dim3")]
+        let is_mesh = self.shape.as_shape::<TriMesh<N>>().is_some();
+        is_mesh
+    }
+
     /// Builds a collider into the `world` attached to the body part `parent`.
     pub fn build_with_parent<'w>(&self, parent: BodyPartHandle, world: &'w mut World<N>) -> Option<&'w mut Collider<N>> {
         self.do_build(parent, world)
@@ -457,6 +533,10 @@ impl<N: Real> ColliderDesc<N> {
             (self.position, 0)
         } else {
             if !self.density.is_zero() {
+                assert!(
+                    !self.has_mesh_shape(),
+                    "A collider with a triangle mesh or polyline shape must have a zero density."
+                );
                 let com = self.position * self.shape.center_of_mass();
                 let inertia = self.shape.inertia(self.density).transformed(&self.position);
                 body.add_local_inertia_and_com(parent.1, com, inertia);
@@ -472,6 +552,7 @@ impl<N: Real> ColliderDesc<N> {
         let material = self.material.clone().unwrap_or_else(|| cworld.default_material());
         let mut data = ColliderData::new(self.name.clone(), self.margin, anchor, ndofs, material);
         data.user_data = self.user_data.as_ref().map(|data| data.0.to_any());
+        data.one_way_normals = self.one_way_normals.clone();
         Some(cworld.add(pos, self.shape.clone(), self.collision_groups, query, data))
     }
 }
@@ -781,6 +781,10 @@ impl<N: Real> Body<N> for FEMSurface<N> {
         self.activation.set_deactivation_threshold(threshold)
     }
 
+    fn num_parts(&self) -> usize {
+        self.elements.len()
+    }
+
     fn part(&self, id: usize) -> Option<&BodyPart<N>> {
         self.elements.get(id).map(|b| b as &BodyPart<N>)
     }
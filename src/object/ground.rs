@@ -67,6 +67,11 @@ impl<N: Real> Body<N> for Ground<N> {
         BodyUpdateStatus::empty()
     }
 
+    #[inline]
+    fn num_parts(&self) -> usize {
+        1
+    }
+
     #[inline]
     fn part(&self, _: usize) -> Option<&BodyPart<N>> {
         Some(self)
@@ -500,6 +500,10 @@ impl<N: Real> Body<N> for MassConstraintSystem<N> {
         self.velocities.fill(N::zero());
     }
 
+    fn num_parts(&self) -> usize {
+        self.elements.len()
+    }
+
     fn part(&self, id: usize) -> Option<&BodyPart<N>> {
         self.elements.get(id).map(|e| e as &BodyPart<N>)
     }
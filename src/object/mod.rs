@@ -1,13 +1,14 @@
 //! Objects that may be added to the physical world.
 
-pub use self::body::{ActivationStatus, Body, BodyPart, BodyStatus, BodyUpdateStatus};
+pub use self::body::{ActivationStatus, Body, BodyPart, BodyStatus, BodyUpdateStatus, SleepThreshold};
 pub use self::body_set::{Bodies, BodiesMut, BodyPartHandle, BodySet, BodyHandle, BodyDesc};
 pub use self::collider::{Collider, ColliderData, ColliderAnchor, ColliderHandle, ColliderDesc, DeformableColliderDesc};
 pub use self::ground::Ground;
 pub use self::multibody::{Multibody, MultibodyDesc};
 pub(crate) use self::multibody_link::MultibodyLinkVec;
 pub use self::multibody_link::MultibodyLink;
-pub use self::rigid_body::{RigidBody, RigidBodyDesc};
+pub use self::rigid_body::{LockedAxes, RigidBody, RigidBodyDesc, RigidBodyDescError};
+pub(crate) use self::rigid_body::RigidBodyState;
 #[cfg(feature = "dim2")]
 pub use self::fem_surface::{FEMSurface, FEMSurfaceDesc};
 #[cfg(feature = "dim3")]
@@ -757,6 +757,11 @@ impl<N: Real> Body<N> for Multibody<N> {
         self.name = name
     }
 
+    #[inline]
+    fn num_parts(&self) -> usize {
+        self.rbs.len()
+    }
+
     #[inline]
     fn part(&self, id: usize) -> Option<&BodyPart<N>> {
         self.link(id).map(|l| l as &BodyPart<N>)
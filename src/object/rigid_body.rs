@@ -1,21 +1,77 @@
 use std::any::Any;
+use std::error::Error;
+use std::fmt;
 use na::{DVectorSlice, DVectorSliceMut, Real};
 
 use crate::math::{Force, Inertia, Isometry, Point, Rotation, Translation, Vector, Velocity,
                   SpatialVector, SPATIAL_DIM, DIM, Dim, ForceType};
 use crate::object::{ActivationStatus, BodyPartHandle, BodyStatus, Body, BodyPart, BodyHandle,
-                    ColliderDesc, BodyDesc, BodyUpdateStatus};
+                    ColliderDesc, BodyDesc, BodyUpdateStatus, SleepThreshold};
 use crate::solver::{IntegrationParameters, ForceDirection};
 use crate::world::{World, ColliderWorld};
 use crate::utils::{UserData, UserDataBox};
+use crate::volumetric::InertiaTensor;
 use ncollide::shape::DeformationsType;
 use ncollide::utils::IsometryOps;
 
 #[cfg(feature = "dim3")]
 use crate::math::AngularVector;
-#[cfg(feature = "dim3")]
 use crate::utils::GeneralizedCross;
 
+#[cfg(feature = "dim2")]
+bitflags! {
+    /// Flags identifying the degrees of freedom of a rigid body that are locked.
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    pub struct LockedAxes: u8 {
+        /// The translation along the `x` axis.
+        const TRANSLATION_X = 0b001;
+        /// The translation along the `y` axis.
+        const TRANSLATION_Y = 0b010;
+        /// The rotation.
+        const ROTATION = 0b100;
+    }
+}
+
+#[cfg(feature = "dim3")]
+bitflags! {
+    /// Flags identifying the degrees of freedom of a rigid body that are locked.
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    pub struct LockedAxes: u8 {
+        /// The translation along the `x` axis.
+        const TRANSLATION_X = 0b000001;
+        /// The translation along the `y` axis.
+        const TRANSLATION_Y = 0b000010;
+        /// The translation along the `z` axis.
+        const TRANSLATION_Z = 0b000100;
+        /// The rotation about the `x` axis.
+        const ROTATION_X = 0b001000;
+        /// The rotation about the `y` axis.
+        const ROTATION_Y = 0b010000;
+        /// The rotation about the `z` axis.
+        const ROTATION_Z = 0b100000;
+    }
+}
+
+// The part of the state of a rigid body captured by a `WorldSnapshot`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub(crate) struct RigidBodyState<N: Real> {
+    position: Isometry<N>,
+    velocity: Velocity<N>,
+    local_inertia: Inertia<N>,
+    local_com: Point<N>,
+    status: BodyStatus,
+    gravity_enabled: bool,
+    gravity_scale: N,
+    ccd_enabled: bool,
+    locked_axes: LockedAxes,
+    activation: ActivationStatus<N>,
+    jacobian_mask: SpatialVector<N>,
+    max_linear_speed: N,
+    max_angular_speed: N,
+}
 
 /// A rigid body.
 #[derive(Debug)]
@@ -34,6 +90,13 @@ pub struct RigidBody<N: Real> {
     acceleration: Velocity<N>,
     status: BodyStatus,
     gravity_enabled: bool,
+    gravity_scale: N,
+    ccd_enabled: bool,
+    locked_axes: LockedAxes,
+    max_linear_speed: N,
+    max_angular_speed: N,
+    margin: Option<N>,
+    status_before_disabling: BodyStatus,
     activation: ActivationStatus<N>,
     jacobian_mask: SpatialVector<N>,
     companion_id: usize,
@@ -62,6 +125,13 @@ impl<N: Real> RigidBody<N> {
             acceleration: Velocity::zero(),
             status: BodyStatus::Dynamic,
             gravity_enabled: true,
+            gravity_scale: N::one(),
+            ccd_enabled: false,
+            locked_axes: LockedAxes::empty(),
+            max_linear_speed: N::max_value(),
+            max_angular_speed: N::max_value(),
+            margin: None,
+            status_before_disabling: BodyStatus::Dynamic,
             activation: ActivationStatus::new_active(),
             jacobian_mask: SpatialVector::repeat(N::one()),
             companion_id: 0,
@@ -118,6 +188,125 @@ impl<N: Real> RigidBody<N> {
         self.jacobian_mask[2].is_zero()
     }
 
+    /// The factor by which the gravity of the world is multiplied before being applied to this rigid body.
+    pub fn gravity_scale(&self) -> N {
+        self.gravity_scale
+    }
+
+    /// Sets the factor by which the gravity of the world is multiplied before being applied to this rigid body.
+    ///
+    /// A scale of zero makes this rigid body ignore gravity, and a negative scale makes it float upward.
+    pub fn set_gravity_scale(&mut self, scale: N) {
+        self.gravity_scale = scale
+    }
+
+    /// Whether continuous collision detection is enabled for this rigid body.
+    pub fn ccd_enabled(&self) -> bool {
+        self.ccd_enabled
+    }
+
+    /// Enable or disable continuous collision detection for this rigid body.
+    ///
+    /// If enabled, this rigid body will be prevented from tunneling through static
+    /// colliders when it moves, in a single timestep, by more than the half of its smallest extent.
+    pub fn enable_ccd(&mut self, enabled: bool) {
+        self.ccd_enabled = enabled
+    }
+
+    /// Whether this rigid body is enabled, i.e., its status is not `BodyStatus::Disabled`.
+    pub fn is_enabled(&self) -> bool {
+        self.status != BodyStatus::Disabled
+    }
+
+    /// Enables or disables this rigid body without removing it from the world.
+    ///
+    /// A disabled rigid body keeps its handle, position, velocity, and user data, but is ignored by
+    /// the integrator and the constraints solver, and its colliders do not interact with any other
+    /// collider. The bodies it was touching are woken up at the next timestep. Once re-enabled, it
+    /// gets back the status it had before being disabled, is woken up, and its colliders are moved
+    /// to its current position.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled == self.is_enabled() {
+            return;
+        }
+
+        if enabled {
+            let status = self.status_before_disabling;
+            self.set_status(status);
+            self.update_status.set_position_changed(true);
+            self.activate();
+        } else {
+            self.status_before_disabling = self.status;
+            self.set_status(BodyStatus::Disabled);
+        }
+    }
+
+    /// The maximum linear speed of this rigid body (default: infinite).
+    pub fn max_linear_speed(&self) -> N {
+        self.max_linear_speed
+    }
+
+    /// Sets the maximum linear speed of this rigid body.
+    ///
+    /// At each timestep, once the forces and the constraints have been applied, the linear velocity
+    /// is scaled down so that its norm does not exceed this value. This happens before the positions
+    /// are integrated and the continuous collision detection is performed, so a rigid body with CCD
+    /// enabled is still prevented from tunneling when moving at this maximum speed.
+    pub fn set_max_linear_speed(&mut self, max_speed: N) {
+        self.max_linear_speed = max_speed
+    }
+
+    /// The maximum angular speed of this rigid body, in radians per second (default: infinite).
+    pub fn max_angular_speed(&self) -> N {
+        self.max_angular_speed
+    }
+
+    /// Sets the maximum angular speed of this rigid body, in radians per second.
+    ///
+    /// See `self.set_max_linear_speed(...)` for details.
+    pub fn set_max_angular_speed(&mut self, max_speed: N) {
+        self.max_angular_speed = max_speed
+    }
+
+    /// The collision margin given to all the colliders attached to this rigid body, if any.
+    pub fn margin(&self) -> Option<N> {
+        self.margin
+    }
+
+    /// Sets the collision margin of all the colliders attached to this rigid body.
+    ///
+    /// The margin is applied to the colliders at the beginning of the next timestep.
+    /// See `ColliderWorld::set_margin(...)` for details.
+    pub fn set_margin(&mut self, margin: N) {
+        self.margin = Some(margin)
+    }
+
+    /// The degrees of freedom of this rigid body that are locked.
+    pub fn locked_axes(&self) -> LockedAxes {
+        self.locked_axes
+    }
+
+    /// Lock some degrees of freedom of this rigid body.
+    ///
+    /// The locked degrees of freedom are marked as kinematic and their velocity is kept to zero so
+    /// that neither forces, impulses, contacts, nor calls to `.set_velocity(...)` can make this rigid
+    /// body move along them. The degrees of freedom not locked anymore are marked as non-kinematic.
+    pub fn set_locked_axes(&mut self, locked_axes: LockedAxes) {
+        self.update_status.set_status_changed(true);
+        self.update_status.set_velocity_changed(true);
+
+        for i in 0..SPATIAL_DIM {
+            if locked_axes.bits() & (1 << i) != 0 {
+                self.jacobian_mask[i] = N::zero();
+                self.velocity.as_vector_mut()[i] = N::zero();
+            } else if self.locked_axes.bits() & (1 << i) != 0 {
+                self.jacobian_mask[i] = N::one();
+            }
+        }
+
+        self.locked_axes = locked_axes;
+    }
+
     /// Disable all rotations of this rigid body.
     ///
     /// This is the same as setting all the rotations of this rigid body as kinematic and setting
@@ -151,6 +340,31 @@ impl<N: Real> RigidBody<N> {
             }
     }
 
+    /// Lock or unlock all the rotations of this rigid body.
+    ///
+    /// A rigid body with locked rotations still translates and collides normally, but its
+    /// angular velocity is reset to zero and is no longer affected by forces, impulses, or contacts.
+    /// This can be toggled at any time.
+    pub fn set_rotation_locked(&mut self, locked: bool) {
+        if locked {
+            self.disable_all_rotations()
+        } else {
+            self.enable_all_rotations()
+        }
+    }
+
+    /// Whether all the rotations of this rigid body are locked.
+    pub fn is_rotation_locked(&self) -> bool {
+        #[cfg(feature = "dim3")]
+            {
+                self.kinematic_rotations().iter().all(|locked| *locked)
+            }
+        #[cfg(feature = "dim2")]
+            {
+                self.kinematic_rotation()
+            }
+    }
+
     /// Disable all translations of this rigid body.
     ///
     /// This is the same as setting all the translations of this rigid body as kinematic and setting
@@ -189,11 +403,40 @@ impl<N: Real> RigidBody<N> {
         &mut self.activation
     }
 
+    /// The velocity thresholds overriding the energy-based deactivation threshold of this rigid body, if any.
+    #[inline]
+    pub fn sleep_threshold(&self) -> Option<&SleepThreshold<N>> {
+        self.activation.sleep_threshold()
+    }
+
+    /// Sets the velocity thresholds this rigid body has to stay under to be put to sleep.
+    ///
+    /// The body can only sleep once it stayed under both `threshold.linear` and `threshold.angular` during
+    /// `threshold.time` seconds. A value of `None` uses the sleep threshold of the world if it has one,
+    /// and the energy-based deactivation threshold of the activation status of this body otherwise. Because a whole island sleeps at once, an island only
+    /// falls asleep once all its bodies can, so the strictest thresholds among them prevail.
+    #[inline]
+    pub fn set_sleep_threshold(&mut self, threshold: Option<SleepThreshold<N>>) {
+        self.activation.set_sleep_threshold(threshold)
+    }
+
+    /// The center of mass of this rigid body, expressed in its local space.
+    #[inline]
+    pub fn local_center_of_mass(&self) -> &Point<N> {
+        &self.local_com
+    }
+
     /// Set the center of mass of this rigid body, expressed in its local space.
+    ///
+    /// The colliders attached to this body remain positioned relative to the body origin, while
+    /// the body rotates about its center of mass and forces applied at a point use the center of
+    /// mass as the origin of their lever arm. The local inertia is assumed to be expressed about
+    /// this point.
     #[inline]
     pub fn set_local_center_of_mass(&mut self, local_com: Point<N>) {
         self.update_status.set_local_com_changed(true);
         self.local_com = local_com;
+        self.com = self.position * local_com;
     }
 
     fn update_inertia_from_local_inertia(&mut self) {
@@ -238,6 +481,9 @@ impl<N: Real> RigidBody<N> {
     }
 
     /// Sets the position of this rigid body.
+    ///
+    /// The velocity of this rigid body is left unchanged. The attached colliders are moved at the
+    /// beginning of the next timestep; use `World::teleport_rigid_body` to move them immediately.
     #[inline]
     pub fn set_position(&mut self, pos: Isometry<N>) {
         self.update_status.set_position_changed(true);
@@ -245,34 +491,108 @@ impl<N: Real> RigidBody<N> {
         self.com = pos * self.local_com;
     }
 
-    /// Set the velocity of this rigid body.
+    /// Set the velocity of this rigid body and wakes it up.
     #[inline]
     pub fn set_velocity(&mut self, vel: Velocity<N>) {
         self.update_status.set_velocity_changed(true);
         self.velocity = vel;
+        self.activate();
     }
 
-    /// Set the linear velocity of this rigid body.
+    /// Set the linear velocity of this rigid body, in units per second, and wakes it up.
     #[inline]
     pub fn set_linear_velocity(&mut self, vel: Vector<N>) {
         self.update_status.set_velocity_changed(true);
         self.velocity.linear = vel;
+        self.activate();
     }
 
     #[cfg(feature = "dim2")]
-    /// Set the angular velocity of this rigid body.
+    /// Set the angular velocity of this rigid body, in radians per second, and wakes it up.
+    ///
+    /// Positive values rotate counterclockwise.
     #[inline]
     pub fn set_angular_velocity(&mut self, vel: N) {
         self.update_status.set_velocity_changed(true);
         self.velocity.angular = vel;
+        self.activate();
     }
 
     #[cfg(feature = "dim3")]
-    /// Set the angular velocity of this rigid body.
+    /// Set the angular velocity of this rigid body and wakes it up.
+    ///
+    /// The direction of `vel` is the rotation axis and its norm is the rotation speed in radians per second.
     #[inline]
     pub fn set_angular_velocity(&mut self, vel: AngularVector<N>) {
         self.update_status.set_velocity_changed(true);
         self.velocity.angular = vel;
+        self.activate();
+    }
+
+    /// Applies an impulse at the center of mass of this rigid body and wakes it up.
+    ///
+    /// Only the linear velocity is modified. Since no point is involved, there is no lever arm: the offset
+    /// between the origin of this body and its center of mass is ignored. Locked translations are not affected.
+    #[inline]
+    pub fn apply_linear_impulse(&mut self, impulse: Vector<N>) {
+        self.apply_force(0, &Force::linear(impulse), ForceType::Impulse, true)
+    }
+
+    #[cfg(feature = "dim2")]
+    /// Applies an angular impulse to this rigid body and wakes it up.
+    ///
+    /// Only the angular velocity is modified, as if the impulse was applied at the center of mass, so
+    /// the offset between the origin of this body and its center of mass is ignored. Positive values
+    /// rotate counterclockwise. Locked rotations are not affected.
+    #[inline]
+    pub fn apply_angular_impulse(&mut self, impulse: N) {
+        self.apply_force(0, &Force::torque(impulse), ForceType::Impulse, true)
+    }
+
+    #[cfg(feature = "dim3")]
+    /// Applies an angular impulse to this rigid body and wakes it up.
+    ///
+    /// Only the angular velocity is modified, as if the impulse was applied at the center of mass, so
+    /// the offset between the origin of this body and its center of mass is ignored. Locked rotations
+    /// are not affected.
+    #[inline]
+    pub fn apply_angular_impulse(&mut self, impulse: AngularVector<N>) {
+        self.apply_force(0, &Force::torque(impulse), ForceType::Impulse, true)
+    }
+
+    /// The kinetic energy of this rigid body.
+    ///
+    /// Only dynamic rigid bodies have a kinetic energy. The velocity along kinematic
+    /// or locked degrees of freedom is ignored.
+    pub fn kinetic_energy(&self) -> N {
+        if self.status != BodyStatus::Dynamic {
+            return N::zero();
+        }
+
+        let mut vel = self.velocity;
+        vel.as_vector_mut().component_mul_assign(&self.jacobian_mask);
+        let momentum = self.inertia * vel;
+
+        momentum.as_vector().dot(vel.as_vector()) * na::convert(0.5)
+    }
+
+    /// The gravitational potential energy of this rigid body.
+    ///
+    /// The height of the center of mass is measured along the direction opposite to `gravity`, relative to
+    /// `reference_height`. Only dynamic rigid bodies affected by gravity have a potential energy.
+    pub fn potential_energy(&self, gravity: &Vector<N>, reference_height: N) -> N {
+        if self.status != BodyStatus::Dynamic || !self.gravity_enabled {
+            return N::zero();
+        }
+
+        let g = gravity.norm() * self.gravity_scale;
+
+        if g.is_zero() {
+            return N::zero();
+        }
+
+        let height = -self.com.coords.dot(gravity) / gravity.norm();
+        self.inertia.linear * g * (height - reference_height)
     }
 
     /// The augmented mass (inluding gyroscropic terms) in world-space of this rigid body.
@@ -299,6 +619,75 @@ impl<N: Real> RigidBody<N> {
         &self.velocity
     }
 
+    /// The linear velocity of the center of mass of this rigid body, in units per second.
+    #[inline]
+    pub fn linear_velocity(&self) -> &Vector<N> {
+        &self.velocity.linear
+    }
+
+    #[cfg(feature = "dim2")]
+    /// The angular velocity of this rigid body, in radians per second.
+    ///
+    /// Positive values rotate counterclockwise.
+    #[inline]
+    pub fn angular_velocity(&self) -> N {
+        self.velocity.angular
+    }
+
+    #[cfg(feature = "dim3")]
+    /// The angular velocity of this rigid body.
+    ///
+    /// Its direction is the rotation axis and its norm is the rotation speed in radians per second.
+    #[inline]
+    pub fn angular_velocity(&self) -> &AngularVector<N> {
+        &self.velocity.angular
+    }
+
+    /// The velocity of the material point of this rigid body located at `point`, in world-space.
+    ///
+    /// This is `v + w × r` where `r` is the vector from the center of mass to `point`.
+    #[inline]
+    pub fn velocity_at_point(&self, point: &Point<N>) -> Vector<N> {
+        let r = point - self.com;
+        self.velocity.linear + self.velocity.angular_vector().gcross(&r)
+    }
+
+    pub(crate) fn state(&self) -> RigidBodyState<N> {
+        RigidBodyState {
+            position: self.position,
+            velocity: self.velocity,
+            local_inertia: self.local_inertia,
+            local_com: self.local_com,
+            status: self.status,
+            gravity_enabled: self.gravity_enabled,
+            gravity_scale: self.gravity_scale,
+            ccd_enabled: self.ccd_enabled,
+            locked_axes: self.locked_axes,
+            activation: self.activation,
+            jacobian_mask: self.jacobian_mask,
+            max_linear_speed: self.max_linear_speed,
+            max_angular_speed: self.max_angular_speed,
+        }
+    }
+
+    pub(crate) fn restore_state(&mut self, state: &RigidBodyState<N>) {
+        self.set_local_center_of_mass(state.local_com);
+        self.set_position(state.position);
+        self.set_velocity(state.velocity);
+        self.set_local_inertia(state.local_inertia);
+        self.set_status(state.status);
+        self.gravity_enabled = state.gravity_enabled;
+        self.gravity_scale = state.gravity_scale;
+        self.ccd_enabled = state.ccd_enabled;
+        self.locked_axes = state.locked_axes;
+        self.activation = state.activation;
+        self.jacobian_mask = state.jacobian_mask;
+        self.max_linear_speed = state.max_linear_speed;
+        self.max_angular_speed = state.max_angular_speed;
+        self.external_forces = Force::zero();
+        self.update_status.set_status_changed(true);
+    }
+
     #[inline]
     fn apply_displacement(&mut self, displacement: &Velocity<N>) {
         let rotation = Rotation::new(displacement.angular);
@@ -410,6 +799,24 @@ impl<N: Real> Body<N> for RigidBody<N> {
 
     #[inline]
     fn integrate(&mut self, params: &IntegrationParameters<N>) {
+        if !self.locked_axes.is_empty() {
+            for i in 0..SPATIAL_DIM {
+                if self.locked_axes.bits() & (1 << i) != 0 {
+                    self.velocity.as_vector_mut()[i] = N::zero();
+                }
+            }
+        }
+
+        let linear_speed = self.velocity.linear.norm();
+        if linear_speed > self.max_linear_speed {
+            self.velocity.linear *= self.max_linear_speed / linear_speed;
+        }
+
+        let angular_speed = self.velocity.angular_vector().norm();
+        if angular_speed > self.max_angular_speed {
+            self.velocity.angular *= self.max_angular_speed / angular_speed;
+        }
+
         let disp = self.velocity * params.dt;
         self.apply_displacement(&disp);
     }
@@ -477,7 +884,7 @@ impl<N: Real> Body<N> for RigidBody<N> {
                     }
 
                 if self.inv_augmented_mass.linear != N::zero() && self.gravity_enabled {
-                    self.acceleration.linear = *gravity;
+                    self.acceleration.linear = *gravity * self.gravity_scale;
                 }
 
                 self.acceleration += self.inv_augmented_mass * self.external_forces;
@@ -487,6 +894,11 @@ impl<N: Real> Body<N> for RigidBody<N> {
         }
     }
 
+    #[inline]
+    fn num_parts(&self) -> usize {
+        1
+    }
+
     #[inline]
     fn part(&self, _: usize) -> Option<&BodyPart<N>> {
         Some(self)
@@ -594,15 +1006,26 @@ impl<N: Real> Body<N> for RigidBody<N> {
         self.update_status.set_local_com_changed(true);
         self.update_status.set_local_inertia_changed(true);
 
-        // Update center of mass.
-        if !inertia.linear.is_zero() {
-            let mass_sum = self.inertia.linear + inertia.linear;
-            self.local_com = (self.local_com * self.inertia.linear + com.coords * inertia.linear) / mass_sum;
-            self.com = self.position * self.local_com;
+        let mass_sum = self.local_inertia.linear + inertia.linear;
+
+        if mass_sum.is_zero() {
+            self.local_inertia += inertia;
+            self.update_inertia_from_local_inertia();
+            return;
         }
 
-        // Update local inertia.
-        self.local_inertia += inertia;
+        // Update center of mass.
+        let local_com = (self.local_com * self.local_inertia.linear + com.coords * inertia.linear) / mass_sum;
+
+        // Update local inertia, expressing both angular inertias wrt. the new center of mass.
+        let shift1 = Point::from(self.local_com - local_com);
+        let shift2 = Point::from(com - local_com);
+        let angular1 = self.local_inertia.angular_matrix().to_relative_wrt_point(self.local_inertia.linear, &shift1);
+        let angular2 = inertia.angular_matrix().to_relative_wrt_point(inertia.linear, &shift2);
+
+        self.local_com = local_com;
+        self.com = self.position * self.local_com;
+        self.local_inertia = Inertia::new_with_angular_matrix(mass_sum, angular1 + angular2);
         self.update_inertia_from_local_inertia();
     }
 
@@ -700,6 +1123,31 @@ impl<N: Real> BodyPart<N> for RigidBody<N> {
 }
 
 
+/// An error preventing a `RigidBodyDesc` from being built.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RigidBodyDescError {
+    /// The rigid body is dynamic but has neither a positive mass nor any collider with a positive density.
+    ZeroMass,
+    /// The mass of the rigid body, or the density of one of its colliders, is negative.
+    NegativeMass,
+    /// A collider with a triangle mesh or polyline shape has a non-zero density.
+    ///
+    /// Those shapes have no volume so their mass properties cannot be computed.
+    MeshWithDensity,
+}
+
+impl fmt::Display for RigidBodyDescError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RigidBodyDescError::ZeroMass => write!(f, "a dynamic rigid body must have a positive mass"),
+            RigidBodyDescError::NegativeMass => write!(f, "the mass and densities of a rigid body must not be negative"),
+            RigidBodyDescError::MeshWithDensity => write!(f, "a collider with a mesh shape must have a zero density"),
+        }
+    }
+}
+
+impl Error for RigidBodyDescError {}
+
 /// The description of a rigid body, used to build a new `RigidBody`.
 ///
 /// This is the structure to use in order to create and add a rigid body
@@ -720,6 +1168,9 @@ pub struct RigidBodyDesc<'a, N: Real> {
     name: String,
     user_data: Option<UserDataBox>,
     gravity_enabled: bool,
+    gravity_scale: N,
+    ccd_enabled: bool,
+    locked_axes: LockedAxes,
     position: Isometry<N>,
     velocity: Velocity<N>,
     local_inertia: Inertia<N>,
@@ -741,6 +1192,9 @@ impl<'a, N: Real> RigidBodyDesc<'a, N> {
             name: String::new(),
             user_data: None,
             gravity_enabled: true,
+            gravity_scale: N::one(),
+            ccd_enabled: false,
+            locked_axes: LockedAxes::empty(),
             position: Isometry::identity(),
             velocity: Velocity::zero(),
             local_inertia: Inertia::zero(),
@@ -780,6 +1234,9 @@ impl<'a, N: Real> RigidBodyDesc<'a, N> {
 
     desc_setters!(
         gravity_enabled, enable_gravity, gravity_enabled: bool
+        gravity_scale, set_gravity_scale, gravity_scale: N
+        ccd_enabled, enable_ccd, ccd_enabled: bool
+        locked_axes, set_locked_axes, locked_axes: LockedAxes
         status, set_status, status: BodyStatus
         name, set_name, name: String
         position, set_position, position: Isometry<N>
@@ -813,6 +1270,9 @@ impl<'a, N: Real> RigidBodyDesc<'a, N> {
 
     desc_getters!(
         [val] is_gravity_enabled -> gravity_enabled: bool
+        [val] get_gravity_scale -> gravity_scale: N
+        [val] is_ccd_enabled -> ccd_enabled: bool
+        [val] get_locked_axes -> locked_axes: LockedAxes
         [val] get_status -> status: BodyStatus
         [val] get_sleep_threshold -> sleep_threshold: Option<N>
         [ref] get_position -> position: Isometry<N>
@@ -825,6 +1285,35 @@ impl<'a, N: Real> RigidBodyDesc<'a, N> {
     pub fn build<'w>(&mut self, world: &'w mut World<N>) -> &'w mut RigidBody<N> {
         world.add_body(self)
     }
+
+    /// Checks that this description results in a rigid body with valid mass properties.
+    ///
+    /// A dynamic rigid body must either have a positive mass or at least one collider with a positive density.
+    pub fn validate(&self) -> Result<(), RigidBodyDescError> {
+        if self.local_inertia.linear < N::zero() || self.colliders.iter().any(|c| c.get_density() < N::zero()) {
+            return Err(RigidBodyDescError::NegativeMass);
+        }
+
+        if self.colliders.iter().any(|c| !c.get_density().is_zero() && c.has_mesh_shape()) {
+            return Err(RigidBodyDescError::MeshWithDensity);
+        }
+
+        if self.status == BodyStatus::Dynamic
+            && self.local_inertia.linear.is_zero()
+            && self.colliders.iter().all(|c| c.get_density().is_zero()) {
+            return Err(RigidBodyDescError::ZeroMass);
+        }
+
+        Ok(())
+    }
+
+    /// Builds a rigid body and all its attached colliders if `self.validate()` succeeds.
+    ///
+    /// Nothing is added to the world if an error is returned.
+    pub fn try_build<'w>(&mut self, world: &'w mut World<N>) -> Result<&'w mut RigidBody<N>, RigidBodyDescError> {
+        self.validate()?;
+        Ok(self.build(world))
+    }
 }
 
 impl<'a, N: Real> BodyDesc<N> for RigidBodyDesc<'a, N> {
@@ -839,6 +1328,8 @@ impl<'a, N: Real> BodyDesc<N> for RigidBodyDesc<'a, N> {
         rb.set_deactivation_threshold(self.sleep_threshold);
         rb.set_translations_kinematic(self.kinematic_translations);
         rb.enable_gravity(self.gravity_enabled);
+        rb.set_gravity_scale(self.gravity_scale);
+        rb.enable_ccd(self.ccd_enabled);
         rb.set_name(self.name.clone());
         let _ = rb.set_user_data(self.user_data.as_ref().map(|data| data.0.to_any()));
 
@@ -851,6 +1342,8 @@ impl<'a, N: Real> BodyDesc<N> for RigidBodyDesc<'a, N> {
                 rb.set_rotation_kinematic(self.kinematic_rotation);
             }
 
+        rb.set_locked_axes(self.locked_axes);
+
         for desc in &self.colliders {
             let part_handle = rb.part_handle();
             let _ = desc.build_with_infos(part_handle, &mut rb, cworld);
@@ -69,6 +69,8 @@ impl<N: Real> NonlinearConstraints<N> {
 pub struct ConstraintSet<N: Real> {
     /// The velocity constraints constructed.
     pub velocity: Constraints<N>,
+    /// The constraints on the pseudo-velocities used to correct penetrations with split impulses.
+    pub pseudo_velocity: Constraints<N>,
     /// The position constraints constructed.
     pub position: NonlinearConstraints<N>,
 }
@@ -78,18 +80,20 @@ impl<N: Real> ConstraintSet<N> {
     pub fn new() -> Self {
         ConstraintSet {
             velocity: Constraints::new(),
+            pseudo_velocity: Constraints::new(),
             position: NonlinearConstraints::new(),
         }
     }
 
     /// The total number of constraints on this set.
     pub fn len(&self) -> usize {
-        self.velocity.len() + self.position.len()
+        self.velocity.len() + self.pseudo_velocity.len() + self.position.len()
     }
 
     /// Remove all constraints from this set.
     pub fn clear(&mut self) {
         self.velocity.clear();
+        self.pseudo_velocity.clear();
         self.position.clear();
     }
 }
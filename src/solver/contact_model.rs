@@ -2,8 +2,11 @@
 
 use downcast_rs::Downcast;
 use na::{DVector, Real};
+use ncollide::query::TrackedContact;
+use ncollide::utils::GenerationalId;
 
-use crate::detection::ColliderContactManifold;
+use crate::detection::{ColliderContactManifold, ColliderContactManifolds};
+use crate::math::Vector;
 use crate::object::BodySet;
 use crate::material::MaterialsCoefficientsTable;
 use crate::solver::{ConstraintSet, IntegrationParameters};
@@ -19,7 +22,7 @@ pub trait ContactModel<N: Real>: Downcast + Send + Sync {
         material_coefficients: &MaterialsCoefficientsTable<N>,
         bodies: &BodySet<N>,
         ext_vels: &DVector<N>,
-        manifolds: &[ColliderContactManifold<N>],
+        manifolds: ColliderContactManifolds<N>,
         ground_j_id: &mut usize,
         j_id: &mut usize,
         jacobians: &mut [N],
@@ -28,6 +31,40 @@ pub trait ContactModel<N: Real>: Downcast + Send + Sync {
 
     /// Stores all the impulses found by the solver into a cache for warmstarting.
     fn cache_impulses(&mut self, constraints: &ConstraintSet<N>);
+
+    /// Forgets all the impulses cached for warmstarting.
+    ///
+    /// Called when all the contacts are removed at once, since their identifiers may then be
+    /// reused by unrelated contacts.
+    fn clear_impulses(&mut self) {}
+
+    /// Forgets the impulse cached for warmstarting at the given contact.
+    ///
+    /// Called when the contact no longer matches the configuration its impulse was computed for, e.g.,
+    /// after one of the bodies involved was teleported.
+    fn forget_impulse(&mut self, _contact_id: GenerationalId) {}
+
+    /// The normal impulse applied at the given contact during the last timestep.
+    ///
+    /// Returns zero if no impulse is known for this contact.
+    fn normal_impulse(&self, _contact_id: GenerationalId) -> N {
+        N::zero()
+    }
+
+    /// The norm of the friction impulse applied at the given contact during the last timestep.
+    ///
+    /// Returns zero if no impulse is known for this contact, or if this model ignores friction.
+    fn tangent_impulse(&self, _contact_id: GenerationalId) -> N {
+        N::zero()
+    }
+
+    /// The impulse applied at the given contact to the second body during the last timestep, in world-space.
+    ///
+    /// The opposite impulse is applied to the first body. Only the normal impulse is accounted for
+    /// by default, models with friction should add the friction impulse.
+    fn contact_impulse(&self, contact: &TrackedContact<N>) -> Vector<N> {
+        contact.contact.normal.into_inner() * self.normal_impulse(contact.id)
+    }
 }
 
 impl_downcast!(ContactModel<N> where N: Real);
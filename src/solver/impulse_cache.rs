@@ -3,6 +3,10 @@ use num::Zero;
 use std::ops::{Index, IndexMut};
 
 /// A cache for impulses.
+///
+/// Impulses are indexed by the identifier of the contact they were applied to. Contact identifiers
+/// are kept by the collision detection layer as long as the same pair of features remains in contact,
+/// so impulses computed at one step are re-used to warm-start the solver at the next one.
 #[derive(Default)]
 pub struct ImpulseCache<N> {
     cache: Vec<(GenerationalId, N)>,
@@ -49,6 +53,13 @@ impl<N: Copy + Zero> ImpulseCache<N> {
         self.cache[contact_id.id].0 = contact_id;
         contact_id.id
     }
+
+    /// Remove the impulse stored for the specified contact, if any.
+    pub fn remove(&mut self, contact_id: GenerationalId) {
+        if self.contains(contact_id) {
+            self.cache[contact_id.id] = (GenerationalId::invalid(), Zero::zero());
+        }
+    }
 }
 
 impl<N> Index<usize> for ImpulseCache<N> {
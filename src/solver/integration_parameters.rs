@@ -1,6 +1,8 @@
 use na::{self, Real};
 
 /// Parameters for a time-step of the physics engine.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct IntegrationParameters<N: Real> {
     /// The timestep (default: `1.0 / 60.0`)
     pub dt: N,
@@ -11,6 +13,28 @@ pub struct IntegrationParameters<N: Real> {
     /// The Error Reduction Parameter in `[0, 1]` is the proportion of
     /// the positional error to be corrected at each time step (default: `0.2`).
     pub erp: N,
+    /// The Baumgarte stabilization factor in `[0, 1]` is the proportion of the penetration depth of
+    /// each contact converted into a separating velocity by the velocity solver (default: `0.0`).
+    ///
+    /// Values between `0.1` and `0.3` are usually stable; larger values add energy to the system
+    /// and cause jitter. Penetrations smaller than `allowed_linear_error` are not corrected.
+    /// This correction occurs in addition to the non-linear position solver, so it may be useful
+    /// to reduce `max_position_iterations` when it is enabled. Setting it to zero disables it, leaving
+    /// penetrations to be corrected by position projection only. It is ignored if `split_impulse` is set.
+    pub baumgarte_factor: N,
+    /// Whether contact penetrations are corrected by a separate pseudo-velocity pass (default: `false`).
+    ///
+    /// After the velocity solver, the non-penetration constraints are solved a second time for
+    /// pseudo-velocities separating the penetrating bodies by a proportion `erp` of their penetration.
+    /// Those pseudo-velocities move the bodies but are then discarded so that deep penetrations do not
+    /// add kinetic energy to the system. This replaces both the Baumgarte stabilization and the
+    /// correction of contacts by the non-linear position solver.
+    pub split_impulse: bool,
+    /// Whether the impulses cached at the previous timestep are re-used to initialize the solver (default: `true`).
+    ///
+    /// If disabled, the solver starts from zero impulses at each timestep so stacks take more
+    /// iterations to stabilize.
+    pub warm_starting: bool,
     /// Each cached impulse are multiplied by this coefficient in `[0, 1]`
     /// when they are re-used to initialize the solver (default `1.0`).
     pub warmstart_coeff: N,
@@ -30,9 +54,9 @@ pub struct IntegrationParameters<N: Real> {
     /// correction direction is close to the kernel of the involved multibody's
     /// jacobian (default: `0.2`).
     pub max_stabilization_multiplier: N,
-    /// Maximum number of iterations performed by the velocity constraints solver.
+    /// Maximum number of iterations performed by the velocity constraints solver (default: `8`).
     pub max_velocity_iterations: usize,
-    /// Maximum number of iterations performed by the position-based constraints solver.
+    /// Maximum number of iterations performed by the position-based constraints solver (default: `3`).
     pub max_position_iterations: usize,
 }
 
@@ -55,6 +79,9 @@ impl<N: Real> IntegrationParameters<N> {
             t: N::zero(),
             dt,
             erp,
+            baumgarte_factor: N::zero(),
+            split_impulse: false,
+            warm_starting: true,
             warmstart_coeff,
             restitution_velocity_threshold,
             allowed_linear_error,
@@ -66,6 +93,17 @@ impl<N: Real> IntegrationParameters<N> {
             max_position_iterations,
         }
     }
+
+    /// The coefficient the cached impulses are multiplied by to initialize the solver.
+    ///
+    /// This is `self.warmstart_coeff` if warm starting is enabled, and zero otherwise.
+    pub fn effective_warmstart_coeff(&self) -> N {
+        if self.warm_starting {
+            self.warmstart_coeff
+        } else {
+            N::zero()
+        }
+    }
 }
 
 impl<N: Real> Default for IntegrationParameters<N> {
@@ -0,0 +1,48 @@
+use na::{DVectorSlice, Real};
+
+use crate::object::Body;
+use crate::solver::IntegrationParameters;
+
+/// A scheme integrating the motion of a body during one timestep.
+///
+/// The external accelerations of a body are assumed constant during a timestep. Integrators are
+/// given the velocity changes due to those accelerations and due to the constraints, and must update
+/// both the velocity and the position of the body accordingly.
+pub trait Integrator<N: Real>: Send + Sync {
+    /// Updates the generalized velocity and the position of `body`.
+    ///
+    /// The velocity change `ext_vels` results from the external forces applied to the body while
+    /// `constraint_vels` results from the constraints solved by the velocity solver.
+    fn integrate(
+        &self,
+        params: &IntegrationParameters<N>,
+        body: &mut Body<N>,
+        ext_vels: DVectorSlice<N>,
+        constraint_vels: DVectorSlice<N>,
+    );
+}
+
+/// The semi-implicit (symplectic) Euler integration scheme.
+///
+/// Velocities are updated first and then used to update the positions. This is the default integrator
+/// and the one best suited for scenes with many contacts.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SymplecticEuler;
+
+impl<N: Real> Integrator<N> for SymplecticEuler {
+    fn integrate(
+        &self,
+        params: &IntegrationParameters<N>,
+        body: &mut Body<N>,
+        ext_vels: DVectorSlice<N>,
+        constraint_vels: DVectorSlice<N>,
+    ) {
+        {
+            let mut vels = body.generalized_velocity_mut();
+            vels += ext_vels;
+            vels += constraint_vels;
+        }
+
+        body.integrate(params);
+    }
+}
@@ -9,6 +9,7 @@ pub use self::contact_model::ContactModel;
 pub use self::helper::ForceDirection;
 pub use self::impulse_cache::ImpulseCache;
 pub use self::integration_parameters::IntegrationParameters;
+pub use self::integrator::{Integrator, SymplecticEuler};
 pub use self::moreau_jean_solver::MoreauJeanSolver;
 pub use self::nonlinear_constraint::{
     GenericNonlinearConstraint, MultibodyJointLimitsNonlinearConstraintGenerator,
@@ -25,6 +26,7 @@ mod contact_model;
 pub mod helper;
 mod impulse_cache;
 mod integration_parameters;
+mod integrator;
 mod moreau_jean_solver;
 mod nonlinear_constraint;
 mod nonlinear_sor_prox;
@@ -1,13 +1,15 @@
 use slab::Slab;
 
 use na::{DVector, Real};
+use ncollide::utils::GenerationalId;
 
 use crate::counters::Counters;
-use crate::detection::ColliderContactManifold;
-use crate::joint::JointConstraint;
+use crate::detection::{ColliderContactManifoldHandles, ColliderContactManifolds, Island};
+use crate::joint::{ConstraintHandle, JointConstraint};
 use crate::object::{BodyHandle, BodySet};
 use crate::material::MaterialsCoefficientsTable;
-use crate::solver::{ConstraintSet, ContactModel, IntegrationParameters, NonlinearSORProx, SORProx};
+use crate::solver::{ConstraintSet, ContactModel, IntegrationParameters, Integrator, NonlinearSORProx,
+                    SORProx, SymplecticEuler};
 use crate::world::ColliderWorld;
 
 /// Moreau-Jean time-stepping scheme.
@@ -15,8 +17,10 @@ pub struct MoreauJeanSolver<N: Real> {
     jacobians: Vec<N>,
     // FIXME: use a Vec or a DVector?
     mj_lambda_vel: DVector<N>,
+    mj_lambda_pseudo_vel: DVector<N>,
     ext_vels: DVector<N>,
     contact_model: Box<ContactModel<N>>,
+    integrator: Box<Integrator<N>>,
     constraints: ConstraintSet<N>,
     internal_constraints: Vec<BodyHandle>,
 }
@@ -29,47 +33,106 @@ impl<N: Real> MoreauJeanSolver<N> {
         MoreauJeanSolver {
             jacobians: Vec::new(),
             mj_lambda_vel: DVector::zeros(0),
+            mj_lambda_pseudo_vel: DVector::zeros(0),
             ext_vels: DVector::zeros(0),
             contact_model,
+            integrator: Box::new(SymplecticEuler),
             constraints,
             internal_constraints: Vec::new(),
         }
     }
 
+    /// The contact model.
+    pub fn contact_model(&self) -> &ContactModel<N> {
+        &*self.contact_model
+    }
+
     /// Sets the contact model.
     pub fn set_contact_model(&mut self, model: Box<ContactModel<N>>) {
         self.contact_model = model
     }
 
+    /// Forgets all the impulses cached by the contact model for warmstarting.
+    pub fn clear_impulses(&mut self) {
+        self.contact_model.clear_impulses()
+    }
+
+    /// Forgets the impulse cached by the contact model for warmstarting at the given contact.
+    pub fn forget_impulse(&mut self, contact_id: GenerationalId) {
+        self.contact_model.forget_impulse(contact_id)
+    }
+
+    /// Sets the scheme used to integrate the motion of the bodies.
+    pub fn set_integrator(&mut self, integrator: Box<Integrator<N>>) {
+        self.integrator = integrator
+    }
+
     /// Perform one step of the time-stepping scheme.
+    ///
+    /// Each island is solved independently. The contact manifolds, retrieved from `cworld` by their
+    /// handles, must be sorted by island, as done by `IslandSet::sort_manifolds`.
     pub fn step(
         &mut self,
         counters: &mut Counters,
         bodies: &mut BodySet<N>,
         joints: &mut Slab<Box<JointConstraint<N>>>,
-        manifolds: &[ColliderContactManifold<N>],
+        manifolds: &[ColliderContactManifoldHandles],
+        islands: &[Island],
+        params: &IntegrationParameters<N>,
+        coefficients: &MaterialsCoefficientsTable<N>,
+        cworld: &ColliderWorld<N>,
+    ) {
+        counters.reset_solver_timers();
+        counters.set_nconstraints(0);
+
+        for island in islands {
+            self.step_island(
+                counters,
+                bodies,
+                joints,
+                ColliderContactManifolds::new(cworld, &manifolds[island.manifolds()]),
+                island.bodies(),
+                island.joints(),
+                params,
+                coefficients,
+                cworld,
+            );
+        }
+    }
+
+    fn step_island(
+        &mut self,
+        counters: &mut Counters,
+        bodies: &mut BodySet<N>,
+        joints: &mut Slab<Box<JointConstraint<N>>>,
+        manifolds: ColliderContactManifolds<N>,
         island: &[BodyHandle],
+        island_joints: &[ConstraintHandle],
         params: &IntegrationParameters<N>,
         coefficients: &MaterialsCoefficientsTable<N>,
         cworld: &ColliderWorld<N>,
     ) {
-        counters.assembly_started();
-        self.assemble_system(counters, params, coefficients, bodies, joints, manifolds, island);
+        counters.assembly_resumed();
+        self.assemble_system(counters, params, coefficients, bodies, joints, manifolds, island, island_joints);
         counters.assembly_completed();
 
-        counters.set_nconstraints(self.constraints.velocity.len());
+        let nconstraints = counters.solver().nconstraints + self.constraints.velocity.len();
+        counters.set_nconstraints(nconstraints);
 
-        counters.velocity_resolution_started();
+        counters.velocity_resolution_resumed();
         self.solve_velocity_constraints(params, bodies);
-        self.save_cache(bodies, joints);
+        self.save_cache(bodies, joints, island_joints);
         counters.velocity_resolution_completed();
 
-        counters.velocity_update_started();
+        counters.velocity_update_resumed();
         self.update_velocities_and_integrate(params, bodies, island);
         counters.velocity_update_completed();
 
-        counters.position_resolution_started();
-        self.solve_position_constraints(params, cworld, bodies, joints);
+        counters.position_resolution_resumed();
+        if params.split_impulse {
+            self.solve_pseudo_velocity_constraints(params, bodies, island);
+        }
+        self.solve_position_constraints(params, cworld, bodies, joints, island_joints);
         counters.position_resolution_completed();
     }
 
@@ -80,8 +143,9 @@ impl<N: Real> MoreauJeanSolver<N> {
         coefficients: &MaterialsCoefficientsTable<N>,
         bodies: &mut BodySet<N>,
         joints: &mut Slab<Box<JointConstraint<N>>>,
-        manifolds: &[ColliderContactManifold<N>],
+        manifolds: ColliderContactManifolds<N>,
         island: &[BodyHandle],
+        island_joints: &[ConstraintHandle],
     ) {
         self.internal_constraints.clear();
         let mut system_ndofs = 0;
@@ -126,7 +190,9 @@ impl<N: Real> MoreauJeanSolver<N> {
         let mut jacobian_sz = 0;
         let mut ground_jacobian_sz = 0;
 
-        for (_, g) in joints.iter() {
+        for handle in island_joints {
+            let g = try_continue!(joints.get(*handle));
+
             if g.is_active(bodies) {
                 let (b1, b2) = g.anchors();
                 let body1 = try_continue!(bodies.body(b1.0));
@@ -146,10 +212,10 @@ impl<N: Real> MoreauJeanSolver<N> {
             }
         }
 
-        for m in manifolds {
+        for m in manifolds.iter() {
             let ndofs1 = try_continue!(bodies.body(m.body1())).status_dependent_ndofs();
             let ndofs2 = try_continue!(bodies.body(m.body2())).status_dependent_ndofs();
-            let sz = self.contact_model.num_velocity_constraints(m) * (ndofs1 + ndofs2) * 2;
+            let sz = self.contact_model.num_velocity_constraints(&m) * (ndofs1 + ndofs2) * 2;
 
             if ndofs1 == 0 || ndofs2 == 0 {
                 ground_jacobian_sz += sz;
@@ -169,7 +235,9 @@ impl<N: Real> MoreauJeanSolver<N> {
         let mut j_id = 0;
         let mut ground_j_id = jacobian_sz;
 
-        for (_, g) in joints {
+        for handle in island_joints {
+            let g = try_continue!(joints.get_mut(*handle));
+
             if g.is_active(bodies) {
                 g.velocity_constraints(
                     params,
@@ -220,12 +288,42 @@ impl<N: Real> MoreauJeanSolver<N> {
         );
     }
 
+    fn solve_pseudo_velocity_constraints(
+        &mut self,
+        params: &IntegrationParameters<N>,
+        bodies: &mut BodySet<N>,
+        island: &[BodyHandle],
+    ) {
+        SORProx::solve(
+            bodies,
+            &mut self.constraints.pseudo_velocity.unilateral_ground,
+            &mut self.constraints.pseudo_velocity.unilateral,
+            &mut self.constraints.pseudo_velocity.bilateral_ground,
+            &mut self.constraints.pseudo_velocity.bilateral,
+            &[],
+            &mut self.mj_lambda_pseudo_vel,
+            &self.jacobians,
+            params.max_velocity_iterations,
+        );
+
+        // The pseudo-velocities move the bodies but are not added to their actual velocities.
+        for handle in island {
+            let body = try_continue!(bodies.body_mut(*handle));
+            let id = body.companion_id();
+            let ndofs = body.ndofs();
+
+            self.mj_lambda_pseudo_vel.rows_mut(id, ndofs).apply(|v| v * params.dt);
+            body.apply_displacement(&self.mj_lambda_pseudo_vel.as_slice()[id..id + ndofs]);
+        }
+    }
+
     fn solve_position_constraints(
         &mut self,
         params: &IntegrationParameters<N>,
         cworld: &ColliderWorld<N>,
         bodies: &mut BodySet<N>,
         joints: &mut Slab<Box<JointConstraint<N>>>,
+        island_joints: &[ConstraintHandle],
     ) {
         NonlinearSORProx::solve(
             params,
@@ -233,6 +331,7 @@ impl<N: Real> MoreauJeanSolver<N> {
             bodies,
             &mut self.constraints.position.unilateral,
             joints,
+            island_joints,
             &self.internal_constraints,
             &mut self.jacobians,
             params.max_position_iterations,
@@ -243,10 +342,13 @@ impl<N: Real> MoreauJeanSolver<N> {
         &mut self,
         bodies: &mut BodySet<N>,
         joints: &mut Slab<Box<JointConstraint<N>>>,
+        island_joints: &[ConstraintHandle],
     ) {
         self.contact_model.cache_impulses(&self.constraints);
 
-        for (_, g) in joints {
+        for handle in island_joints {
+            let g = try_continue!(joints.get_mut(*handle));
+
             if g.is_active(bodies) {
                 g.cache_impulses(&self.constraints);
             }
@@ -254,9 +356,17 @@ impl<N: Real> MoreauJeanSolver<N> {
     }
 
     fn resize_buffers(&mut self, ndofs: usize) {
-        // XXX: use resize functions instead of reallocating.
-        self.mj_lambda_vel = DVector::zeros(ndofs);
-        self.ext_vels = DVector::zeros(ndofs);
+        // The buffers are only grown so that they are not reallocated at each step.
+        // Their extra rows are never read by the solver.
+        if self.mj_lambda_vel.len() < ndofs {
+            self.mj_lambda_vel = DVector::zeros(ndofs);
+            self.mj_lambda_pseudo_vel = DVector::zeros(ndofs);
+            self.ext_vels = DVector::zeros(ndofs);
+        } else {
+            self.mj_lambda_vel.rows_mut(0, ndofs).fill(N::zero());
+            self.mj_lambda_pseudo_vel.rows_mut(0, ndofs).fill(N::zero());
+            self.ext_vels.rows_mut(0, ndofs).fill(N::zero());
+        }
     }
 
     fn update_velocities_and_integrate(
@@ -270,13 +380,12 @@ impl<N: Real> MoreauJeanSolver<N> {
             let id = body.companion_id();
             let ndofs = body.ndofs();
 
-            {
-                let mut mb_vels = body.generalized_velocity_mut();
-                mb_vels += self.ext_vels.rows(id, ndofs);
-                mb_vels += self.mj_lambda_vel.rows(id, ndofs);
-            }
-
-            body.integrate(params);
+            self.integrator.integrate(
+                params,
+                body,
+                self.ext_vels.rows(id, ndofs),
+                self.mj_lambda_vel.rows(id, ndofs),
+            );
         }
     }
 }
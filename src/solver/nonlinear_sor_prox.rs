@@ -3,7 +3,7 @@ use slab::Slab;
 use std::ops::MulAssign;
 
 use crate::world::ColliderWorld;
-use crate::joint::JointConstraint;
+use crate::joint::{ConstraintHandle, JointConstraint};
 use crate::object::{BodySet, ColliderAnchor, BodyHandle};
 use crate::solver::{ForceDirection, IntegrationParameters, NonlinearConstraintGenerator,
                     NonlinearUnilateralConstraint, GenericNonlinearConstraint};
@@ -20,6 +20,7 @@ impl NonlinearSORProx {
         bodies: &mut BodySet<N>,
         constraints: &mut [NonlinearUnilateralConstraint<N>],
         joints_constraints: &Slab<Box<JointConstraint<N>>>, // FIXME: ugly, use a slice of refs instead.
+        island_joints: &[ConstraintHandle],
         internal_constraints: &[BodyHandle],
         jacobians: &mut [N],
         max_iter: usize,
@@ -32,8 +33,10 @@ impl NonlinearSORProx {
                 Self::solve_unilateral(params, cworld, bodies, constraint, jacobians, dim1, dim2);
             }
 
-            for joint in &*joints_constraints {
-                Self::solve_generator(params, bodies, &**joint.1, jacobians)
+            for handle in island_joints {
+                if let Some(joint) = joints_constraints.get(*handle) {
+                    Self::solve_generator(params, bodies, &**joint, jacobians)
+                }
             }
 
             for constraint in internal_constraints {
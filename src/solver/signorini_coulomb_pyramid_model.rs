@@ -2,7 +2,10 @@ use alga::linear::FiniteDimInnerSpace;
 use na::{self, DVector, Real, Unit};
 use std::ops::Range;
 
-use crate::detection::ColliderContactManifold;
+use ncollide::query::TrackedContact;
+use ncollide::utils::GenerationalId;
+
+use crate::detection::{ColliderContactManifold, ColliderContactManifolds};
 use crate::math::{Vector, DIM};
 use crate::object::BodySet;
 use crate::material::{Material, MaterialContext, MaterialsCoefficientsTable};
@@ -53,7 +56,7 @@ impl<N: Real> ContactModel<N> for SignoriniCoulombPyramidModel<N> {
         coefficients: &MaterialsCoefficientsTable<N>,
         bodies: &BodySet<N>,
         ext_vels: &DVector<N>,
-        manifolds: &[ColliderContactManifold<N>],
+        manifolds: ColliderContactManifolds<N>,
         ground_j_id: &mut usize,
         j_id: &mut usize,
         jacobians: &mut [N],
@@ -64,7 +67,8 @@ impl<N: Real> ContactModel<N> for SignoriniCoulombPyramidModel<N> {
         let id_friction_ground = constraints.velocity.bilateral_ground.len();
         let id_friction = constraints.velocity.bilateral.len();
 
-        for manifold in manifolds {
+        for manifold in manifolds.iter() {
+            let manifold = &manifold;
             let body1 = try_continue!(bodies.body(manifold.body1()));
             let body2 = try_continue!(bodies.body(manifold.body2()));
 
@@ -103,7 +107,9 @@ impl<N: Real> ContactModel<N> for SignoriniCoulombPyramidModel<N> {
                     constraints,
                 );
 
-                SignoriniModel::build_position_constraint(bodies, manifold, c, constraints);
+                if !params.split_impulse {
+                    SignoriniModel::build_position_constraint(bodies, manifold, c, constraints);
+                }
 
                 let dependency;
 
@@ -155,7 +161,7 @@ impl<N: Real> ContactModel<N> for SignoriniCoulombPyramidModel<N> {
                         Some(&mut rhs)
                     );
 
-                    let warmstart = impulse[i] * params.warmstart_coeff;
+                    let warmstart = impulse[i] * params.effective_warmstart_coeff();
 
                     if geom.is_ground_constraint() {
                         let constraint = BilateralGroundConstraint::new(
@@ -217,4 +223,38 @@ impl<N: Real> ContactModel<N> for SignoriniCoulombPyramidModel<N> {
             self.impulses[c.impulse_id / DIM][c.impulse_id % DIM] = c.impulse;
         }
     }
+
+    fn clear_impulses(&mut self) {
+        self.impulses.clear()
+    }
+
+    fn forget_impulse(&mut self, contact_id: GenerationalId) {
+        self.impulses.remove(contact_id)
+    }
+
+    fn normal_impulse(&self, contact_id: GenerationalId) -> N {
+        self.impulses.get(contact_id)[0]
+    }
+
+    fn tangent_impulse(&self, contact_id: GenerationalId) -> N {
+        let impulse = self.impulses.get(contact_id);
+        (1..DIM).fold(N::zero(), |sq, i| sq + impulse[i] * impulse[i]).sqrt()
+    }
+
+    fn contact_impulse(&self, contact: &TrackedContact<N>) -> Vector<N> {
+        let impulse = self.impulses.get(contact.id);
+        let normal = contact.contact.normal.into_inner();
+        let mut res = normal * impulse[0];
+        let mut i = 1;
+
+        // The friction impulses are applied along the same basis as the one used by `self.constraints(...)`,
+        // with the opposite sign to the normal impulse on the second body.
+        Vector::orthonormal_subspace_basis(&[normal], |friction_dir| {
+            res -= *friction_dir * impulse[i];
+            i += 1;
+            true
+        });
+
+        res
+    }
 }
@@ -2,8 +2,8 @@ use na::{self, DVector, Real};
 use std::ops::Range;
 
 use ncollide::query::TrackedContact;
-use ncollide::utils::IsometryOps;
-use crate::detection::ColliderContactManifold;
+use ncollide::utils::{GenerationalId, IsometryOps};
+use crate::detection::{ColliderContactManifold, ColliderContactManifolds};
 use crate::object::{BodySet, Body, BodyPart};
 use crate::material::{Material, MaterialContext, MaterialsCoefficientsTable, LocalMaterialProperties};
 use crate::solver::helper;
@@ -82,15 +82,39 @@ impl<N: Real> SignoriniModel<N> {
 
         // Handle predictive contact if no penetration.
         let depth = c.contact.depth + data1.margin() + data2.margin();
+        let mut pseudo_rhs = N::zero();
         if depth < N::zero() {
             rhs += (-depth) / params.dt;
+            pseudo_rhs = (-depth) / params.dt;
+        } else if depth > params.allowed_linear_error {
+            if params.split_impulse {
+                // The penetration is corrected by the pseudo-velocities only.
+                pseudo_rhs = -(depth - params.allowed_linear_error) * params.erp / params.dt;
+            } else {
+                // Baumgarte stabilization.
+                rhs -= (depth - params.allowed_linear_error) * params.baumgarte_factor / params.dt;
+            }
         }
 
         // FIXME: would it be more efficient to consider the contact active iff. the rhs
         // is still negative at this point?
 
-        let warmstart = impulse * params.warmstart_coeff;
+        let warmstart = impulse * params.effective_warmstart_coeff();
         if geom.is_ground_constraint() {
+            if params.split_impulse {
+                constraints
+                    .pseudo_velocity
+                    .unilateral_ground
+                    .push(UnilateralGroundConstraint::new(
+                        geom,
+                        assembly_id1,
+                        assembly_id2,
+                        pseudo_rhs,
+                        N::zero(),
+                        impulse_id,
+                    ));
+            }
+
             constraints
                 .velocity
                 .unilateral_ground
@@ -105,6 +129,20 @@ impl<N: Real> SignoriniModel<N> {
 
             return true;
         } else {
+            if params.split_impulse {
+                constraints
+                    .pseudo_velocity
+                    .unilateral
+                    .push(UnilateralConstraint::new(
+                        geom,
+                        assembly_id1,
+                        assembly_id2,
+                        pseudo_rhs,
+                        N::zero(),
+                        impulse_id,
+                    ));
+            }
+
             constraints
                 .velocity
                 .unilateral
@@ -192,7 +230,7 @@ impl<N: Real> ContactModel<N> for SignoriniModel<N> {
         coefficients: &MaterialsCoefficientsTable<N>,
         bodies: &BodySet<N>,
         ext_vels: &DVector<N>,
-        manifolds: &[ColliderContactManifold<N>],
+        manifolds: ColliderContactManifolds<N>,
         ground_j_id: &mut usize,
         j_id: &mut usize,
         jacobians: &mut [N],
@@ -201,7 +239,8 @@ impl<N: Real> ContactModel<N> for SignoriniModel<N> {
         let id_vel_ground = constraints.velocity.unilateral_ground.len();
         let id_vel = constraints.velocity.unilateral.len();
 
-        for manifold in manifolds {
+        for manifold in manifolds.iter() {
+            let manifold = &manifold;
             let body1 = try_ret!(bodies.body(manifold.body1()));
             let body2 = try_ret!(bodies.body(manifold.body2()));
 
@@ -237,7 +276,9 @@ impl<N: Real> ContactModel<N> for SignoriniModel<N> {
                     constraints,
                 );
 
-                Self::build_position_constraint(bodies, manifold, c, constraints);
+                if !params.split_impulse {
+                    Self::build_position_constraint(bodies, manifold, c, constraints);
+                }
             }
         }
 
@@ -257,4 +298,16 @@ impl<N: Real> ContactModel<N> for SignoriniModel<N> {
             self.impulses[c.impulse_id] = c.impulse;
         }
     }
+
+    fn clear_impulses(&mut self) {
+        self.impulses.clear()
+    }
+
+    fn forget_impulse(&mut self, contact_id: GenerationalId) {
+        self.impulses.remove(contact_id)
+    }
+
+    fn normal_impulse(&self, contact_id: GenerationalId) -> N {
+        self.impulses.get(contact_id)
+    }
 }
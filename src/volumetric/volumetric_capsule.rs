@@ -1,3 +1,5 @@
+use num::Zero;
+
 use na::Real;
 use ncollide::shape::Capsule;
 
@@ -16,22 +18,34 @@ pub fn capsule_area<N: Real>(half_height: N, radius: N) -> N {
 
 /// Computes the unit angular inertia of a capsule.
 pub fn capsule_unit_angular_inertia<N: Real>(half_height: N, radius: N) -> AngularInertia<N> {
-    let mut res = volumetric::cylinder_unit_angular_inertia(half_height, radius);
-    res += volumetric::ball_unit_angular_inertia(radius);
+    let cylinder_mass = volumetric::cylinder_volume(half_height, radius);
+    let ball_mass = volumetric::ball_volume(radius);
+    let total_mass = cylinder_mass + ball_mass;
 
-    // Parallel axis theorem for the hemispheres.
-    if cfg!(feature = "dim3") {
-        let h = half_height * na::convert(2.0);
-        let extra = h * h * na::convert(0.5) + h * radius * na::convert(3.0 / 8.0);
-        res[(0, 0)] += extra;
-        res[(2, 2)] += extra;
+    if total_mass.is_zero() {
+        return AngularInertia::zero();
+    }
+
+    let cylinder_inertia = volumetric::cylinder_unit_angular_inertia(half_height, radius) * cylinder_mass;
+    let mut ball_inertia = volumetric::ball_unit_angular_inertia(radius) * ball_mass;
+
+    // Parallel axis theorem for the hemispheres. `d` is the distance between the
+    // center of mass of one hemisphere and the center of its flat face.
+    let _2: N = na::convert(2.0f64);
+    let d = if cfg!(feature = "dim3") {
+        radius * na::convert(3.0f64 / 8.0)
     } else {
-        let h = half_height * na::convert(2.0);
-        let extra = h * h * na::convert(0.5) + h * radius * na::convert(3.0 / 8.0);
-        res[(0, 0)] += extra;
+        radius * na::convert(4.0f64) / (N::pi() * na::convert(3.0f64))
+    };
+    let extra = ball_mass * (half_height * half_height + _2 * half_height * d);
+
+    ball_inertia[(0, 0)] += extra;
+
+    if cfg!(feature = "dim3") {
+        ball_inertia[(2, 2)] += extra;
     }
 
-    res
+    (cylinder_inertia + ball_inertia) / total_mass
 }
 
 
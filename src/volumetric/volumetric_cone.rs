@@ -69,8 +69,9 @@ where
         3 => {
             let sq_radius = radius * radius;
             let sq_height = half_height * half_height * na::convert(4.0f64);
+            // Expressed wrt. the center of mass, not the apex.
             let off_principal =
-                sq_radius * na::convert(3.0f64 / 20.0) + sq_height * na::convert(3.0f64 / 5.0);
+                sq_radius * na::convert(3.0f64 / 20.0) + sq_height * na::convert(3.0f64 / 80.0);
 
             let principal = sq_radius * na::convert(3.0f64 / 10.0);
 
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+use std::mem;
 use std::collections::{hash_map, HashMap};
 
 use na::Real;
@@ -6,10 +8,10 @@ use ncollide::broad_phase::BroadPhasePairFilter;
 use ncollide::narrow_phase::{Interaction, ContactAlgorithm, ProximityAlgorithm};
 use ncollide::query::{Ray, RayIntersection, ContactManifold, Proximity};
 use ncollide::shape::ShapeHandle;
-use ncollide::bounding_volume::AABB;
+use ncollide::bounding_volume::{BoundingVolume, AABB};
 use ncollide::events::{ContactEvents, ProximityEvents};
 
-use crate::object::{Collider, ColliderData, ColliderHandle, ColliderAnchor, BodySet, BodyHandle, BodyPartHandle};
+use crate::object::{Collider, ColliderData, ColliderHandle, ColliderAnchor, BodySet, BodyHandle, BodyPartHandle, BodyStatus, RigidBody};
 use crate::material::{BasicMaterial, MaterialHandle};
 use crate::math::{Isometry, Point};
 
@@ -21,7 +23,9 @@ pub struct ColliderWorld<N: Real> {
     cworld: CollisionWorld<N, ColliderData<N>>,
     collider_lists: HashMap<BodyHandle, (ColliderHandle, ColliderHandle)>, // (head, tail)
     colliders_w_parent: Vec<ColliderHandle>,
-    default_material: MaterialHandle<N>
+    default_material: MaterialHandle<N>,
+    // The margin the broad phase loosens the bounding volumes with.
+    bv_margin: N,
 }
 
 impl<N: Real> ColliderWorld<N> {
@@ -38,7 +42,8 @@ impl<N: Real> ColliderWorld<N> {
             cworld,
             collider_lists: HashMap::new(),
             colliders_w_parent: Vec::new(),
-            default_material: MaterialHandle::new(BasicMaterial::default())
+            default_material: MaterialHandle::new(BasicMaterial::default()),
+            bv_margin: margin,
         }
     }
 
@@ -78,11 +83,50 @@ impl<N: Real> ColliderWorld<N> {
         });
     }
 
+    /// Updates the broad phase pairs of the colliders attached to bodies disabled or re-enabled since the last call.
+    ///
+    /// The colliders of disabled bodies do not interact with any other collider. The bodies that were
+    /// in contact with the colliders of newly disabled bodies are added to `touched_bodies`.
+    pub(crate) fn sync_disabled_colliders(&mut self, bodies: &BodySet<N>, touched_bodies: &mut Vec<BodyHandle>) {
+        for collider_id in &self.colliders_w_parent {
+            let (disabled, groups) = {
+                let collider = try_continue!(self.cworld.collision_object(*collider_id));
+                let body = try_continue!(bodies.body(collider.data().body()));
+                let disabled = body.status() == BodyStatus::Disabled;
+
+                if disabled == collider.data().body_disabled() {
+                    continue;
+                }
+
+                (disabled, *collider.collision_groups())
+            };
+
+            if disabled {
+                if let Some(contacts) = self.contacts_with(*collider_id, true) {
+                    for (c1, c2, _, _) in contacts {
+                        touched_bodies.push(c1.body());
+                        touched_bodies.push(c2.body());
+                    }
+                }
+            }
+
+            let collider = try_continue!(self.cworld.collision_object_mut(*collider_id));
+            collider.data_mut().set_body_disabled(disabled);
+            // Force the broad phase to re-evaluate the pairs involving this collider.
+            self.cworld.set_collision_groups(*collider_id, groups);
+        }
+    }
+
     /// The material given to colliders without user-defined materials.
     pub fn default_material(&self) -> MaterialHandle<N> {
         self.default_material.clone()
     }
 
+    /// Sets the material given to the colliders built afterwards without user-defined materials.
+    pub fn set_default_material(&mut self, material: MaterialHandle<N>) {
+        self.default_material = material
+    }
+
     /// The underlying collision world from the ncollide crate.
     pub fn as_collider_world(&self) -> &CollisionWorld<N, ColliderData<N>> {
         &self.cworld
@@ -198,6 +242,20 @@ impl<N: Real> ColliderWorld<N> {
         let _ = self.collider_lists.remove(&handle);
     }
 
+    /// Remove all the colliders.
+    ///
+    /// The broad phase pair filters and the default material are kept.
+    pub(crate) fn clear(&mut self) {
+        let mut handles: Vec<_> = self.colliders().map(|co| co.handle()).collect();
+        // Removing the last handles first lets the next colliders reuse the handles in increasing order.
+        handles.sort_unstable_by(|a, b| b.cmp(a));
+
+        self.cworld.remove(&handles);
+        self.cworld.clear_events();
+        self.collider_lists.clear();
+        self.colliders_w_parent.clear();
+    }
+
     /// Iterator through all the colliders with the given name.
     pub fn colliders_with_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Collider<N>> {
         self.colliders().filter(move |co| co.name() == name)
@@ -293,6 +351,109 @@ impl<N: Real> ColliderWorld<N> {
         self.cworld.set_collision_groups(handle, groups)
     }
 
+    /// Sets the collision groups of all the colliders attached to the given body.
+    ///
+    /// Two colliders can interact only if the membership of each one is whitelisted and not blacklisted
+    /// by the other. Thus, blacklisting always takes precedence over whitelisting.
+    pub fn set_body_collision_groups(&mut self, body: BodyHandle, groups: CollisionGroups) {
+        let handles: Vec<_> = self.body_colliders(body).map(|co| co.handle()).collect();
+
+        for handle in handles {
+            self.cworld.set_collision_groups(handle, groups)
+        }
+    }
+
+    /// Sets the collision margin of the given collider.
+    ///
+    /// The margin is a skin surrounding the shape of the collider used to generate stable contacts.
+    /// A larger margin reduces jitter but visibly inflates the collider. The mass properties of the
+    /// body the collider is attached to are not modified: they are always computed from the shape itself.
+    ///
+    /// The collider keeps its handle and its contacts, which are updated with the new margin at the next step.
+    pub fn set_margin(&mut self, handle: ColliderHandle, margin: N) {
+        let position = {
+            let collider = try_ret!(self.cworld.collision_object_mut(handle));
+            let old_margin = collider.data().margin();
+
+            if margin == old_margin {
+                return;
+            }
+
+            let query_type = match collider.query_type() {
+                GeometricQueryType::Contacts(linear, angular) => {
+                    GeometricQueryType::Contacts(linear - old_margin + margin, angular)
+                }
+                query_type => query_type,
+            };
+
+            let mut data = mem::replace(collider.data_mut(), Self::placeholder_data(&self.default_material));
+            data.set_margin(margin);
+
+            // The query type of a collision object cannot be modified so it is replaced by an identical
+            // object with the new query type. Its handle, broad phase proxy, and interaction graph node
+            // are kept so its contacts remain untouched.
+            let position = *collider.position();
+            *collider = CollisionObject::new(
+                collider.handle(),
+                collider.proxy_handle(),
+                collider.graph_index(),
+                position,
+                collider.shape().clone(),
+                *collider.collision_groups(),
+                query_type,
+                data,
+            );
+
+            position
+        };
+
+        // Flags the collider as modified and loosens its bounding volume with the new margin.
+        self.cworld.set_position(handle, position)
+    }
+
+    // Data temporarily stored in a collider while its actual data is moved out of it.
+    fn placeholder_data(material: &MaterialHandle<N>) -> ColliderData<N> {
+        ColliderData::new(
+            String::new(),
+            N::zero(),
+            ColliderAnchor::OnDeformableBody { body: BodyHandle::ground(), body_parts: None },
+            0,
+            material.clone(),
+        )
+    }
+
+    /// Applies the collision margins set on rigid bodies to the colliders attached to them.
+    pub(crate) fn sync_margins(&mut self, bodies: &BodySet<N>) {
+        for i in 0..self.colliders_w_parent.len() {
+            let collider_id = self.colliders_w_parent[i];
+            let margin = {
+                let collider = try_continue!(self.cworld.collision_object(collider_id));
+                let body = try_continue!(bodies.body(collider.data().body()));
+                let rb = try_continue!(body.downcast_ref::<RigidBody<N>>());
+                let margin = try_continue!(rb.margin());
+
+                if margin == collider.data().margin() {
+                    continue;
+                }
+
+                margin
+            };
+
+            self.set_margin(collider_id, margin)
+        }
+    }
+
+    /// Sets the collision margin of all the colliders attached to the given body.
+    ///
+    /// See `ColliderWorld::set_margin` for details.
+    pub fn set_body_margin(&mut self, body: BodyHandle, margin: N) {
+        let handles: Vec<_> = self.body_colliders(body).map(|co| co.handle()).collect();
+
+        for handle in handles {
+            self.set_margin(handle, margin)
+        }
+    }
+
     /// Computes the interferences between every rigid bodies on this world and a ray.
     #[inline]
     pub fn interferences_with_ray<'a>(
@@ -304,6 +465,36 @@ impl<N: Real> ColliderWorld<N> {
         self.cworld.interferences_with_ray(ray, groups).map(|res| (Collider::from_ref(res.0), res.1))
     }
 
+    /// Computes the interferences between every rigid bodies on this world and a ray, sorted by increasing time of impact.
+    pub fn sorted_interferences_with_ray<'a>(
+        &'a self,
+        ray: &'a Ray<N>,
+        groups: &'a CollisionGroups,
+    ) -> Vec<(&'a Collider<N>, RayIntersection<N>)>
+    {
+        let mut res: Vec<_> = self.interferences_with_ray(ray, groups).collect();
+        res.sort_by(|a, b| a.1.toi.partial_cmp(&b.1.toi).unwrap_or(Ordering::Equal));
+        res
+    }
+
+    /// Computes the first collider hit by a ray, if any.
+    pub fn first_interference_with_ray<'a>(
+        &'a self,
+        ray: &'a Ray<N>,
+        groups: &'a CollisionGroups,
+    ) -> Option<(&'a Collider<N>, RayIntersection<N>)>
+    {
+        let mut res: Option<(&'a Collider<N>, RayIntersection<N>)> = None;
+
+        for (collider, inter) in self.interferences_with_ray(ray, groups) {
+            if res.as_ref().map(|best| inter.toi < best.1.toi).unwrap_or(true) {
+                res = Some((collider, inter))
+            }
+        }
+
+        res
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a point.
     #[inline]
     pub fn interferences_with_point<'a>(
@@ -315,6 +506,56 @@ impl<N: Real> ColliderWorld<N> {
         self.cworld.interferences_with_point(point, groups).map(|co| Collider::from_ref(co))
     }
 
+    /// Computes all the colliders containing the given point, sorted by increasing collider handle.
+    ///
+    /// The ordering is deterministic: colliders added later to the world usually come last.
+    pub fn colliders_containing_point<'a>(
+        &'a self,
+        point: &'a Point<N>,
+        groups: &'a CollisionGroups,
+    ) -> Vec<&'a Collider<N>>
+    {
+        let mut res: Vec<_> = self.interferences_with_point(point, groups).collect();
+        res.sort_by_key(|co| co.handle());
+        res
+    }
+
+    /// Computes the collider a user would intuitively pick first among the ones containing the given point.
+    ///
+    /// Sensors are ignored. The collider with the smallest local bounding box is selected so that
+    /// a small object resting on top of a larger one is picked before it. Ties are broken by picking
+    /// the collider with the largest handle, i.e., usually the one added last to the world.
+    pub fn topmost_collider_containing_point<'a>(
+        &'a self,
+        point: &'a Point<N>,
+        groups: &'a CollisionGroups,
+    ) -> Option<&'a Collider<N>>
+    {
+        let mut res: Option<(&'a Collider<N>, N)> = None;
+
+        for collider in self.interferences_with_point(point, groups) {
+            if collider.query_type().is_proximity_query() {
+                continue;
+            }
+
+            let aabb = collider.shape().aabb(&Isometry::identity());
+            let size = aabb.half_extents().iter().fold(N::one(), |size, e| size * *e);
+
+            let is_better = match res {
+                None => true,
+                Some((best, best_size)) => {
+                    size < best_size || (size == best_size && collider.handle() > best.handle())
+                }
+            };
+
+            if is_better {
+                res = Some((collider, size))
+            }
+        }
+
+        res.map(|(collider, _)| collider)
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a aabb.
     #[inline]
     pub fn interferences_with_aabb<'a>(
@@ -326,6 +567,17 @@ impl<N: Real> ColliderWorld<N> {
         self.cworld.interferences_with_aabb(aabb, groups).map(|co| Collider::from_ref(co))
     }
 
+    /// The bounding box of the given collider as seen by the broad phase.
+    ///
+    /// It is larger than the collider itself because it accounts for its query limit, i.e., its margin
+    /// and the prediction distances used by the collision detection, and for the margin of the broad phase.
+    pub fn broad_phase_aabb(&self, handle: ColliderHandle) -> Option<AABB<N>> {
+        let co = self.cworld.collision_object(handle)?;
+        let mut aabb = co.shape().aabb(co.position());
+        aabb.loosen(co.query_type().query_limit() + self.bv_margin);
+        Some(aabb)
+    }
+
     /// The contact events pool.
     pub fn contact_events(&self) -> &ContactEvents {
         self.cworld.contact_events()
@@ -550,7 +802,13 @@ struct BodyStatusCollisionFilter;
 impl<N: Real> BroadPhasePairFilter<N, ColliderData<N>> for BodyStatusCollisionFilter {
     /// Activate an action for when two objects start or stop to be close to each other.
     fn is_pair_valid(&self, b1: &CollisionObject<N, ColliderData<N>>, b2: &CollisionObject<N, ColliderData<N>>) -> bool {
+        if b1.data().body_disabled() || b2.data().body_disabled() {
+            return false;
+        }
+
+        // Sensors must report proximities even if they are attached to bodies without any degree of freedom.
         b1.data().body_status_dependent_ndofs() != 0 || b2.data().body_status_dependent_ndofs() != 0
+            || b1.query_type().is_proximity_query() || b2.query_type().is_proximity_query()
     }
 }
 
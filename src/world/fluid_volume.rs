@@ -0,0 +1,133 @@
+use na::Real;
+use ncollide::bounding_volume::AABB;
+use ncollide::world::CollisionGroups;
+
+use crate::math::{ForceType, Point, Vector};
+use crate::object::{BodySet, ColliderAnchor};
+use crate::utils::GeneralizedCross;
+use crate::world::ColliderWorld;
+
+/// The handle of a fluid volume.
+pub type FluidVolumeHandle = usize;
+
+/// An axis-aligned region filled with a fluid applying buoyancy and drag forces to the bodies inside of it.
+///
+/// The volume of fluid displaced by each collider is approximated by the intersection of its
+/// bounding box with the region. The buoyancy force is `-gravity * density * displaced_volume` and
+/// is applied at the center of this intersection, so partially submerged bodies bob at the surface
+/// and tend to stay upright. The drag force is `-linear_drag * submerged_fraction * velocity`, where
+/// `velocity` is the velocity of the body at the same point.
+#[derive(Clone, Debug)]
+pub struct FluidVolume<N: Real> {
+    aabb: AABB<N>,
+    density: N,
+    linear_drag: N,
+    angular_drag: N,
+}
+
+impl<N: Real> FluidVolume<N> {
+    /// Creates a region of fluid with the given density, without any drag.
+    pub fn new(aabb: AABB<N>, density: N) -> Self {
+        FluidVolume {
+            aabb,
+            density,
+            linear_drag: N::zero(),
+            angular_drag: N::zero(),
+        }
+    }
+
+    /// The region filled with this fluid.
+    pub fn aabb(&self) -> &AABB<N> {
+        &self.aabb
+    }
+
+    /// Sets the region filled with this fluid.
+    pub fn set_aabb(&mut self, aabb: AABB<N>) {
+        self.aabb = aabb
+    }
+
+    /// The density of this fluid.
+    pub fn density(&self) -> N {
+        self.density
+    }
+
+    /// Sets the density of this fluid.
+    pub fn set_density(&mut self, density: N) {
+        self.density = density
+    }
+
+    /// The coefficient of the drag force opposed to the velocity of the submerged bodies.
+    pub fn linear_drag(&self) -> N {
+        self.linear_drag
+    }
+
+    /// Sets the coefficient of the drag force opposed to the velocity of the submerged bodies.
+    pub fn set_linear_drag(&mut self, drag: N) {
+        self.linear_drag = drag
+    }
+
+    /// The coefficient of the drag torque opposed to the angular velocity of the submerged bodies.
+    pub fn angular_drag(&self) -> N {
+        self.angular_drag
+    }
+
+    /// Sets the coefficient of the drag torque opposed to the angular velocity of the submerged bodies.
+    pub fn set_angular_drag(&mut self, drag: N) {
+        self.angular_drag = drag
+    }
+
+    /// Applies the buoyancy and drag forces to the dynamic bodies overlapping this region.
+    pub(crate) fn apply(&self, gravity: &Vector<N>, bodies: &mut BodySet<N>, cworld: &ColliderWorld<N>) {
+        let groups = CollisionGroups::new();
+
+        for co in cworld.interferences_with_aabb(&self.aabb, &groups) {
+            let part_handle = match co.anchor() {
+                ColliderAnchor::OnBodyPart { body_part, .. } => *body_part,
+                _ => continue,
+            };
+
+            if co.is_sensor() {
+                continue;
+            }
+
+            let body = try_continue!(bodies.body_mut(part_handle.0));
+
+            if !body.is_dynamic() {
+                continue;
+            }
+
+            let aabb = co.shape().aabb(co.position());
+            let mins = Point::from(na::sup(&aabb.mins().coords, &self.aabb.mins().coords));
+            let maxs = Point::from(na::inf(&aabb.maxs().coords, &self.aabb.maxs().coords));
+            let submerged_volume = volume(&mins, &maxs);
+
+            if submerged_volume.is_zero() {
+                continue;
+            }
+
+            let fraction = submerged_volume / volume(aabb.mins(), aabb.maxs());
+            let center = na::center(&mins, &maxs);
+
+            let (point_velocity, angular_velocity) = {
+                let part = try_continue!(body.part(part_handle.1));
+                let velocity = part.velocity();
+                let r = center - part.center_of_mass();
+                let angular = velocity.angular_vector();
+                (velocity.linear + angular.gcross(&r), angular)
+            };
+
+            let force = -gravity * (self.density * submerged_volume) - point_velocity * (self.linear_drag * fraction);
+            body.apply_force_at_point(part_handle.1, &force, &center, ForceType::Force, false);
+
+            if !self.angular_drag.is_zero() {
+                let torque = -angular_velocity * (self.angular_drag * fraction);
+                body.apply_torque(part_handle.1, &torque, ForceType::Force, false);
+            }
+        }
+    }
+}
+
+// The volume, or area in 2D, of the box with the given corners. Zero if the box is empty.
+fn volume<N: Real>(mins: &Point<N>, maxs: &Point<N>) -> N {
+    (maxs - mins).iter().fold(N::one(), |vol, e| vol * e.max(N::zero()))
+}
@@ -0,0 +1,94 @@
+use na::{self, Real, Unit};
+use ncollide::shape::ShapeHandle;
+use ncollide::world::CollisionGroups;
+
+use crate::math::{Isometry, Translation, Vector};
+use crate::object::{BodyHandle, Collider};
+use crate::world::ShapeCastHit;
+
+/// Parameters of the character-like moves performed by `World::move_kinematic`.
+#[derive(Clone, Debug)]
+pub struct KinematicMoveParameters<N: Real> {
+    /// The direction the moved bodies consider as up (default: the `y` axis).
+    pub up: Unit<Vector<N>>,
+    /// The maximum angle, in radians, between `up` and the normal of a surface the bodies can walk on (default: `π / 4`).
+    ///
+    /// The moves are never allowed to climb steeper surfaces.
+    pub max_slope_angle: N,
+    /// The height of the tallest obstacles the bodies automatically step over (default: `0.3`).
+    ///
+    /// Setting it to zero disables stepping up.
+    pub max_step_height: N,
+    /// The distance kept between the moved bodies and the obstacles they hit (default: `0.01`).
+    pub offset: N,
+    /// The maximum number of times a move is redirected along the surfaces it hits (default: `4`).
+    pub max_slides: usize,
+    /// The mass used to compute the impulse applied to the dynamic bodies hit by a move (default: `1.0`).
+    ///
+    /// The impulse is this mass multiplied by the part of the move velocity blocked by the dynamic body.
+    pub push_mass: N,
+}
+
+impl<N: Real> Default for KinematicMoveParameters<N> {
+    fn default() -> Self {
+        KinematicMoveParameters {
+            up: Vector::y_axis(),
+            max_slope_angle: N::frac_pi_4(),
+            max_step_height: na::convert(0.3),
+            offset: na::convert(0.01),
+            max_slides: 4,
+            push_mass: N::one(),
+        }
+    }
+}
+
+/// The outcome of a move performed by `World::move_kinematic`.
+#[derive(Clone, Debug)]
+pub struct ActualMove<N: Real> {
+    /// The translation actually applied to the moved body.
+    pub translation: Vector<N>,
+    /// The obstacles hit during the move, in the order they were hit.
+    pub hits: Vec<ShapeCastHit<N>>,
+    /// Whether the move was stopped by a surface flat enough to walk on.
+    pub grounded: bool,
+    /// Whether the body stepped over an obstacle during the move.
+    pub stepped_up: bool,
+}
+
+impl<N: Real> ActualMove<N> {
+    pub(crate) fn new() -> Self {
+        ActualMove {
+            translation: Vector::zeros(),
+            hits: Vec::new(),
+            grounded: false,
+            stepped_up: false,
+        }
+    }
+}
+
+// The collider of a body moved by `World::move_kinematic`, swept from the initial position of the body.
+pub(crate) struct KinematicSweep<N: Real> {
+    pub body: BodyHandle,
+    pub shape: ShapeHandle<N>,
+    pub groups: CollisionGroups,
+    start: Isometry<N>,
+    position_wrt_body: Isometry<N>,
+}
+
+impl<N: Real> KinematicSweep<N> {
+    pub fn new(body: BodyHandle, collider: &Collider<N>, start: Isometry<N>) -> Self {
+        KinematicSweep {
+            body,
+            shape: collider.shape().clone(),
+            groups: *collider.collision_groups(),
+            start,
+            position_wrt_body: collider.position_wrt_body(),
+        }
+    }
+
+    // The position of the collider once the body is translated by `translation` from its initial position.
+    pub fn position(&self, translation: &Vector<N>) -> Isometry<N> {
+        let translation = Translation::from(self.start.translation.vector + translation);
+        Isometry::from_parts(translation, self.start.rotation) * self.position_wrt_body
+    }
+}
@@ -1,7 +1,22 @@
 //! The physics world.
 
 pub use self::world::World;
+pub use self::world_desc::{WorldDesc, WorldDescError};
 pub use self::collider_world::ColliderWorld;
+pub use self::world_snapshot::WorldSnapshot;
+pub use self::shape_cast_hit::ShapeCastHit;
+pub use self::radial_falloff::RadialFalloff;
+pub use self::fluid_volume::{FluidVolume, FluidVolumeHandle};
+pub use self::kinematic_move::{ActualMove, KinematicMoveParameters};
 
 mod world;
+mod world_desc;
 mod collider_world;
+mod world_snapshot;
+mod shape_cast_hit;
+mod radial_falloff;
+mod fluid_volume;
+mod kinematic_move;
+
+#[cfg(test)]
+mod tests;
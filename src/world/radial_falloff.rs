@@ -0,0 +1,30 @@
+use na::Real;
+
+/// The way the strength of a radial impulse decreases with the distance to its center.
+///
+/// See `World::apply_radial_impulse`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RadialFalloff {
+    /// The strength decreases linearly, from its full value at the center to zero at the radius.
+    Linear,
+    /// The strength decreases quadratically, from its full value at the center to zero at the radius.
+    Quadratic,
+}
+
+impl RadialFalloff {
+    /// The factor the strength is multiplied by at the given distance from the center.
+    ///
+    /// Returns zero if `distance` is larger than `radius`.
+    pub fn factor<N: Real>(&self, distance: N, radius: N) -> N {
+        if distance >= radius {
+            return N::zero();
+        }
+
+        let remaining = N::one() - distance / radius;
+
+        match *self {
+            RadialFalloff::Linear => remaining,
+            RadialFalloff::Quadratic => remaining * remaining,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use na::{Real, Unit};
+
+use crate::math::{Point, Vector};
+use crate::object::{BodyHandle, ColliderHandle};
+
+/// The first collider hit by a shape moving with a constant velocity.
+///
+/// This is the result of `World::cast_shape`.
+#[derive(Copy, Clone, Debug)]
+pub struct ShapeCastHit<N: Real> {
+    /// The time of impact, i.e., the moved shape first touches the collider after moving by `velocity * toi`.
+    pub toi: N,
+    /// The handle of the body the hit collider is attached to.
+    pub body: BodyHandle,
+    /// The handle of the hit collider.
+    pub collider: ColliderHandle,
+    /// The contact point, in world-space, on the hit collider at the time of impact.
+    pub point: Point<N>,
+    /// The normal of the hit collider at the contact point, pointing toward the moved shape.
+    pub normal: Unit<Vector<N>>,
+}
@@ -0,0 +1,1231 @@
+use ncollide::shape::{Ball, Cuboid, ShapeHandle};
+use crate::math::Vector;
+use crate::object::{Body, BodyHandle, BodyStatus, ColliderDesc, ColliderHandle, RigidBodyDesc};
+use crate::world::World;
+
+// A world with a downward gravity.
+fn world_with_gravity() -> World<f32> {
+    let mut world = World::new();
+    world.set_gravity(Vector::y() * -9.81);
+    world
+}
+
+// A world with a downward gravity and a ground whose top lies at y = 0.
+fn world_with_ground() -> World<f32> {
+    let mut world = world_with_gravity();
+    let _ = add_ground(&mut world, 10.0);
+    world
+}
+
+// Adds a static cuboid with the given half-extents whose top lies at y = 0.
+fn add_ground(world: &mut World<f32>, half_extent: f32) -> ColliderHandle {
+    ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(half_extent))))
+        .translation(Vector::y() * -half_extent)
+        .build(world)
+        .handle()
+}
+
+fn box_collider(half_extent: f32) -> ColliderDesc<f32> {
+    ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(half_extent)))).density(1.0)
+}
+
+fn ball_collider(radius: f32) -> ColliderDesc<f32> {
+    ColliderDesc::new(ShapeHandle::new(Ball::new(radius))).density(1.0)
+}
+
+fn add_body(world: &mut World<f32>, collider: &ColliderDesc<f32>, translation: Vector<f32>) -> BodyHandle {
+    RigidBodyDesc::new()
+        .collider(collider)
+        .translation(translation)
+        .build(world)
+        .handle()
+}
+
+// Adds a dynamic box of unit density.
+fn add_box(world: &mut World<f32>, half_extent: f32, translation: Vector<f32>) -> BodyHandle {
+    add_body(world, &box_collider(half_extent), translation)
+}
+
+// Adds a dynamic ball of unit density.
+fn add_ball(world: &mut World<f32>, radius: f32, translation: Vector<f32>) -> BodyHandle {
+    add_body(world, &ball_collider(radius), translation)
+}
+
+// The height of the center of a box resting on the ground, taking its margin into account.
+fn resting_height(half_extent: f32) -> f32 {
+    half_extent + box_collider(half_extent).get_margin()
+}
+
+fn run(world: &mut World<f32>, nsteps: usize) {
+    for _ in 0..nsteps {
+        world.step();
+    }
+}
+
+#[test]
+fn box_stack_settles() {
+    let mut world = world_with_ground();
+    let shift = resting_height(0.5) * 2.0;
+    let top = (0..5)
+        .map(|i| add_box(&mut world, 0.5, Vector::y() * (shift / 2.0 + i as f32 * shift)))
+        .last()
+        .unwrap();
+
+    run(&mut world, 250);
+
+    let settled = world.rigid_body(top).unwrap().position().translation.vector;
+    assert!((settled.y - shift * 4.5).abs() < 0.05, "The stack collapsed: {:?}", settled);
+
+    for _ in 0..250 {
+        world.step();
+        let pos = world.rigid_body(top).unwrap().position().translation.vector;
+        assert!((pos - settled).norm() < 1.0e-2, "The top box drifted: {:?} vs {:?}", pos, settled);
+    }
+}
+
+#[test]
+fn heavy_box_does_not_launch_light_box() {
+    // Returns how high any of the boxes rose above its resting position.
+    fn launch_height(split_impulse: bool) -> f32 {
+        let mut world = world_with_ground();
+        world.enable_split_impulse(split_impulse);
+        world.set_position_bias(0.2);
+
+        let light_y = resting_height(0.5);
+        let heavy_rest_y = light_y + resting_height(0.5) * 2.0;
+        let light = add_box(&mut world, 0.5, Vector::y() * light_y);
+        let heavy = add_body(&mut world, &box_collider(0.5).density(100.0), Vector::y() * 3.0);
+
+        let mut landed = false;
+        let mut height = 0.0f32;
+
+        for _ in 0..300 {
+            world.step();
+
+            let light = world.rigid_body(light).unwrap();
+            let heavy = world.rigid_body(heavy).unwrap();
+            height = height.max(light.position().translation.vector.y - light_y);
+
+            // The heavy box is above its resting position until it lands.
+            landed = landed || heavy.velocity().linear.y >= 0.0;
+            if landed {
+                height = height.max(heavy.position().translation.vector.y - heavy_rest_y);
+            }
+        }
+
+        height
+    }
+
+    let split = launch_height(true);
+    let baumgarte = launch_height(false);
+    assert!(split < 0.05, "A box launched with split impulses: {}", split);
+    assert!(split < baumgarte, "Split impulses: {}, Baumgarte: {}", split, baumgarte);
+}
+
+#[test]
+fn symplectic_euler_keeps_the_energy_of_a_spring_bounded() {
+    use crate::force_generator::Spring;
+    use crate::math::Point;
+    use crate::object::BodyPartHandle;
+    use crate::solver::SymplecticEuler;
+
+    // Returns the largest relative deviation of the energy of a mass on a spring from its initial value.
+    fn energy_drift() -> f32 {
+        let stiffness = 10.0;
+        let mut world = World::new();
+        world.set_integrator(SymplecticEuler);
+
+        let body = RigidBodyDesc::new()
+            .collider(&box_collider(0.5))
+            .translation(Vector::x() * 1.5)
+            .sleep_threshold(None)
+            .build(&mut world)
+            .handle();
+        let mass = world.rigid_body(body).unwrap().augmented_mass().linear;
+        let spring = Spring::new_tethered(BodyPartHandle(body, 0), Point::origin(), Point::origin(), 1.0, stiffness);
+        let _ = world.add_force_generator(spring);
+
+        let energy = |world: &World<f32>| {
+            let rb = world.rigid_body(body).unwrap();
+            let elongation = rb.position().translation.vector.norm() - 1.0;
+            rb.velocity().linear.norm_squared() * mass * 0.5 + elongation * elongation * stiffness * 0.5
+        };
+
+        let initial_energy = energy(&world);
+        let mut drift = 0.0f32;
+
+        for _ in 0..600 {
+            world.step();
+            drift = drift.max((energy(&world) - initial_energy).abs() / initial_energy);
+        }
+
+        drift
+    }
+
+    let euler_drift = energy_drift();
+    assert!(euler_drift < 0.1, "Symplectic Euler drift: {}", euler_drift);
+}
+
+#[test]
+fn custom_integrators_replace_the_default_one() {
+    use na::DVectorSlice;
+    use crate::solver::{IntegrationParameters, Integrator};
+
+    // Ignores the external forces.
+    struct ConstraintsOnly;
+
+    impl Integrator<f32> for ConstraintsOnly {
+        fn integrate(
+            &self,
+            params: &IntegrationParameters<f32>,
+            body: &mut Body<f32>,
+            _: DVectorSlice<f32>,
+            constraint_vels: DVectorSlice<f32>,
+        ) {
+            body.generalized_velocity_mut().axpy(1.0, &constraint_vels, 1.0);
+            body.integrate(params);
+        }
+    }
+
+    let mut world = world_with_gravity();
+    world.set_integrator(ConstraintsOnly);
+    let body = add_box(&mut world, 0.5, Vector::zeros());
+
+    run(&mut world, 10);
+    assert_eq!(world.rigid_body(body).unwrap().position().translation.vector, Vector::zeros());
+}
+
+#[test]
+fn separate_boxes_form_separate_islands() {
+    let mut world = world_with_ground();
+    let y = resting_height(0.5);
+    let left = add_box(&mut world, 0.5, Vector::x() * -3.0 + Vector::y() * y);
+    let right = add_box(&mut world, 0.5, Vector::x() * 3.0 + Vector::y() * y);
+
+    run(&mut world, 10);
+
+    assert_eq!(world.islands().len(), 2);
+    assert!(world.islands().iter().all(|island| island.bodies().len() == 1));
+    assert_ne!(world.islands()[0].bodies()[0], world.islands()[1].bodies()[0]);
+    assert!(world.islands().iter().any(|island| island.bodies()[0] == left));
+    assert!(world.islands().iter().any(|island| island.bodies()[0] == right));
+}
+
+#[test]
+#[should_panic(expected = "non-finite velocity")]
+fn nan_velocity_violates_invariants() {
+    let mut world = World::<f32>::new();
+    world.set_check_invariants(true);
+    let handle = add_box(&mut world, 0.5, Vector::zeros());
+
+    world.step();
+    world.rigid_body_mut(handle).unwrap().set_linear_velocity(Vector::repeat(std::f32::NAN));
+    world.step();
+}
+
+#[test]
+fn valid_world_satisfies_invariants() {
+    let mut world = World::<f32>::new();
+    world.set_check_invariants(true);
+    let handle = add_box(&mut world, 0.5, Vector::zeros());
+    world.rigid_body_mut(handle).unwrap().set_linear_velocity(Vector::x());
+
+    run(&mut world, 10);
+}
+
+#[test]
+fn overlapping_static_bodies_do_not_collide() {
+    let mut world = World::<f32>::new();
+    let collider_desc = ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(1.0))));
+    let mut rb_desc = RigidBodyDesc::new()
+        .status(BodyStatus::Static)
+        .collider(&collider_desc);
+
+    let b1 = rb_desc.build(&mut world).handle();
+    let b2 = rb_desc.set_translation(Vector::x() * 0.5).build(&mut world).handle();
+
+    run(&mut world, 5);
+
+    assert!(world.rigid_body(b1).unwrap().is_static());
+    assert!(world.rigid_body(b2).unwrap().is_static());
+    assert_eq!(world.collider_world().contact_pairs(false).count(), 0);
+    assert_eq!(world.rigid_body(b2).unwrap().position().translation.vector, Vector::x() * 0.5);
+}
+
+#[cfg(feature = "dim3")]
+#[test]
+fn box_rests_on_trimesh_ramp() {
+    use na::{Point3, Vector3};
+    use ncollide::shape::TriMesh;
+
+    let mut world = world_with_gravity();
+
+    let slope = 0.15f32;
+    let vertices = vec![
+        Point3::new(-10.0, -10.0 * slope, -10.0),
+        Point3::new(10.0, 10.0 * slope, -10.0),
+        Point3::new(10.0, 10.0 * slope, 10.0),
+        Point3::new(-10.0, -10.0 * slope, 10.0),
+    ];
+    let indices = vec![Point3::new(0, 2, 1), Point3::new(0, 3, 2)];
+    let _ = ColliderDesc::new(ShapeHandle::new(TriMesh::new(vertices, indices, None)))
+        .build(&mut world);
+
+    let rad = 0.5;
+    let handle = add_box(&mut world, rad, Vector3::y() * 2.0);
+
+    run(&mut world, 300);
+
+    let rb = world.rigid_body(handle).unwrap();
+    let normal = Vector3::new(-slope, 1.0, 0.0).normalize();
+    let height = rb.position().translation.vector.dot(&normal);
+
+    assert!(rb.velocity().linear.norm() < 1.0e-2, "The box did not come to rest: {:?}", rb.velocity());
+    assert!((height - rad).abs() < 0.05, "The box is not resting on the ramp: {}", height);
+}
+
+#[test]
+fn activation_handler_is_notified_once_per_transition() {
+    use std::sync::{Arc, Mutex};
+    use crate::detection::ActivationHandler;
+
+    struct Recorder(Arc<Mutex<Vec<(BodyHandle, bool)>>>);
+
+    impl ActivationHandler<f32> for Recorder {
+        fn handle_sleep(&mut self, world: &World<f32>, body: BodyHandle) {
+            assert!(!world.rigid_body(body).unwrap().is_active());
+            self.0.lock().unwrap().push((body, false));
+        }
+
+        fn handle_wake(&mut self, world: &World<f32>, body: BodyHandle) {
+            assert!(world.rigid_body(body).unwrap().is_active());
+            self.0.lock().unwrap().push((body, true));
+        }
+    }
+
+    let mut world = world_with_ground();
+    let handle = add_box(&mut world, 0.5, Vector::y() * resting_height(0.5));
+
+    let transitions = Arc::new(Mutex::new(Vec::new()));
+    world.set_activation_handler(Box::new(Recorder(transitions.clone())));
+
+    run(&mut world, 500);
+
+    assert_eq!(*transitions.lock().unwrap(), vec![(handle, false)]);
+
+    world.rigid_body_mut(handle).unwrap().activate();
+    world.step();
+
+    assert_eq!(*transitions.lock().unwrap(), vec![(handle, false), (handle, true)]);
+}
+
+#[test]
+fn distance_between_balls() {
+    let mut world = World::<f32>::new();
+    let b1 = add_ball(&mut world, 0.5, Vector::zeros());
+    let b2 = add_ball(&mut world, 0.5, Vector::x() * 3.0);
+    let b3 = add_ball(&mut world, 0.5, Vector::x() * 3.8);
+
+    let (dist, p1, p2) = world.distance_between(b1, b2).unwrap();
+    assert!((dist - 2.0).abs() < 1.0e-5);
+    assert!((p1.coords - Vector::x() * 0.5).norm() < 1.0e-5);
+    assert!((p2.coords - Vector::x() * 2.5).norm() < 1.0e-5);
+
+    let (dist, _, _) = world.distance_between(b2, b3).unwrap();
+    assert!((dist + 0.2).abs() < 1.0e-5);
+}
+
+#[test]
+fn cast_shape_hits_ground() {
+    use na;
+    use ncollide::world::CollisionGroups;
+    use crate::math::Isometry;
+
+    let mut world = World::<f32>::new();
+    let _ = add_ground(&mut world, 10.0);
+    let body = add_ball(&mut world, 0.5, Vector::y() * 5.0);
+    world.step();
+
+    let ball = Ball::new(0.5f32);
+    let start = Isometry::new(Vector::y() * 5.0, na::zero());
+    let velocity = -Vector::y();
+    let groups = CollisionGroups::new();
+
+    // The body itself is hit first unless it is excluded.
+    let hit = world.cast_shape(&ball, &start, &velocity, 10.0, &groups, None).unwrap();
+    assert_eq!(hit.body, body);
+    assert_eq!(hit.toi, 0.0);
+
+    let hit = world.cast_shape(&ball, &start, &velocity, 10.0, &groups, Some(body)).unwrap();
+    assert!(hit.body.is_ground());
+    assert!((hit.toi - 4.5).abs() < 1.0e-3);
+    // The contact point is computed iteratively so it is not exact in 2D.
+    assert!(hit.point.coords.norm() < 1.0e-2);
+    assert!((hit.normal.into_inner() - Vector::y()).norm() < 1.0e-3);
+
+    assert!(world.cast_shape(&ball, &start, &velocity, 4.0, &groups, Some(body)).is_none());
+}
+
+#[test]
+fn changing_gravity_wakes_up_sleeping_bodies() {
+    let mut world = world_with_ground();
+    let gravity = *world.gravity();
+    let handle = add_box(&mut world, 0.5, Vector::y() * resting_height(0.5));
+
+    run(&mut world, 500);
+
+    assert!(!world.rigid_body(handle).unwrap().is_active());
+
+    world.set_gravity(gravity);
+    assert!(!world.rigid_body(handle).unwrap().is_active());
+
+    world.set_gravity(-gravity);
+    assert!(world.rigid_body(handle).unwrap().is_active());
+
+    run(&mut world, 10);
+
+    assert!(world.rigid_body(handle).unwrap().position().translation.vector.y > 0.6);
+}
+
+#[test]
+fn overloaded_joint_breaks() {
+    use std::sync::{Arc, Mutex};
+    use crate::joint::{ConstraintHandle, FixedConstraint, JointBreakHandler, JointConstraint};
+    use crate::math::{Point, Rotation};
+    use crate::object::BodyPartHandle;
+
+    struct Recorder(Arc<Mutex<Vec<ConstraintHandle>>>);
+
+    impl JointBreakHandler<f32> for Recorder {
+        fn handle_joint_broken(&mut self, handle: ConstraintHandle, _: Box<JointConstraint<f32>>, force: f32, _: f32) {
+            assert!(force > 1.0);
+            self.0.lock().unwrap().push(handle);
+        }
+    }
+
+    let mut world = world_with_gravity();
+    let body = add_ball(&mut world, 0.5, Vector::zeros());
+    let joint = world.add_constraint(FixedConstraint::new(
+        BodyPartHandle::ground(),
+        BodyPartHandle(body, 0),
+        Point::origin(),
+        Rotation::identity(),
+        Point::origin(),
+        Rotation::identity(),
+    ));
+
+    let broken = Arc::new(Mutex::new(Vec::new()));
+    world.set_joint_break_handler(Box::new(Recorder(broken.clone())));
+    world.set_joint_breaking_force(joint, Some(100.0));
+
+    run(&mut world, 10);
+
+    assert!(broken.lock().unwrap().is_empty());
+    assert_eq!(world.joint_breaking_force(joint), Some(100.0));
+
+    // The weight of the ball is larger than this threshold.
+    world.set_joint_breaking_force(joint, Some(1.0));
+    world.step();
+
+    assert_eq!(world.joint_breaking_force(joint), None);
+    assert_eq!(*broken.lock().unwrap(), vec![joint]);
+
+    run(&mut world, 20);
+
+    assert!(world.rigid_body(body).unwrap().position().translation.vector.y < -0.1);
+}
+
+#[test]
+fn disabled_warm_starting_resets_contact_impulses() {
+    fn total_normal_impulse(world: &World<f32>) -> f32 {
+        world
+            .collider_world()
+            .contact_pairs(true)
+            .flat_map(|(_, _, _, manifold)| manifold.contacts())
+            .map(|c| world.contact_normal_impulse(c.id))
+            .sum()
+    }
+
+    let mut world = world_with_ground();
+    let _ = RigidBodyDesc::new()
+        .collider(&box_collider(0.5))
+        .translation(Vector::y() * resting_height(0.5))
+        .sleep_threshold(None)
+        .build(&mut world);
+
+    run(&mut world, 100);
+
+    assert!(world.warm_starting());
+    let settled = total_normal_impulse(&world);
+    assert!(settled > 0.0);
+
+    // Without any solver iteration, only the warm-starting impulses are applied.
+    world.integration_parameters_mut().max_velocity_iterations = 0;
+    world.step();
+    assert!((total_normal_impulse(&world) - settled).abs() < settled * 1.0e-3);
+
+    world.set_warm_starting(false);
+    world.step();
+    assert_eq!(total_normal_impulse(&world), 0.0);
+}
+
+#[test]
+fn setting_velocity_wakes_up_rigid_body() {
+    use crate::math::Point;
+
+    let mut world = World::<f32>::new();
+    let handle = add_ball(&mut world, 0.5, Vector::zeros());
+
+    let rb = world.rigid_body_mut(handle).unwrap();
+    rb.deactivate();
+    assert!(!rb.is_active());
+
+    #[cfg(feature = "dim2")]
+    rb.set_angular_velocity(2.0);
+    #[cfg(feature = "dim3")]
+    rb.set_angular_velocity(Vector::z() * 2.0);
+    assert!(rb.is_active());
+
+    rb.set_linear_velocity(Vector::x());
+    assert_eq!(*rb.linear_velocity(), Vector::x());
+
+    // The rotation around the z axis moves the point above the center of mass along -x.
+    let v = rb.velocity_at_point(&(Point::origin() + Vector::y()));
+    assert!((v + Vector::x()).norm() < 1.0e-6);
+}
+
+#[test]
+fn falling_body_speed_is_clamped() {
+    let mut world = world_with_gravity();
+    let handle = add_ball(&mut world, 0.5, Vector::zeros());
+    world.rigid_body_mut(handle).unwrap().set_max_linear_speed(2.0);
+
+    run(&mut world, 60);
+
+    let rb = world.rigid_body(handle).unwrap();
+    assert!((rb.linear_velocity().norm() - 2.0).abs() < 1.0e-5);
+    assert!(rb.position().translation.vector.y > -2.5);
+}
+
+#[test]
+fn radial_impulse_pushes_nearby_dynamic_bodies() {
+    use crate::math::Point;
+    use crate::world::RadialFalloff;
+
+    let mut world = World::<f32>::new();
+    let near = add_ball(&mut world, 0.5, Vector::x() * 1.0);
+    let far = add_ball(&mut world, 0.5, Vector::x() * 4.0);
+    let fixed = RigidBodyDesc::new()
+        .collider(&ball_collider(0.5))
+        .translation(-Vector::x())
+        .status(BodyStatus::Static)
+        .build(&mut world)
+        .handle();
+
+    // Let the broad phase register the colliders first.
+    world.step();
+    world.apply_radial_impulse(&Point::origin(), 2.0, 1.0, RadialFalloff::Linear);
+    world.step();
+
+    let near_vel = world.rigid_body(near).unwrap().linear_velocity().clone();
+    assert!(near_vel.x > 0.0);
+    assert!(near_vel.y.abs() < 1.0e-5);
+    assert_eq!(*world.rigid_body(far).unwrap().linear_velocity(), Vector::zeros());
+    assert_eq!(*world.rigid_body(fixed).unwrap().linear_velocity(), Vector::zeros());
+
+    assert_eq!(RadialFalloff::Quadratic.factor(1.0, 2.0), 0.25);
+    assert_eq!(RadialFalloff::Linear.factor(3.0, 2.0), 0.0);
+}
+
+#[test]
+fn light_box_floats_in_fluid_volume() {
+    use ncollide::bounding_volume::AABB;
+    use crate::math::Point;
+    use crate::world::FluidVolume;
+
+    let mut world = world_with_gravity();
+
+    // Water fills the region below y = 0.
+    let mut extents = Vector::repeat(10.0);
+    extents[1] = 0.0;
+    let aabb = AABB::new(Point::origin() - Vector::repeat(10.0), Point::origin() + extents);
+    let mut fluid = FluidVolume::new(aabb, 2.0);
+    fluid.set_linear_drag(5.0);
+    fluid.set_angular_drag(1.0);
+    let _ = world.add_fluid_volume(fluid);
+
+    let handle = add_box(&mut world, 0.5, -Vector::y() * 3.0);
+
+    run(&mut world, 600);
+
+    // Half as dense as the fluid, the box floats half-submerged.
+    let y = world.rigid_body(handle).unwrap().position().translation.vector.y;
+    assert!(y.abs() < 0.1);
+}
+
+#[test]
+fn disabled_body_stops_supporting_the_bodies_above() {
+    let mut world = world_with_ground();
+    let shift = resting_height(0.5);
+    let lower = add_box(&mut world, 0.5, Vector::y() * shift);
+    let upper = add_box(&mut world, 0.5, Vector::y() * shift * 3.0);
+
+    run(&mut world, 500);
+
+    assert!(!world.rigid_body(upper).unwrap().is_active());
+    let lower_pos = *world.rigid_body(lower).unwrap().position();
+
+    world.rigid_body_mut(lower).unwrap().set_enabled(false);
+    world.step();
+    assert!(world.rigid_body(upper).unwrap().is_active());
+
+    run(&mut world, 100);
+
+    // The upper box fell through the disabled one, which did not move.
+    assert!(world.rigid_body(upper).unwrap().position().translation.vector.y < shift * 1.5);
+    assert_eq!(*world.rigid_body(lower).unwrap().position(), lower_pos);
+
+    let rb = world.rigid_body_mut(lower).unwrap();
+    rb.set_enabled(true);
+    assert!(rb.is_enabled());
+    assert_eq!(rb.status(), BodyStatus::Dynamic);
+}
+
+#[test]
+fn rotated_compound_contacts_lie_on_transformed_children() {
+    use std::f32::consts::FRAC_PI_2;
+    use na;
+    use ncollide::shape::Compound;
+    use crate::math::Isometry;
+
+    let mut world = world_with_ground();
+
+    // An L-shaped compound whose second arm is itself rotated.
+    #[cfg(feature = "dim2")]
+    let (quarter_turn, tilt) = (FRAC_PI_2, 0.7);
+    #[cfg(feature = "dim3")]
+    let (quarter_turn, tilt) = (Vector::z() * FRAC_PI_2, Vector::repeat(0.7));
+
+    let arm = ShapeHandle::new(Cuboid::new(Vector::repeat(0.2) + Vector::x() * 0.8));
+    let children = vec![
+        (Isometry::new(Vector::x() * 0.8, na::zero()), arm.clone()),
+        (Isometry::new(Vector::y() * 0.8, quarter_turn), arm),
+    ];
+
+    let collider_desc = ColliderDesc::new(ShapeHandle::new(Compound::new(children.clone())))
+        .density(1.0);
+    let body = RigidBodyDesc::new()
+        .collider(&collider_desc)
+        .position(Isometry::new(Vector::y() * 2.0, tilt))
+        .build(&mut world)
+        .handle();
+
+    let mut ncontacts = 0;
+
+    for _ in 0..120 {
+        world.step();
+
+        for (c1, c2, _, manifold) in world.collider_world().contact_pairs(false) {
+            for c in manifold.contacts() {
+                let (collider, point) = if c1.body() == body {
+                    (c1, c.contact.world1)
+                } else {
+                    assert_eq!(c2.body(), body);
+                    (c2, c.contact.world2)
+                };
+
+                // This is the transform the testbed uses to render each child.
+                let dist = children
+                    .iter()
+                    .map(|(delta, shape)| {
+                        let pos = collider.position() * delta;
+                        shape.as_point_query().unwrap().distance_to_point(&pos, &point, true)
+                    })
+                    .fold(::std::f32::MAX, f32::min);
+
+                assert!(dist < 1.0e-3, "Contact point {:?} is {:?} away from the children.", point, dist);
+                ncontacts += 1;
+            }
+        }
+    }
+
+    assert!(ncontacts > 0);
+}
+
+#[test]
+fn kinematic_move_slides_and_steps_up() {
+    let mut world = World::<f32>::new();
+    let _ = add_ground(&mut world, 10.0);
+
+    // A low step at x = 2, and a wall too high to step over at x = 6.
+    let _ = ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(1.0) + Vector::y() * -0.9)))
+        .translation(Vector::x() * 3.0 + Vector::y() * 0.1)
+        .build(&mut world);
+    let _ = ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(0.5) + Vector::y() * 1.5)))
+        .translation(Vector::x() * 6.5 + Vector::y() * 2.0)
+        .build(&mut world);
+
+    let collider_desc = ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(0.5))));
+    let character = RigidBodyDesc::new()
+        .collider(&collider_desc)
+        .status(BodyStatus::Kinematic)
+        .translation(Vector::y() * 0.52)
+        .build(&mut world)
+        .handle();
+    world.step();
+
+    // The character steps on the step instead of being blocked by it.
+    let mv = world.move_kinematic(character, &(Vector::x() * 2.0));
+    assert!(mv.stepped_up);
+    let pos = world.rigid_body(character).unwrap().position().translation.vector;
+    assert!((pos.x - 2.0).abs() < 1.0e-3);
+    assert!(pos.y > 0.7 && pos.y < 0.8);
+
+    // Moving diagonally into the ground slides along it.
+    let mv = world.move_kinematic(character, &(Vector::x() * 1.0 - Vector::y()));
+    assert!(mv.grounded);
+    let pos = world.rigid_body(character).unwrap().position().translation.vector;
+    assert!((pos.x - 3.0).abs() < 1.0e-3);
+    assert!(pos.y > 0.7 && pos.y < 0.8);
+
+    // The wall stops the character.
+    let mv = world.move_kinematic(character, &(Vector::x() * 5.0));
+    assert!(!mv.stepped_up);
+    assert!(!mv.hits.is_empty());
+    let pos = world.rigid_body(character).unwrap().position().translation.vector;
+    assert!(pos.x < 5.5 && pos.x > 5.4);
+}
+
+#[test]
+fn kinematic_move_pushes_dynamic_bodies() {
+    let mut world = World::<f32>::new();
+    let character = RigidBodyDesc::new()
+        .collider(&box_collider(0.5))
+        .status(BodyStatus::Kinematic)
+        .build(&mut world)
+        .handle();
+    let crate_body = add_box(&mut world, 0.5, Vector::x() * 1.5);
+    world.step();
+
+    let mv = world.move_kinematic(character, &(Vector::x() * 1.0));
+    assert_eq!(mv.hits.len(), 1);
+    assert_eq!(mv.hits[0].body, crate_body);
+    assert!(world.rigid_body(character).unwrap().position().translation.vector.x < 0.5);
+    assert!(world.rigid_body(crate_body).unwrap().velocity().linear.x > 0.0);
+}
+
+#[test]
+fn quiescent_world_resumes_when_woken_up() {
+    use crate::math::{Force, ForceType};
+
+    let mut world = world_with_ground();
+    let handle = add_box(&mut world, 0.5, Vector::y() * resting_height(0.5));
+
+    assert!(!world.is_quiescent());
+
+    run(&mut world, 500);
+
+    assert!(world.is_quiescent());
+    let pos = *world.rigid_body(handle).unwrap().position();
+    let t = world.integration_parameters().t;
+    world.step();
+    assert_eq!(*world.rigid_body(handle).unwrap().position(), pos);
+    assert!(world.integration_parameters().t > t);
+
+    // An impulse wakes the body up and the simulation resumes.
+    world.body_mut(handle).unwrap().apply_force(
+        0,
+        &Force::linear(Vector::y() * 10.0),
+        ForceType::Impulse,
+        true,
+    );
+    assert!(!world.is_quiescent());
+    world.step();
+    assert!(world.rigid_body(handle).unwrap().position().translation.vector.y > pos.translation.vector.y);
+
+    run(&mut world, 1000);
+
+    // So does adding a new body.
+    assert!(world.is_quiescent());
+    let new_body = add_box(&mut world, 0.5, Vector::y() * 5.0);
+    assert!(!world.is_quiescent());
+    world.step();
+    assert!(world.rigid_body(new_body).unwrap().position().translation.vector.y < 5.0);
+}
+
+#[test]
+fn substeps_stabilize_stacks() {
+    // The largest distance the top box of a stack sinks during its first timesteps.
+    fn max_stack_sink(nsubsteps: usize) -> f32 {
+        let mut world = world_with_ground();
+        world.set_substeps(nsubsteps);
+        world.set_velocity_iterations(2);
+        world.set_position_iterations(1);
+
+        let shift = resting_height(0.5) * 2.0;
+        let top = (0..10)
+            .map(|i| add_box(&mut world, 0.5, Vector::y() * (i as f32 * shift + shift / 2.0)))
+            .last()
+            .unwrap();
+
+        let start = world.rigid_body(top).unwrap().position().translation.vector.y;
+        let mut max_sink = 0.0f32;
+
+        for _ in 0..120 {
+            world.step();
+            let y = world.rigid_body(top).unwrap().position().translation.vector.y;
+            max_sink = max_sink.max(start - y);
+        }
+
+        max_sink
+    }
+
+    let sink1 = max_stack_sink(1);
+    let sink4 = max_stack_sink(4);
+    assert!(sink4 < sink1, "Sink with 4 substeps: {}, with 1 substep: {}", sink4, sink1);
+}
+
+#[test]
+fn substeps_preserve_the_timestep() {
+    let mut world = World::<f32>::new();
+    let dt = world.timestep();
+    world.set_substeps(4);
+
+    run(&mut world, 3);
+
+    assert_eq!(world.timestep(), dt);
+    assert!((world.integration_parameters().t - dt * 3.0).abs() < 1.0e-6);
+}
+
+#[test]
+fn contacts_expose_manifolds_with_impulses() {
+    let mut world = world_with_gravity();
+    let ground = add_ground(&mut world, 10.0);
+    let body = add_box(&mut world, 0.5, Vector::y() * resting_height(0.5));
+
+    run(&mut world, 30);
+
+    let manifolds: Vec<_> = world.contacts().collect();
+    assert_eq!(manifolds.len(), 1);
+
+    let manifold = &manifolds[0];
+    assert!(manifold.collider1() == ground || manifold.collider2() == ground);
+    assert!(manifold.body1() == body || manifold.body2() == body);
+    assert!(manifold.len() > 0);
+
+    let total_impulse: f32 = manifold.contacts().map(|c| c.normal_impulse).sum();
+    assert!(total_impulse > 0.0);
+    assert!(manifold.deepest_contact().is_some());
+}
+
+#[test]
+fn body_margin_inflates_contacts_but_not_mass() {
+    let mut world = world_with_ground();
+    let rad = 0.5;
+    let body = add_ball(&mut world, rad, Vector::y() * rad);
+    let mass = world.rigid_body(body).unwrap().augmented_mass().linear;
+
+    let margin = 0.1;
+    world.rigid_body_mut(body).unwrap().set_margin(margin);
+
+    run(&mut world, 120);
+
+    for collider in world.collider_world().body_colliders(body) {
+        assert_eq!(collider.margin(), margin);
+    }
+
+    let rb = world.rigid_body(body).unwrap();
+    assert_eq!(rb.augmented_mass().linear, mass);
+    assert!(rb.position().translation.vector.y > rad + margin * 0.8);
+}
+
+#[test]
+fn changing_the_margin_keeps_the_contacts() {
+    let mut world = world_with_ground();
+    let rad = 0.5;
+    let body = add_ball(&mut world, rad, Vector::y() * (rad + ball_collider(rad).get_margin()));
+
+    run(&mut world, 30);
+    let collider = world.collider_world().body_colliders(body).next().unwrap().handle();
+
+    world.rigid_body_mut(body).unwrap().set_margin(0.1);
+    world.step();
+
+    assert!(world.contact_events().iter().next().is_none());
+    assert_eq!(world.contacts().count(), 1);
+    assert_eq!(world.collider(collider).unwrap().margin(), 0.1);
+}
+
+#[test]
+fn penetration_counters_stay_below_the_slop_on_a_resting_stack() {
+    let mut world = world_with_ground();
+    world.enable_performance_counters();
+
+    let shift = resting_height(0.5) * 2.0;
+    for i in 0..3 {
+        let _ = add_box(&mut world, 0.5, Vector::y() * (i as f32 * shift + shift / 2.0));
+    }
+
+    run(&mut world, 300);
+
+    let cd = world.performance_counters().collision_detection();
+    assert!(cd.max_penetration >= cd.avg_penetration);
+    assert!(cd.max_penetration < 0.01, "Max penetration: {}", cd.max_penetration);
+}
+
+#[test]
+fn joints_report_world_anchors_and_coordinates() {
+    use crate::joint::PrismaticConstraint;
+    use crate::math::Point;
+    use crate::object::BodyPartHandle;
+
+    let mut world = World::new();
+    let parts: Vec<_> = (0..2)
+        .map(|i| BodyPartHandle(add_box(&mut world, 0.5, Vector::x() * (i as f32 + 2.0)), 0))
+        .collect();
+
+    let anchor = Point::from(Vector::y());
+    let handles: Vec<_> = parts
+        .iter()
+        .map(|part| {
+            let constraint =
+                PrismaticConstraint::new(BodyPartHandle::ground(), *part, Point::origin(), Vector::x_axis(), anchor);
+            world.add_constraint(constraint)
+        })
+        .collect();
+
+    let joints: Vec<_> = world.joints().collect();
+    assert_eq!(joints.len(), 2);
+
+    for (i, joint) in joints.iter().enumerate() {
+        let x = i as f32 + 2.0;
+        assert_eq!(joint.handle, handles[i]);
+        assert_eq!(joint.body_part2, parts[i]);
+        assert!(joint.anchor1.coords.norm() < 1.0e-5);
+        assert!((joint.anchor2.coords - (Vector::x() * x + Vector::y())).norm() < 1.0e-5);
+        assert!((joint.coordinate.unwrap() - x).abs() < 1.0e-5);
+        assert_eq!(joint.motor_impulse, 0.0);
+    }
+}
+
+#[test]
+fn sleep_threshold_delays_sleeping() {
+    use crate::object::SleepThreshold;
+
+    let mut world = world_with_gravity();
+    let _ = add_ground(&mut world, 50.0);
+
+    let rad = 0.5;
+    let debris = add_ball(&mut world, rad, Vector::y() * rad);
+    let machinery = add_ball(&mut world, rad, Vector::x() * 10.0 + Vector::y() * rad);
+
+    world
+        .rigid_body_mut(machinery)
+        .unwrap()
+        .set_sleep_threshold(Some(SleepThreshold::new(0.1, 0.1, 5.0)));
+
+    run(&mut world, 200);
+
+    assert!(!world.rigid_body(debris).unwrap().is_active());
+    assert!(world.rigid_body(machinery).unwrap().is_active());
+
+    run(&mut world, 200);
+
+    assert!(!world.rigid_body(machinery).unwrap().is_active());
+}
+
+#[test]
+fn linear_impulse_ignores_center_of_mass_offset_and_locked_axes() {
+    use crate::object::LockedAxes;
+
+    let mut world = World::new();
+    let body = add_body(&mut world, &box_collider(0.5).translation(Vector::x() * 2.0), Vector::zeros());
+
+    let rb = world.rigid_body_mut(body).unwrap();
+    rb.set_locked_axes(LockedAxes::TRANSLATION_X);
+    rb.deactivate();
+    rb.apply_linear_impulse(Vector::x() * 3.0 + Vector::y() * 2.0);
+
+    let mass = rb.augmented_mass().linear;
+    let velocity = rb.velocity();
+    assert!(rb.is_active());
+    assert_eq!(velocity.linear.x, 0.0);
+    assert!((velocity.linear.y - 2.0f32 / mass).abs() < 1.0e-5);
+    assert_eq!(velocity.angular_vector().norm(), 0.0);
+}
+
+#[test]
+fn identical_scenes_give_identical_results() {
+    use crate::math::Isometry;
+
+    fn simulate() -> Vec<Isometry<f32>> {
+        let mut world = world_with_ground();
+        let mut handles = Vec::new();
+
+        for i in 0..5 {
+            for j in 0..5 {
+                let translation = Vector::x() * (i as f32 * 0.9) + Vector::y() * (j as f32 * 1.1 + 0.6);
+                handles.push(add_box(&mut world, 0.5, translation));
+            }
+        }
+
+        run(&mut world, 200);
+
+        handles.iter().map(|h| *world.rigid_body(*h).unwrap().position()).collect()
+    }
+
+    let positions1 = simulate();
+    let positions2 = simulate();
+    assert_eq!(positions1, positions2);
+}
+
+#[test]
+fn world_desc_builds_and_validates() {
+    use crate::world::{WorldDesc, WorldDescError};
+
+    let world = WorldDesc::new()
+        .gravity(Vector::y() * -9.81)
+        .timestep(1.0 / 120.0)
+        .velocity_iterations(12)
+        .position_iterations(4)
+        .substeps(2)
+        .build();
+
+    assert_eq!(*world.gravity(), Vector::y() * -9.81);
+    assert_eq!(world.timestep(), 1.0 / 120.0);
+    assert_eq!(world.velocity_iterations(), 12);
+    assert_eq!(world.position_iterations(), 4);
+    assert_eq!(world.substeps(), 2);
+
+    let default_world = WorldDesc::<f32>::new().build();
+    let new_world = World::<f32>::new();
+    assert_eq!(default_world.timestep(), new_world.timestep());
+    assert_eq!(default_world.velocity_iterations(), new_world.velocity_iterations());
+
+    let err = |desc: WorldDesc<f32>| desc.try_build().err();
+    assert_eq!(err(WorldDesc::new().timestep(0.0)), Some(WorldDescError::NonPositiveTimestep));
+    assert_eq!(err(WorldDesc::new().velocity_iterations(0)), Some(WorldDescError::ZeroVelocityIterations));
+    assert_eq!(err(WorldDesc::new().substeps(0)), Some(WorldDescError::ZeroSubsteps));
+    assert_eq!(err(WorldDesc::new().friction(-1.0)), Some(WorldDescError::NegativeFriction));
+    assert_eq!(err(WorldDesc::new().restitution(1.5)), Some(WorldDescError::InvalidRestitution));
+}
+
+#[test]
+fn contact_impact_is_reported_once_per_collision() {
+    use std::sync::{Arc, Mutex};
+    use crate::detection::{ContactHandler, ContactImpact, ContactPair};
+
+    struct Recorder(Arc<Mutex<Vec<f32>>>);
+
+    impl ContactHandler<f32> for Recorder {
+        fn handle_contact_started(&mut self, _: &ContactPair<f32>) {}
+        fn handle_contact_stopped(&mut self, _: &ContactPair<f32>) {}
+
+        fn handle_contact_impact(&mut self, impact: &ContactImpact<f32>) {
+            self.0.lock().unwrap().push(impact.normal_impulse);
+        }
+    }
+
+    let mut world = world_with_gravity();
+    let ground = add_ground(&mut world, 10.0);
+    let ball = add_ball(&mut world, 0.5, Vector::y() * 3.0);
+    let mass = world.rigid_body(ball).unwrap().augmented_mass().linear;
+    let ball_collider = world.collider_world().body_colliders(ball).next().unwrap().handle();
+
+    let impacts = Arc::new(Mutex::new(Vec::new()));
+    world.set_contact_handler(Box::new(Recorder(impacts.clone())));
+
+    run(&mut world, 180);
+
+    let impacts = impacts.lock().unwrap();
+    assert_eq!(impacts.len(), 1);
+
+    // The impact stops a ball falling from a height of 2.5, which is much stronger than
+    // what is needed to support it during a single timestep once at rest.
+    let resting_impulse = world.contact_pair_normal_impulse(ground, ball_collider);
+    assert!(resting_impulse > 0.0);
+    let weight_impulse = mass * 9.81 * world.timestep();
+    assert!((resting_impulse - weight_impulse).abs() < weight_impulse * 0.1);
+    assert!(impacts[0] > resting_impulse * 2.0, "Impact: {}, resting: {}", impacts[0], resting_impulse);
+}
+
+#[test]
+fn topmost_collider_prefers_small_and_recent_colliders() {
+    use ncollide::world::CollisionGroups;
+    use crate::math::Point;
+
+    let mut world = World::<f32>::new();
+    let big = ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(2.0))))
+        .build(&mut world)
+        .handle();
+    let small = ColliderDesc::new(ShapeHandle::new(Ball::new(0.5)))
+        .build(&mut world)
+        .handle();
+    let _ = ColliderDesc::new(ShapeHandle::new(Ball::new(0.1)))
+        .sensor(true)
+        .build(&mut world);
+    let small_twin = ColliderDesc::new(ShapeHandle::new(Ball::new(0.5)))
+        .build(&mut world)
+        .handle();
+
+    world.step();
+
+    let groups = CollisionGroups::new();
+    let topmost = |world: &World<f32>, point: Point<f32>| {
+        world.collider_world().topmost_collider_containing_point(&point, &groups).map(|co| co.handle())
+    };
+
+    assert_eq!(topmost(&world, Point::origin()), Some(small_twin));
+    assert_eq!(topmost(&world, Point::from(Vector::repeat(1.5))), Some(big));
+    assert_eq!(topmost(&world, Point::from(Vector::repeat(3.0))), None);
+
+    world.remove_colliders(&[small_twin]);
+    world.step();
+    assert_eq!(topmost(&world, Point::origin()), Some(small));
+}
+
+#[test]
+fn cleared_world_allocates_handles_from_scratch() {
+    use crate::joint::FixedConstraint;
+    use crate::math::{Point, Rotation};
+    use crate::object::BodyPartHandle;
+
+    fn build_scene(world: &mut World<f32>) -> Vec<(BodyHandle, Vec<ColliderHandle>)> {
+        let handles: Vec<_> = (0..3)
+            .map(|i| {
+                let body = add_box(world, 0.5, Vector::y() * (i as f32));
+                let colliders = world.collider_world().body_colliders(body).map(|co| co.handle()).collect();
+                (body, colliders)
+            })
+            .collect();
+
+        let joint = FixedConstraint::new(
+            BodyPartHandle(handles[0].0, 0),
+            BodyPartHandle(handles[1].0, 0),
+            Point::origin(),
+            Rotation::identity(),
+            Point::origin(),
+            Rotation::identity(),
+        );
+        let _ = world.add_constraint(joint);
+        handles
+    }
+
+    let mut fresh = World::new();
+    let expected = build_scene(&mut fresh);
+
+    let mut world = world_with_gravity();
+    let _ = build_scene(&mut world);
+    let _ = build_scene(&mut world);
+
+    run(&mut world, 10);
+
+    let contact_ids: Vec<_> = world.contacts().flat_map(|m| m.contacts()).map(|c| c.id).collect();
+    assert!(contact_ids.iter().any(|id| world.contact_normal_impulse(*id) != 0.0));
+
+    world.clear();
+    assert!(contact_ids.iter().all(|id| world.contact_normal_impulse(*id) == 0.0));
+    assert_eq!(world.bodies().count(), 0);
+    assert_eq!(world.colliders().count(), 0);
+    assert_eq!(world.joints().count(), 0);
+    assert_eq!(world.contacts().count(), 0);
+    assert_eq!(world.integration_parameters().t, 0.0);
+    assert_eq!(world.gravity(), &(Vector::y() * -9.81));
+
+    assert_eq!(build_scene(&mut world), expected);
+    world.step();
+    assert_eq!(world.joints().count(), 1);
+}
+
+#[test]
+fn total_contact_force_supports_the_weight_of_a_resting_body() {
+    let mut world = world_with_ground();
+    let body = add_box(&mut world, 0.5, Vector::y() * resting_height(0.5));
+    let weight = Vector::y() * (world.rigid_body(body).unwrap().augmented_mass().linear * 9.81);
+
+    assert_eq!(world.total_contact_force(body), Vector::zeros());
+
+    run(&mut world, 60);
+
+    let force = world.total_contact_force(body);
+    assert!((force - weight).norm() < weight.norm() * 0.1, "Force: {:?}, weight: {:?}", force, weight);
+
+    // The ground is pushed down by the same force.
+    let ground_force = world.total_contact_force(BodyHandle::ground());
+    assert!((ground_force + force).norm() < 1.0e-3);
+}
+
+#[test]
+fn world_sleep_threshold_applies_to_bodies_without_their_own() {
+    use crate::object::SleepThreshold;
+
+    let mut world = world_with_gravity();
+    world.set_sleep_threshold(Some(SleepThreshold::new(0.1, 0.1, 5.0)));
+    let _ = add_ground(&mut world, 50.0);
+
+    let rad = 0.5;
+    let ball = add_ball(&mut world, rad, Vector::y() * rad);
+
+    run(&mut world, 200);
+
+    assert!(world.rigid_body(ball).unwrap().is_active());
+
+    run(&mut world, 200);
+
+    assert!(!world.rigid_body(ball).unwrap().is_active());
+
+    world.clear();
+    assert!(world.sleep_threshold().is_some());
+}
+
+#[test]
+fn teleported_body_loses_its_contacts() {
+    use na;
+    use ncollide::events::ContactEvent;
+    use crate::math::Isometry;
+
+    let mut world = world_with_ground();
+    let body = add_box(&mut world, 0.5, Vector::y() * resting_height(0.5));
+    let collider = world.collider_world().body_colliders(body).next().unwrap().handle();
+
+    run(&mut world, 10);
+
+    let old_ids: Vec<_> = world.contacts().flat_map(|m| m.contacts()).map(|c| c.id).collect();
+    assert!(old_ids.iter().any(|id| world.contact_normal_impulse(*id) > 0.0));
+
+    // Still on the ground: the contacts are kept but not their impulses.
+    let position = Isometry::new(Vector::x() * 3.0 + Vector::y() * resting_height(0.5), na::zero());
+    world.teleport_rigid_body(body, position);
+    assert_eq!(world.contacts().count(), 1);
+    assert!(old_ids.iter().all(|id| world.contact_normal_impulse(*id) == 0.0));
+
+    // Away from the ground: the contacts are removed right away.
+    world.teleport_rigid_body(body, Isometry::new(Vector::y() * 5.0, na::zero()));
+    assert_eq!(world.contacts().count(), 0);
+    assert!(world.contact_events().iter().any(|e| match *e {
+        ContactEvent::Stopped(c1, c2) => c1 == collider || c2 == collider,
+        ContactEvent::Started(..) => false,
+    }));
+}
+
+#[test]
+fn restored_snapshot_replays_the_same_fall() {
+    let mut world = world_with_gravity();
+    let body = add_box(&mut world, 0.5, Vector::zeros());
+    world.rigid_body_mut(body).unwrap().set_max_linear_speed(1.0);
+
+    let snapshot = world.snapshot();
+    run(&mut world, 10);
+    let position = *world.rigid_body(body).unwrap().position();
+    let velocity = world.rigid_body(body).unwrap().velocity().linear;
+
+    world.rigid_body_mut(body).unwrap().set_max_linear_speed(100.0);
+    world.restore(&snapshot);
+    assert_eq!(world.integration_parameters().t, 0.0);
+    assert_eq!(world.rigid_body(body).unwrap().max_linear_speed(), 1.0);
+
+    // Without any contact, the same steps give the same results.
+    run(&mut world, 10);
+    assert_eq!(*world.rigid_body(body).unwrap().position(), position);
+    assert_eq!(world.rigid_body(body).unwrap().velocity().linear, velocity);
+}
@@ -1,21 +1,33 @@
 use slab::Slab;
+use std::collections::{HashMap, HashSet};
+use std::mem;
 
 use na::{self, Real};
 use ncollide;
-use ncollide::events::{ContactEvents, ProximityEvents};
+use ncollide::bounding_volume::{BoundingVolume, AABB};
+use ncollide::query::{self, ClosestPoints, Proximity};
+use ncollide::shape::{FeatureId, Shape};
+use ncollide::events::{ContactEvent, ContactEvents, ProximityEvents};
+use ncollide::utils::GenerationalId;
+use ncollide::world::CollisionGroups;
 
 use crate::counters::Counters;
-use crate::detection::{ActivationManager, ColliderContactManifold};
+use crate::detection::{ActivationHandler, ActivationManager, ColliderContactManifold, ColliderContactManifoldHandles,
+                       ColliderContactManifolds, ContactHandler, ContactImpact, ContactManifoldInfo, ContactPair, Island,
+                       IslandSet, ProximityPair};
 use crate::force_generator::{ForceGenerator, ForceGeneratorHandle};
-use crate::joint::{ConstraintHandle, JointConstraint};
-use crate::math::Vector;
+use crate::joint::{ConstraintHandle, JointBreakHandler, JointConstraint, JointInfo};
+use crate::math::{ForceType, Isometry, Point, Translation, Vector};
 use crate::object::{
-    Body, BodySet, BodyDesc, BodyStatus, Collider, ColliderAnchor,
-    ColliderHandle, Multibody, RigidBody, BodyHandle,
+    Body, BodySet, BodyDesc, BodyPartHandle, BodyStatus, Collider, ColliderAnchor,
+    ColliderHandle, Multibody, RigidBody, BodyHandle, SleepThreshold,
 };
-use crate::material::MaterialsCoefficientsTable;
-use crate::solver::{ContactModel, IntegrationParameters, MoreauJeanSolver, SignoriniCoulombPyramidModel};
-use crate::world::ColliderWorld;
+use crate::material::{MaterialId, MaterialsCoefficientsTable};
+use crate::solver::{ContactModel, IntegrationParameters, Integrator, MoreauJeanSolver,
+                    SignoriniCoulombPyramidModel};
+use crate::world::{ActualMove, ColliderWorld, FluidVolume, FluidVolumeHandle, KinematicMoveParameters, RadialFalloff,
+                   ShapeCastHit, WorldSnapshot};
+use crate::world::kinematic_move::KinematicSweep;
 
 
 /// The physics world.
@@ -23,6 +35,9 @@ pub struct World<N: Real> {
     counters: Counters,
     bodies: BodySet<N>,
     active_bodies: Vec<BodyHandle>,
+    islands: IslandSet,
+    // The contact manifolds solved during the last step. Kept to avoid reallocating them at each step.
+    contact_manifolds: Vec<ColliderContactManifoldHandles>,
     cworld: ColliderWorld<N>,
     solver: MoreauJeanSolver<N>,
     activation_manager: ActivationManager<N>,
@@ -31,8 +46,25 @@ pub struct World<N: Real> {
     prediction: N,
     gravity: Vector<N>,
     constraints: Slab<Box<JointConstraint<N>>>,
+    // The maximum force and torque of each breakable joint.
+    joint_breaking_thresholds: HashMap<ConstraintHandle, (Option<N>, Option<N>)>,
+    joint_break_handler: Option<Box<JointBreakHandler<N>>>,
     forces: Slab<Box<ForceGenerator<N>>>,
+    fluid_volumes: Slab<FluidVolume<N>>,
     params: IntegrationParameters<N>,
+    kinematic_move_params: KinematicMoveParameters<N>,
+    contact_handler: Option<Box<ContactHandler<N>>>,
+    // Pairs of colliders that started touching and whose impact was not reported yet.
+    pending_impacts: Vec<(ColliderHandle, ColliderHandle)>,
+    activation_handler: Option<Box<ActivationHandler<N>>>,
+    sleeping_bodies: HashSet<BodyHandle>,
+    // Always empty between two steps. Kept to avoid reallocating the transitions at each step.
+    activation_transitions: Vec<(BodyHandle, bool)>,
+    ccd_positions: Vec<(BodyHandle, Isometry<N>)>,
+    accumulator: N,
+    max_steps_per_frame: usize,
+    substeps: usize,
+    check_invariants: bool,
 }
 
 impl<N: Real> World<N> {
@@ -40,8 +72,16 @@ impl<N: Real> World<N> {
     ///
     /// The ground body is automatically created and added to the world without any colliders attached.
     pub fn new() -> Self {
+        Self::with_broad_phase_margin(Self::default_broad_phase_margin())
+    }
+
+    // The margin added to the bounding volumes of the broad phase by `World::new()`.
+    pub(crate) fn default_broad_phase_margin() -> N {
+        na::convert(0.01f64)
+    }
+
+    pub(crate) fn with_broad_phase_margin(bv_margin: N) -> Self {
         let counters = Counters::new(false);
-        let bv_margin = na::convert(0.01f64);
         let prediction = na::convert(0.002);
         let bodies = BodySet::new();
         let active_bodies = Vec::new();
@@ -59,6 +99,8 @@ impl<N: Real> World<N> {
             counters,
             bodies,
             active_bodies,
+            islands: IslandSet::new(),
+            contact_manifolds: Vec::new(),
             cworld,
             solver,
             activation_manager,
@@ -66,8 +108,22 @@ impl<N: Real> World<N> {
             prediction,
             gravity,
             constraints,
+            joint_breaking_thresholds: HashMap::new(),
+            joint_break_handler: None,
             forces,
-            params
+            fluid_volumes: Slab::new(),
+            params,
+            kinematic_move_params: KinematicMoveParameters::default(),
+            contact_handler: None,
+            pending_impacts: Vec::new(),
+            activation_handler: None,
+            sleeping_bodies: HashSet::new(),
+            activation_transitions: Vec::new(),
+            ccd_positions: Vec::new(),
+            accumulator: N::zero(),
+            max_steps_per_frame: 5,
+            substeps: 1,
+            check_invariants: false,
         }
     }
 
@@ -96,6 +152,95 @@ impl<N: Real> World<N> {
         self.solver.set_contact_model(Box::new(model))
     }
 
+    /// The normal impulse applied at the given contact during the last timestep.
+    ///
+    /// Returns zero if the contact was not handled by the constraint solver, e.g., if it involves
+    /// only sleeping bodies or was just created.
+    pub fn contact_normal_impulse(&self, contact_id: GenerationalId) -> N {
+        self.solver.contact_model().normal_impulse(contact_id)
+    }
+
+    /// The sum of the normal impulses applied during the last timestep at all the contacts between two colliders.
+    ///
+    /// Returns zero if the two colliders are not in contact.
+    pub fn contact_pair_normal_impulse(&self, collider1: ColliderHandle, collider2: ColliderHandle) -> N {
+        Self::pair_normal_impulse(&self.cworld, self.solver.contact_model(), collider1, collider2)
+    }
+
+    fn pair_normal_impulse(
+        cworld: &ColliderWorld<N>,
+        contact_model: &ContactModel<N>,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ) -> N {
+        cworld
+            .contact_pair(collider1, collider2, true)
+            .map(|(_, _, _, manifold)| {
+                manifold.contacts().fold(N::zero(), |sum, c| sum + contact_model.normal_impulse(c.id))
+            })
+            .unwrap_or_else(N::zero)
+    }
+
+    /// The total force applied by the contacts to the given body during the last timestep.
+    ///
+    /// This is the sum of the normal and friction impulses applied at all the contacts of the body,
+    /// divided by the duration of the last substep. A body resting on the ground is thus supported by
+    /// a force roughly opposite to its weight. This is only a one-step estimate: it fluctuates with the
+    /// convergence of the solver, and its friction part depends on the contact model. Contacts of
+    /// sleeping bodies report the force of the last timestep they were simulated.
+    pub fn total_contact_force(&self, body: BodyHandle) -> Vector<N> {
+        let contact_model = self.solver.contact_model();
+        let mut impulse = Vector::zeros();
+
+        for (c1, c2, _, manifold) in self.cworld.contact_pairs(true) {
+            // The impulses between two colliders of the same body cancel out.
+            let sign = match (c1.body() == body, c2.body() == body) {
+                (false, true) => N::one(),
+                (true, false) => -N::one(),
+                _ => continue,
+            };
+
+            for c in manifold.contacts() {
+                impulse += contact_model.contact_impulse(c) * sign;
+            }
+        }
+
+        let substep_dt = self.params.dt / na::convert(self.substeps as f64);
+        impulse / substep_dt
+    }
+
+    /// All the contact manifolds between the colliders of this world with at least one contact.
+    ///
+    /// They are the ones computed at the end of the last timestep, along with the impulses applied
+    /// by the solver at each of their contacts during this timestep.
+    pub fn contacts(&self) -> impl Iterator<Item = ContactManifoldInfo<N>> {
+        let contact_model = self.solver.contact_model();
+
+        self.cworld.contact_pairs(true).map(move |(c1, c2, _, manifold)| {
+            ContactManifoldInfo::new(ColliderContactManifold::new(c1, c2, manifold), contact_model)
+        })
+    }
+
+    /// Whether the impulses of the previous timestep are used to initialize the constraints solver.
+    pub fn warm_starting(&self) -> bool {
+        self.params.warm_starting
+    }
+
+    /// Enables or disables the initialization of the constraints solver with the impulses of the previous timestep.
+    ///
+    /// When disabled, the solver starts from zero impulses at each timestep. The impulses applied at
+    /// each contact can be compared using `self.contact_normal_impulse(...)`.
+    pub fn set_warm_starting(&mut self, enabled: bool) {
+        self.params.warm_starting = enabled
+    }
+
+    /// Set the scheme used to integrate the motion of all bodies.
+    ///
+    /// The default is `SymplecticEuler`.
+    pub fn set_integrator<I: Integrator<N> + 'static>(&mut self, integrator: I) {
+        self.solver.set_integrator(Box::new(integrator))
+    }
+
     /// Retrieve a reference to the parameters for the integration.
     pub fn integration_parameters(&self) -> &IntegrationParameters<N> {
         &self.params
@@ -106,6 +251,39 @@ impl<N: Real> World<N> {
         &mut self.params
     }
 
+    /// The parameters of the moves performed by `self.move_kinematic(...)`.
+    pub fn kinematic_move_parameters(&self) -> &KinematicMoveParameters<N> {
+        &self.kinematic_move_params
+    }
+
+    /// Retrieve a mutable reference to the parameters of the moves performed by `self.move_kinematic(...)`.
+    pub fn kinematic_move_parameters_mut(&mut self) -> &mut KinematicMoveParameters<N> {
+        &mut self.kinematic_move_params
+    }
+
+    /// Reference to the manager responsible for putting bodies to sleep and waking them up.
+    pub fn activation_manager(&self) -> &ActivationManager<N> {
+        &self.activation_manager
+    }
+
+    /// Mutable reference to the manager responsible for putting bodies to sleep and waking them up.
+    pub fn activation_manager_mut(&mut self) -> &mut ActivationManager<N> {
+        &mut self.activation_manager
+    }
+
+    /// The velocity thresholds and time to sleep used by the bodies that do not have their own, if any.
+    pub fn sleep_threshold(&self) -> Option<&SleepThreshold<N>> {
+        self.activation_manager.sleep_threshold()
+    }
+
+    /// Sets the linear and angular velocity thresholds and the time to sleep used by the bodies that do not have their own.
+    ///
+    /// A body can only sleep once it stayed under both velocity thresholds during `threshold.time` seconds.
+    /// A value of `None` (the default) lets those bodies rely on their energy-based deactivation threshold.
+    pub fn set_sleep_threshold(&mut self, threshold: Option<SleepThreshold<N>>) {
+        self.activation_manager.set_sleep_threshold(threshold)
+    }
+
     /// Reference to the lookup table for friction and restitution coefficients.
     pub fn materials_coefficients_table(&self) -> &MaterialsCoefficientsTable<N> {
         &self.material_coefficients
@@ -116,6 +294,15 @@ impl<N: Real> World<N> {
         &mut self.material_coefficients
     }
 
+    /// Overrides the friction and restitution coefficients used for contacts between two materials.
+    ///
+    /// This applies to the contacts between colliders whose materials have the ids `m1` and `m2`,
+    /// in any order, instead of combining the coefficients of both materials.
+    pub fn set_material_pair(&mut self, m1: MaterialId, m2: MaterialId, friction: N, restitution: N) {
+        self.material_coefficients.set_friction_coefficient(m1, m2, friction);
+        self.material_coefficients.set_restitution_coefficient(m1, m2, restitution);
+    }
+
     /// Retrieve the timestep used for the integration.
     pub fn timestep(&self) -> N {
         self.params.dt
@@ -126,6 +313,210 @@ impl<N: Real> World<N> {
         self.params.dt = dt;
     }
 
+    /// The maximum number of iterations performed by the velocity constraints solver.
+    pub fn velocity_iterations(&self) -> usize {
+        self.params.max_velocity_iterations
+    }
+
+    /// Sets the maximum number of iterations performed by the velocity constraints solver.
+    ///
+    /// More iterations improve the stability of stacks and joint chains at the cost of performances.
+    pub fn set_velocity_iterations(&mut self, niter: usize) {
+        self.params.max_velocity_iterations = niter
+    }
+
+    /// The maximum number of iterations performed by the position-based constraints solver.
+    pub fn position_iterations(&self) -> usize {
+        self.params.max_position_iterations
+    }
+
+    /// Sets the maximum number of iterations performed by the position-based constraints solver.
+    ///
+    /// More iterations reduce the penetrations and joint drift at the cost of performances.
+    pub fn set_position_iterations(&mut self, niter: usize) {
+        self.params.max_position_iterations = niter
+    }
+
+    /// The relative normal velocity below which contacts are resolved without restitution.
+    pub fn restitution_threshold(&self) -> N {
+        self.params.restitution_velocity_threshold
+    }
+
+    /// Sets the relative normal velocity below which contacts are resolved without restitution.
+    ///
+    /// This prevents bodies resting on each other from bouncing indefinitely with tiny amplitudes.
+    pub fn set_restitution_threshold(&mut self, velocity: N) {
+        self.params.restitution_velocity_threshold = velocity
+    }
+
+    /// The Baumgarte factor used to feed the contact penetration back into the velocity solver.
+    pub fn position_bias(&self) -> N {
+        self.params.baumgarte_factor
+    }
+
+    /// Sets the Baumgarte factor used to feed the contact penetration back into the velocity solver.
+    ///
+    /// Values between `0.1` and `0.3` are usually stable. Setting it to zero (the default) disables this
+    /// bias so that penetrations are only corrected by the non-linear position solver, whose number of
+    /// iterations is set by `self.set_position_iterations(...)`. See also `self.enable_split_impulse(...)`.
+    pub fn set_position_bias(&mut self, beta: N) {
+        self.params.baumgarte_factor = beta
+    }
+
+    /// Whether contact penetrations are corrected by a separate pseudo-velocity pass.
+    pub fn split_impulse_enabled(&self) -> bool {
+        self.params.split_impulse
+    }
+
+    /// Enables or disables the correction of contact penetrations by a separate pseudo-velocity pass.
+    ///
+    /// When enabled, penetrating bodies are moved apart by pseudo-velocities that are discarded after
+    /// each step, so that they separate without gaining kinetic energy. This replaces the Baumgarte
+    /// bias set by `self.set_position_bias(...)` and the correction of contacts by the non-linear
+    /// position solver. This is disabled by default.
+    pub fn enable_split_impulse(&mut self, enabled: bool) {
+        self.params.split_impulse = enabled
+    }
+
+    /// The penetration depth below which contacts are not corrected.
+    pub fn penetration_slop(&self) -> N {
+        self.params.allowed_linear_error
+    }
+
+    /// Sets the penetration depth below which contacts are not corrected.
+    ///
+    /// A small positive value keeps resting contacts from jittering.
+    pub fn set_penetration_slop(&mut self, slop: N) {
+        self.params.allowed_linear_error = slop
+    }
+
+    /// The maximum number of timesteps executed by a single call to `self.step_with_accumulator(...)`.
+    pub fn max_steps_per_frame(&self) -> usize {
+        self.max_steps_per_frame
+    }
+
+    /// Sets the maximum number of timesteps executed by a single call to `self.step_with_accumulator(...)`.
+    ///
+    /// This prevents the simulation from falling further and further behind when
+    /// a single timestep takes longer to compute than the real time it simulates.
+    pub fn set_max_steps_per_frame(&mut self, max_steps: usize) {
+        self.max_steps_per_frame = max_steps
+    }
+
+    /// The number of substeps each call to `self.step()` is divided into.
+    pub fn substeps(&self) -> usize {
+        self.substeps
+    }
+
+    /// Sets the number of substeps each call to `self.step()` is divided into.
+    ///
+    /// Each substep performs collision detection and solves the constraints with a timestep of
+    /// `dt / nsubsteps`. This stabilizes tall stacks and stiff joints far better than adding solver
+    /// iterations, at the cost of performances. The forces applied to the bodies before the step
+    /// remain applied during all its substeps.
+    pub fn set_substeps(&mut self, nsubsteps: usize) {
+        assert!(nsubsteps > 0, "The number of substeps must be at least 1.");
+        self.substeps = nsubsteps
+    }
+
+    /// Whether the state of every body is checked to be finite after each timestep.
+    pub fn check_invariants(&self) -> bool {
+        self.check_invariants
+    }
+
+    /// Enables or disables the verification that the state of every body is finite after each timestep.
+    ///
+    /// When enabled, `self.step()` panics with the handle of the first body with a non-finite
+    /// position or velocity right after the integration. This helps finding the timestep at which
+    /// a simulation becomes unstable. The check is a linear pass over the bodies, and does nothing
+    /// when disabled (the default).
+    pub fn set_check_invariants(&mut self, check: bool) {
+        self.check_invariants = check
+    }
+
+    /// Accumulates `frame_time` and executes as many timesteps of fixed length `self.timestep()` as needed
+    /// to catch up with it.
+    ///
+    /// The remaining time is carried forward to the next call. At most `self.max_steps_per_frame()`
+    /// timesteps are executed, the time not simulated because of this limit being discarded.
+    /// Returns the number of timesteps actually executed.
+    pub fn step_with_accumulator(&mut self, frame_time: N) -> usize {
+        assert!(self.params.dt > N::zero(), "The timestep must be strictly positive.");
+        self.accumulator += frame_time;
+
+        let mut nsteps = 0;
+        while self.accumulator >= self.params.dt && nsteps < self.max_steps_per_frame {
+            self.step();
+            self.accumulator -= self.params.dt;
+            nsteps += 1;
+        }
+
+        if self.accumulator >= self.params.dt {
+            // We hit the maximum number of steps: drop the time we could not simulate.
+            self.accumulator = N::zero();
+        }
+
+        nsteps
+    }
+
+    /// Captures the current state of all the rigid bodies of this world.
+    ///
+    /// This includes the position, velocity, mass properties, status, speed limits, and sleeping state
+    /// of each rigid body, as well as the current simulation time. The contact manifolds, the impulses
+    /// cached by the contact model for warm-starting, the joints, and the other kinds of bodies are
+    /// not captured.
+    pub fn snapshot(&self) -> WorldSnapshot<N> {
+        let rigid_bodies = self.bodies.bodies()
+            .filter_map(|b| b.downcast_ref::<RigidBody<N>>())
+            .map(|rb| (rb.handle(), rb.state()))
+            .collect();
+
+        WorldSnapshot {
+            version: WorldSnapshot::<N>::VERSION,
+            t: self.params.t,
+            accumulator: self.accumulator,
+            rigid_bodies,
+        }
+    }
+
+    /// Restores the state of the rigid bodies of this world from the given snapshot.
+    ///
+    /// Rigid bodies removed since the snapshot was taken are ignored and rigid bodies added since are
+    /// left unchanged. The contact manifolds and the impulses cached for warm-starting are the ones
+    /// of the current state of the world, so the timesteps following a restoration are not bit-identical
+    /// to the ones that followed the snapshot whenever contacts or joints are involved.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot<N>) {
+        for (handle, state) in &snapshot.rigid_bodies {
+            let position = {
+                let rb = try_continue!(self.bodies.body_mut(*handle).and_then(|b| b.downcast_mut::<RigidBody<N>>()));
+                rb.restore_state(state);
+                *rb.position()
+            };
+
+            self.move_rigid_body_colliders(*handle, &position);
+        }
+
+        self.params.t = snapshot.t;
+        self.accumulator = snapshot.accumulator;
+        self.cworld.perform_broad_phase();
+    }
+
+    /// The sum of the kinetic energies of all the rigid bodies of this world.
+    pub fn kinetic_energy(&self) -> N {
+        self.bodies.bodies()
+            .filter_map(|b| b.downcast_ref::<RigidBody<N>>())
+            .fold(N::zero(), |acc, rb| acc + rb.kinetic_energy())
+    }
+
+    /// The sum of the gravitational potential energies of all the rigid bodies of this world.
+    ///
+    /// Heights are measured along the direction opposite to the gravity, relative to `reference_height`.
+    pub fn potential_energy(&self, reference_height: N) -> N {
+        self.bodies.bodies()
+            .filter_map(|b| b.downcast_ref::<RigidBody<N>>())
+            .fold(N::zero(), |acc, rb| acc + rb.potential_energy(&self.gravity, reference_height))
+    }
+
     /// Activate the given body.
     pub fn activate_body(&mut self, handle: BodyHandle) {
         Self::activate_body_at(&mut self.bodies, handle)
@@ -161,9 +552,40 @@ impl<N: Real> World<N> {
         &mut *self.constraints[handle]
     }
 
+    /// The active joint constraints of this world, with their world-space anchors.
+    ///
+    /// They are yielded by increasing handle so the iteration order stays the same from one timestep to
+    /// the next. Joints attached to a body part that does not exist are skipped.
+    pub fn joints(&self) -> impl Iterator<Item = JointInfo<N>> + '_ {
+        let bodies = &self.bodies;
+
+        self.constraints
+            .iter()
+            .filter(move |(_, j)| j.is_active(bodies))
+            .filter_map(move |(handle, j)| {
+                let (b1, b2) = j.anchors();
+                let (anchor1, anchor2) = j.local_anchors();
+                let body1 = bodies.body(b1.0)?;
+                let body2 = bodies.body(b2.0)?;
+                let pos1 = body1.position_at_material_point(body1.part(b1.1)?, &anchor1);
+                let pos2 = body2.position_at_material_point(body2.part(b2.1)?, &anchor2);
+
+                Some(JointInfo {
+                    handle,
+                    body_part1: b1,
+                    body_part2: b2,
+                    anchor1: Point::from(pos1.translation.vector),
+                    anchor2: Point::from(pos2.translation.vector),
+                    coordinate: j.coordinate(bodies),
+                    motor_impulse: j.motor_impulse(),
+                })
+            })
+    }
+
     /// Remove the specified constraint from the world.
     pub fn remove_constraint(&mut self, handle: ConstraintHandle) -> Box<JointConstraint<N>> {
         let constraint = self.constraints.remove(handle);
+        let _ = self.joint_breaking_thresholds.remove(&handle);
         let (anchor1, anchor2) = constraint.anchors();
         self.activate_body(anchor1.0);
         self.activate_body(anchor2.0);
@@ -171,6 +593,56 @@ impl<N: Real> World<N> {
         constraint
     }
 
+    /// The maximum force the specified joint can apply before breaking, if any.
+    pub fn joint_breaking_force(&self, handle: ConstraintHandle) -> Option<N> {
+        self.joint_breaking_thresholds.get(&handle).and_then(|t| t.0)
+    }
+
+    /// The maximum torque the specified joint can apply before breaking, if any.
+    pub fn joint_breaking_torque(&self, handle: ConstraintHandle) -> Option<N> {
+        self.joint_breaking_thresholds.get(&handle).and_then(|t| t.1)
+    }
+
+    /// Sets the maximum force the specified joint can apply before breaking.
+    ///
+    /// The force is deduced from the linear impulse applied by the joint once the velocity solver
+    /// converged (see `JointConstraint::applied_impulses`), so transient impulses of the first solver
+    /// iterations do not break the joint. A broken joint is removed from the world at the end of the
+    /// velocity resolution, both its bodies are woken up, and the joint break handler is notified.
+    /// Set to `None` to make the joint unbreakable by force.
+    pub fn set_joint_breaking_force(&mut self, handle: ConstraintHandle, max_force: Option<N>) {
+        let max_torque = self.joint_breaking_torque(handle);
+        self.set_joint_breaking_thresholds(handle, max_force, max_torque)
+    }
+
+    /// Sets the maximum torque the specified joint can apply before breaking.
+    ///
+    /// See `self.set_joint_breaking_force(...)` for details.
+    pub fn set_joint_breaking_torque(&mut self, handle: ConstraintHandle, max_torque: Option<N>) {
+        let max_force = self.joint_breaking_force(handle);
+        self.set_joint_breaking_thresholds(handle, max_force, max_torque)
+    }
+
+    fn set_joint_breaking_thresholds(&mut self, handle: ConstraintHandle, max_force: Option<N>, max_torque: Option<N>) {
+        if max_force.is_none() && max_torque.is_none() {
+            let _ = self.joint_breaking_thresholds.remove(&handle);
+        } else if self.constraints.contains(handle) {
+            let _ = self.joint_breaking_thresholds.insert(handle, (max_force, max_torque));
+        }
+    }
+
+    /// Set the listener notified whenever a joint breaks.
+    ///
+    /// This replaces the previous joint break handler, if any.
+    pub fn set_joint_break_handler(&mut self, handler: Box<JointBreakHandler<N>>) {
+        self.joint_break_handler = Some(handler);
+    }
+
+    /// Remove the joint break handler of this world, if any.
+    pub fn remove_joint_break_handler(&mut self) -> Option<Box<JointBreakHandler<N>>> {
+        self.joint_break_handler.take()
+    }
+
     /// Remove the specified collider from the world.
     pub fn remove_colliders(&mut self, handles: &[ColliderHandle]) {
         self.cworld.remove(handles);
@@ -202,8 +674,44 @@ impl<N: Real> World<N> {
         self.forces.remove(handle)
     }
 
+    /// Add a region of fluid applying buoyancy and drag forces to the bodies inside of it.
+    pub fn add_fluid_volume(&mut self, fluid: FluidVolume<N>) -> FluidVolumeHandle {
+        self.fluid_volumes.insert(fluid)
+    }
+
+    /// Retrieve a reference to the specified fluid volume.
+    pub fn fluid_volume(&self, handle: FluidVolumeHandle) -> Option<&FluidVolume<N>> {
+        self.fluid_volumes.get(handle)
+    }
+
+    /// Retrieve a mutable reference to the specified fluid volume.
+    pub fn fluid_volume_mut(&mut self, handle: FluidVolumeHandle) -> Option<&mut FluidVolume<N>> {
+        self.fluid_volumes.get_mut(handle)
+    }
+
+    /// Remove the specified fluid volume from the world.
+    pub fn remove_fluid_volume(&mut self, handle: FluidVolumeHandle) -> Option<FluidVolume<N>> {
+        if self.fluid_volumes.contains(handle) {
+            Some(self.fluid_volumes.remove(handle))
+        } else {
+            None
+        }
+    }
+
     /// Set the gravity.
+    ///
+    /// If it differs from the current gravity, all the sleeping dynamic bodies are woken up so they
+    /// respond to the change. The gravity applied to each body is multiplied by its own gravity scale.
+    /// A zero gravity is supported.
     pub fn set_gravity(&mut self, gravity: Vector<N>) {
+        if gravity != self.gravity {
+            for body in self.bodies.bodies_mut() {
+                if body.is_dynamic() {
+                    body.activate();
+                }
+            }
+        }
+
         self.gravity = gravity
     }
 
@@ -212,10 +720,85 @@ impl<N: Real> World<N> {
         &self.gravity
     }
 
+    /// Whether the next timesteps can be skipped because nothing moves in this world.
+    ///
+    /// This is the case when every dynamic body is asleep, every kinematic body is asleep with a zero
+    /// velocity, and there is no force generator or fluid volume that could set bodies in motion.
+    /// Waking a body up, e.g., by applying an impulse or adding a new body, ends this state.
+    pub fn is_quiescent(&self) -> bool {
+        self.forces.is_empty() && self.fluid_volumes.is_empty() && self.bodies.bodies().all(|b| match b.status() {
+            BodyStatus::Dynamic => !b.is_active(),
+            BodyStatus::Kinematic => {
+                !b.activation_status().is_active() && b.generalized_velocity().norm_squared().is_zero()
+            }
+            BodyStatus::Static | BodyStatus::Disabled => true,
+        })
+    }
+
     /// Execute one time step of the physics simulation.
+    ///
+    /// If this world is quiescent, the solver is skipped entirely and only the colliders of the
+    /// bodies moved manually are updated.
     pub fn step(&mut self) {
         self.counters.step_started();
 
+        if self.is_quiescent() {
+            self.step_quiescent();
+            return;
+        }
+
+        for i in 0..self.substeps {
+            self.substep(i == 0, i + 1 == self.substeps);
+        }
+
+        self.update_penetration_counters();
+        self.counters.step_completed();
+    }
+
+    // Measures the penetration depths of the contacts persisting at the end of the timestep.
+    //
+    // As for the solver, the depth of a contact accounts for the margins of its colliders.
+    fn update_penetration_counters(&mut self) {
+        if !self.counters.enabled() {
+            return;
+        }
+
+        let mut max = N::zero();
+        let mut sum = N::zero();
+        let mut npenetrations = 0;
+
+        for (c1, c2, _, manifold) in self.cworld.contact_pairs(true) {
+            for c in manifold.contacts() {
+                let depth = c.contact.depth + c1.margin() + c2.margin();
+
+                if depth > N::zero() {
+                    max = max.max(depth);
+                    sum += depth;
+                    npenetrations += 1;
+                }
+            }
+        }
+
+        let avg = if npenetrations != 0 {
+            sum / na::convert(npenetrations as f64)
+        } else {
+            N::zero()
+        };
+
+        self.counters.set_penetrations(
+            na::try_convert(max).unwrap_or(0.0),
+            na::try_convert(avg).unwrap_or(0.0),
+        );
+    }
+
+    // Performs one of the `self.substeps` substeps of a timestep, each lasting `dt / self.substeps`.
+    //
+    // The force generators are applied at the first substep and the resulting forces are kept until
+    // the last one, so that their effect over the whole timestep does not depend on the number of substeps.
+    fn substep(&mut self, first: bool, last: bool) {
+        let full_dt = self.params.dt;
+        let substep_dt = full_dt / na::convert(self.substeps as f64);
+
         /*
          *
          * Update body dynamics and accelerations.
@@ -223,14 +806,22 @@ impl<N: Real> World<N> {
          */
         for b in self.bodies.bodies_mut() {
             b.update_kinematics();
-            b.update_dynamics(self.params.dt);
+            b.update_dynamics(substep_dt);
         }
 
-        let params = &self.params;
-        let bodies = &mut self.bodies;
-        self.forces.retain(|_, f| {
-            f.apply(params, bodies)
-        });
+        if first {
+            let params = &self.params;
+            let bodies = &mut self.bodies;
+            self.forces.retain(|_, f| {
+                f.apply(params, bodies)
+            });
+
+            for (_, fluid) in self.fluid_volumes.iter() {
+                fluid.apply(&self.gravity, &mut self.bodies, &self.cworld);
+            }
+        }
+
+        self.params.dt = substep_dt;
 
         for b in self.bodies.bodies_mut() {
             b.update_acceleration(&self.gravity, &self.params);
@@ -241,21 +832,25 @@ impl<N: Real> World<N> {
          * Sync colliders and perform CD if the user moved
          * manually some bodies.
          */
-        self.cworld.clear_events();
+        if first {
+            self.cworld.clear_events();
+        }
+
+        self.sync_disabled_bodies();
+        self.cworld.sync_margins(&self.bodies);
         self.cworld.sync_colliders(&self.bodies);
         self.cworld.perform_broad_phase();
         self.cworld.perform_narrow_phase();
 
         /*
          *
-         * Handle sleeping and collision
-         * islands.
+         * Handle sleeping.
          *
          */
-        // FIXME: for now, no island is built.
         self.counters.island_construction_started();
         self.active_bodies.clear();
-        self.activation_manager.update(
+        self.activation_manager.update_with_params(
+            &self.params,
             &mut self.bodies,
             &self.cworld,
             &self.constraints,
@@ -268,7 +863,8 @@ impl<N: Real> World<N> {
          * Collect contact manifolds.
          *
          */
-        let mut contact_manifolds = Vec::new(); // FIXME: avoid allocations.
+        self.contact_manifolds.clear();
+
         for (c1, c2, _, manifold) in self.cworld.contact_pairs(false) {
             let b1 = try_continue!(self.bodies.body(c1.body()));
             let b2 = try_continue!(self.bodies.body(c2.body()));
@@ -278,10 +874,42 @@ impl<N: Real> World<N> {
                 && ((b1.status_dependent_ndofs() != 0 && b1.is_active())
                 || (b2.status_dependent_ndofs() != 0 && b2.is_active()))
                 {
-                    contact_manifolds.push(ColliderContactManifold::new(c1, c2, manifold));
+                    let manifold = ColliderContactManifold::new(c1, c2, manifold);
+
+                    if !self.is_one_way_contact_ignored(&manifold) {
+                        self.contact_manifolds.push(ColliderContactManifoldHandles::new(&manifold));
+                    }
                 }
         }
 
+        // The collision world yields the contact pairs in an arbitrary order, which changes from one
+        // run to the other. Since the solver results depend on the order of the constraints, sort them
+        // so that identical inputs give bit-identical results, e.g., for lockstep networking and replays.
+        self.contact_manifolds.sort_unstable_by_key(|m| {
+            (m.body1.uid(), m.body2.uid(), m.collider1.uid(), m.collider2.uid())
+        });
+
+        /*
+         *
+         * Build the islands solved independently.
+         *
+         */
+        self.counters.island_construction_resumed();
+        self.islands.build(&self.bodies, &self.constraints, &self.contact_manifolds, &self.active_bodies);
+        self.islands.sort_manifolds(&mut self.contact_manifolds);
+        self.counters.island_construction_completed();
+
+        if self.counters.enabled() {
+            let bodies = &self.bodies;
+            self.counters.set_nactive_bodies(self.active_bodies.len());
+            self.counters.set_njoints(self.constraints.iter().filter(|c| c.1.is_active(bodies)).count());
+            let manifolds = ColliderContactManifolds::new(&self.cworld, &self.contact_manifolds);
+            self.counters.set_ncontact_pairs(self.contact_manifolds.len());
+            self.counters.set_ncontacts(manifolds.iter().map(|m| m.len()).sum());
+            self.counters.set_ninteraction_pairs(self.cworld.interaction_pairs(false).count());
+            self.counters.set_niterations(self.params.max_velocity_iterations, self.params.max_position_iterations);
+        }
+
         /*
          *
          * Solve the system and integrate.
@@ -295,23 +923,39 @@ impl<N: Real> World<N> {
             b.set_companion_id(0);
         }
 
+        self.ccd_positions.clear();
+        for b in self.bodies.bodies() {
+            if let Some(rb) = b.downcast_ref::<RigidBody<N>>() {
+                if rb.ccd_enabled() && rb.is_dynamic() && rb.is_active() {
+                    self.ccd_positions.push((rb.handle(), *rb.position()));
+                }
+            }
+        }
+
         self.solver.step(
             &mut self.counters,
             &mut self.bodies,
             &mut self.constraints,
-            &contact_manifolds[..],
-            &self.active_bodies[..],
+            &self.contact_manifolds[..],
+            self.islands.islands(),
             &self.params,
             &self.material_coefficients,
             &self.cworld,
         );
 
+        self.break_joints();
+
         for b in self.bodies.bodies_mut() {
             if b.status() == BodyStatus::Kinematic {
                 b.integrate(&self.params)
             }
         }
 
+        self.solve_ccd();
+
+        if self.check_invariants {
+            self.assert_bodies_are_finite();
+        }
 
         /*
          *
@@ -321,9 +965,10 @@ impl<N: Real> World<N> {
          */
         // FIXME: objects involved in a non-linear position stabilization already
         // updated their kinematics.
+        let dt = self.params.dt;
         self.bodies.bodies_mut().for_each(|b| {
             b.update_kinematics();
-            b.update_dynamics(params.dt);
+            b.update_dynamics(dt);
         });
 
         /*
@@ -343,25 +988,398 @@ impl<N: Real> World<N> {
          * Finally, clear the update flag of every body.
          *
          */
+        self.bodies.bodies_mut().for_each(|b| {
+            if last {
+                b.clear_forces();
+            }
+
+            b.clear_update_flags();
+        });
+
+        // The events of all the substeps are reported at once.
+        if last {
+            self.notify_contact_handler();
+            self.notify_activation_handler();
+        }
+
+        self.params.t += self.params.dt;
+        self.params.dt = full_dt;
+    }
+
+    // The cheap timestep performed when nothing moves: only the colliders of bodies moved or
+    // disabled manually since the last timestep need to be updated.
+    fn step_quiescent(&mut self) {
+        self.cworld.clear_events();
+        self.sync_disabled_bodies();
+        self.cworld.sync_margins(&self.bodies);
+        self.cworld.sync_colliders(&self.bodies);
+        self.counters.collision_detection_started();
+        self.cworld.perform_broad_phase();
+        self.cworld.perform_narrow_phase();
+        self.counters.collision_detection_completed();
+
         self.bodies.bodies_mut().for_each(|b| {
             b.clear_forces();
             b.clear_update_flags();
         });
 
+        self.notify_contact_handler();
+
         self.params.t += self.params.dt;
+        self.update_penetration_counters();
         self.counters.step_completed();
     }
 
+    // Whether the contacts of this manifold must be ignored because one of its colliders is one-way
+    // and the other one approaches it from its non-solid side.
+    fn is_one_way_contact_ignored(&self, manifold: &ColliderContactManifold<N>) -> bool {
+        let c = try_ret!(manifold.deepest_contact(), false);
+        let feature1 = c.kinematic.feature1();
+        let feature2 = c.kinematic.feature2();
+        let part1 = manifold.body_part1(feature1);
+        let part2 = manifold.body_part2(feature2);
+        let normal = c.contact.normal.into_inner();
+
+        self.is_ignored_by_one_way_collider(manifold.collider1, feature1, part1, part2, normal)
+            || self.is_ignored_by_one_way_collider(manifold.collider2, feature2, part2, part1, -normal)
+    }
+
+    // `dir` is the contact normal pointing from `collider` toward the other collider.
+    fn is_ignored_by_one_way_collider(
+        &self,
+        collider: &Collider<N>,
+        feature: FeatureId,
+        part: BodyPartHandle,
+        other_part: BodyPartHandle,
+        dir: Vector<N>,
+    ) -> bool {
+        let normals = try_ret!(collider.one_way_normals(), false);
+        let i = if normals.len() == 1 { 0 } else { collider.shape().subshape_containing_feature(feature) };
+        let normal = collider.position() * try_ret!(normals.get(i), false).into_inner();
+
+        if dir.dot(&normal) <= N::zero() {
+            return true;
+        }
+
+        let part_velocity = |handle: BodyPartHandle| {
+            self.bodies.body(handle.0)
+                .and_then(|b| b.part(handle.1).map(|p| b.status_dependent_body_part_velocity(p).linear))
+                .unwrap_or_else(Vector::zeros)
+        };
+
+        (part_velocity(other_part) - part_velocity(part)).dot(&normal) > N::zero()
+    }
+
+    // Panics if the position or velocity of any body is NaN or infinite.
+    fn assert_bodies_are_finite(&self) {
+        fn is_finite<N: Real>(x: N) -> bool {
+            x.abs() <= N::max_value()
+        }
+
+        for body in self.bodies.bodies() {
+            let handle = body.handle();
+
+            if !body.generalized_velocity().iter().all(|v| is_finite(*v)) {
+                panic!("Invariant violated at t = {:?}: body {:?} has a non-finite velocity.", self.params.t, handle);
+            }
+
+            for i in 0..body.num_parts() {
+                let pos = try_continue!(body.part(i)).position();
+
+                if !pos.to_homogeneous().iter().all(|v| is_finite(*v)) {
+                    panic!("Invariant violated at t = {:?}: body {:?} has a non-finite position.", self.params.t, handle);
+                }
+            }
+
+            if let Some((_, positions)) = body.deformed_positions() {
+                if !positions.iter().all(|v| is_finite(*v)) {
+                    panic!("Invariant violated at t = {:?}: body {:?} has a non-finite deformation.", self.params.t, handle);
+                }
+            }
+        }
+    }
+
+    // Removes the colliders of disabled bodies from the collision pairs and wakes up the bodies they touched.
+    fn sync_disabled_bodies(&mut self) {
+        let mut touched_bodies = Vec::new();
+        self.cworld.sync_disabled_colliders(&self.bodies, &mut touched_bodies);
+
+        for handle in touched_bodies {
+            self.activate_body(handle);
+        }
+    }
+
+    // Removes the joints that applied a force or torque larger than their breaking thresholds.
+    fn break_joints(&mut self) {
+        if self.joint_breaking_thresholds.is_empty() {
+            return;
+        }
+
+        let dt = self.params.dt;
+        let mut broken = Vec::new();
+
+        for (handle, (max_force, max_torque)) in &self.joint_breaking_thresholds {
+            let joint = try_continue!(self.constraints.get(*handle));
+
+            if !joint.is_active(&self.bodies) {
+                continue;
+            }
+
+            let (linear, angular) = joint.applied_impulses();
+            let force = linear / dt;
+            let torque = angular / dt;
+
+            if max_force.map(|max| force > max).unwrap_or(false) || max_torque.map(|max| torque > max).unwrap_or(false) {
+                broken.push((*handle, force, torque));
+            }
+        }
+
+        // The iteration order of the hash map is not deterministic.
+        broken.sort_by_key(|b| b.0);
+
+        for (handle, force, torque) in broken {
+            let joint = self.remove_constraint(handle);
+
+            if let Some(handler) = &mut self.joint_break_handler {
+                handler.handle_joint_broken(handle, joint, force, torque);
+            }
+        }
+    }
+
+    // Prevents fast rigid bodies with CCD enabled from tunneling through static colliders.
+    //
+    // This must be called after the integration, but before the colliders are synchronized
+    // with their bodies so that their positions still match the start of the timestep.
+    fn solve_ccd(&mut self) {
+        for (handle, prev_pos) in &self.ccd_positions {
+            let curr_pos = *try_continue!(self.bodies.body(*handle)
+                .and_then(|b| b.downcast_ref::<RigidBody<N>>()))
+                .position();
+            let disp = curr_pos.translation.vector - prev_pos.translation.vector;
+            let disp_norm = disp.norm();
+            let mut min_toi = N::one();
+
+            for c1 in self.cworld.body_colliders(*handle) {
+                if c1.is_sensor() {
+                    continue;
+                }
+
+                // Only sweep if the displacement is large enough for the collider to tunnel.
+                let aabb1 = c1.shape().aabb(c1.position());
+                let min_half_extent = aabb1.half_extents().iter().fold(N::max_value(), |a, b| a.min(*b));
+
+                if disp_norm <= min_half_extent {
+                    continue;
+                }
+
+                let mut swept_aabb = AABB::new(aabb1.mins() + disp, aabb1.maxs() + disp);
+                swept_aabb.merge(&aabb1);
+
+                for c2 in self.cworld.interferences_with_aabb(&swept_aabb, c1.collision_groups()) {
+                    if c2.is_sensor() || c2.body() == *handle || !c1.collision_groups().can_interact_with_groups(c2.collision_groups()) {
+                        continue;
+                    }
+
+                    let is_static = c2.body().is_ground() || self.bodies.body(c2.body()).map(|b| b.is_static()).unwrap_or(false);
+                    if !is_static {
+                        continue;
+                    }
+
+                    let toi = query::time_of_impact(
+                        c1.position(),
+                        &disp,
+                        &**c1.shape(),
+                        c2.position(),
+                        &Vector::zeros(),
+                        &**c2.shape(),
+                    );
+
+                    // A zero time of impact means the colliders are already touching and
+                    // will be handled by the regular contact resolution.
+                    if let Some(toi) = toi {
+                        if toi > N::zero() && toi < min_toi {
+                            min_toi = toi;
+                        }
+                    }
+                }
+            }
+
+            if min_toi < N::one() {
+                let rb = try_continue!(self.bodies.body_mut(*handle)
+                    .and_then(|b| b.downcast_mut::<RigidBody<N>>()));
+                let mut new_pos = curr_pos;
+                new_pos.translation.vector = prev_pos.translation.vector + disp * min_toi;
+                rb.set_position(new_pos);
+            }
+        }
+    }
+
+    fn notify_activation_handler(&mut self) {
+        let mut transitions = mem::replace(&mut self.activation_transitions, Vec::new());
+
+        for body in self.bodies.bodies() {
+            if !body.is_dynamic() {
+                continue;
+            }
+
+            let handle = body.handle();
+
+            if body.is_active() {
+                if self.sleeping_bodies.remove(&handle) {
+                    transitions.push((handle, true));
+                }
+            } else if self.sleeping_bodies.insert(handle) {
+                transitions.push((handle, false));
+            }
+        }
+
+        if let Some(mut handler) = self.activation_handler.take() {
+            for (handle, woke_up) in &transitions {
+                if *woke_up {
+                    handler.handle_wake(self, *handle);
+                } else {
+                    handler.handle_sleep(self, *handle);
+                }
+            }
+
+            self.activation_handler = Some(handler);
+        }
+
+        transitions.clear();
+        self.activation_transitions = transitions;
+    }
+
+    fn notify_contact_handler(&mut self) {
+        let handler = try_ret!(self.contact_handler.as_mut());
+
+        for event in self.cworld.contact_events() {
+            let (handle1, handle2, started) = match *event {
+                ContactEvent::Started(h1, h2) => (h1, h2, true),
+                ContactEvent::Stopped(h1, h2) => (h1, h2, false),
+            };
+
+            let collider1 = try_continue!(self.cworld.collider(handle1));
+            let collider2 = try_continue!(self.cworld.collider(handle2));
+            let contacts = self.cworld
+                .contact_pair(handle1, handle2, false)
+                .map(|(_, _, _, manifold)| manifold.contacts().map(|c| c.contact.clone()).collect())
+                .unwrap_or_else(Vec::new);
+
+            let pair = ContactPair {
+                collider1: handle1,
+                collider2: handle2,
+                body1: collider1.body(),
+                body2: collider2.body(),
+                contacts,
+            };
+
+            if started {
+                self.pending_impacts.push((handle1, handle2));
+                handler.handle_contact_started(&pair);
+            } else {
+                self.pending_impacts.retain(|p| *p != (handle1, handle2) && *p != (handle2, handle1));
+                handler.handle_contact_stopped(&pair);
+            }
+        }
+
+        // Only the first impulse after the colliders started touching is reported, so that resting
+        // contacts do not trigger an impact at each timestep.
+        let cworld = &self.cworld;
+        let contact_model = self.solver.contact_model();
+
+        self.pending_impacts.retain(|&(handle1, handle2)| {
+            let collider1 = try_ret!(cworld.collider(handle1), false);
+            let collider2 = try_ret!(cworld.collider(handle2), false);
+            let normal_impulse = Self::pair_normal_impulse(cworld, contact_model, handle1, handle2);
+
+            if normal_impulse.is_zero() {
+                return true;
+            }
+
+            handler.handle_contact_impact(&ContactImpact {
+                collider1: handle1,
+                collider2: handle2,
+                body1: collider1.body(),
+                body2: collider2.body(),
+                normal_impulse,
+            });
+
+            false
+        });
+
+        for event in self.cworld.proximity_events() {
+            let started = event.new_status == Proximity::Intersecting;
+            let stopped = event.prev_status == Proximity::Intersecting;
+
+            if started == stopped {
+                continue;
+            }
+
+            let collider1 = try_continue!(self.cworld.collider(event.collider1));
+            let collider2 = try_continue!(self.cworld.collider(event.collider2));
+            let pair = ProximityPair {
+                collider1: event.collider1,
+                collider2: event.collider2,
+                body1: collider1.body(),
+                body2: collider2.body(),
+            };
+
+            if started {
+                handler.handle_proximity_started(&pair);
+            } else {
+                handler.handle_proximity_stopped(&pair);
+            }
+        }
+    }
+
+    /// Remove the specified body.
+    ///
+    /// Its colliders, contacts, and the joint constraints attached to it are removed as well,
+    /// and the bodies that were touching it or attached to it are woken up.
+    /// The handle of the removed body is never reused by this world.
+    pub fn remove_body(&mut self, handle: BodyHandle) {
+        self.remove_bodies(&[handle])
+    }
+
     /// Remove the specified bodies.
     pub fn remove_bodies(&mut self, handles: &[BodyHandle]) {
         for handle in handles {
             self.bodies.remove_body(*handle);
             self.cworld.remove_body(*handle);
+            let _ = self.sleeping_bodies.remove(handle);
         }
 
         self.cleanup_after_body_removal();
     }
 
+    /// Removes all the bodies, colliders, joints, force generators and fluid volumes of this world.
+    ///
+    /// The handles are allocated from scratch again, so a scene built after this call gets the same
+    /// handles as it would on a new world. Handles obtained before the call must not be used anymore.
+    /// The integration parameters, gravity, materials, broad phase pair filters and handlers are kept,
+    /// but the elapsed time is reset to zero. Since `World::step` borrows the world mutably, a world
+    /// can never be cleared in the middle of a timestep.
+    pub fn clear(&mut self) {
+        self.bodies = BodySet::new();
+        self.active_bodies.clear();
+        self.islands = IslandSet::new();
+        self.contact_manifolds.clear();
+        self.cworld.clear();
+        self.solver.clear_impulses();
+        let sleep_threshold = self.activation_manager.sleep_threshold().cloned();
+        self.activation_manager = ActivationManager::new(self.activation_manager.mix_factor());
+        self.activation_manager.set_sleep_threshold(sleep_threshold);
+        self.constraints.clear();
+        self.joint_breaking_thresholds.clear();
+        self.forces.clear();
+        self.fluid_volumes.clear();
+        self.pending_impacts.clear();
+        self.sleeping_bodies.clear();
+        self.ccd_positions.clear();
+        self.accumulator = N::zero();
+        self.params.t = N::zero();
+    }
+
     fn cleanup_after_body_removal(&mut self) {
         self.activate_bodies_touching_deleted_bodies();
         self.cleanup_constraints_with_deleted_anchors();
@@ -401,7 +1419,10 @@ impl<N: Real> World<N> {
             }
 
             b1_exists && b2_exists
-        })
+        });
+
+        let constraints = &self.constraints;
+        self.joint_breaking_thresholds.retain(|handle, _| constraints.contains(*handle));
     }
 
     /// Adds a body to the world.
@@ -447,6 +1468,66 @@ impl<N: Real> World<N> {
         self.bodies.body_mut(handle)?.downcast_mut::<RigidBody<N>>()
     }
 
+    /// Teleports the specified rigid body to the given position.
+    ///
+    /// Unlike `RigidBody::set_position`, this immediately moves the colliders attached to the rigid body
+    /// and updates their bounding volumes in the broad phase, so that geometric queries performed
+    /// before the next timestep take the new position into account. The impulses cached by the solver
+    /// for the contacts of those colliders are discarded since they do not match the new position. The
+    /// velocity of the rigid body is preserved and the rigid body is woken up.
+    ///
+    /// The contact pairs whose bounding volumes stop overlapping are removed right away: the
+    /// `ContactEvent::Stopped` events of those that had contacts are available from `self.contact_events()`
+    /// until the next timestep, but are not passed to the contact handlers. The other contacts are
+    /// updated at the next timestep, which reports the contacts started or stopped at the new position
+    /// as usual.
+    ///
+    /// Does nothing if the handle does not correspond to a rigid body in this world.
+    pub fn teleport_rigid_body(&mut self, handle: BodyHandle, position: Isometry<N>) {
+        if self.rigid_body(handle).is_none() {
+            return;
+        }
+
+        let mut contact_ids = Vec::new();
+        for collider in self.cworld.body_colliders(handle) {
+            let contacts = try_continue!(self.cworld.contacts_with(collider.handle(), false));
+            for (_, _, _, manifold) in contacts {
+                contact_ids.extend(manifold.contacts().map(|c| c.id));
+            }
+        }
+
+        for contact_id in contact_ids {
+            self.solver.forget_impulse(contact_id);
+        }
+
+        self.move_rigid_body(handle, position);
+    }
+
+    // Moves the given rigid body and its colliders right away, keeping their contacts.
+    fn move_rigid_body(&mut self, handle: BodyHandle, position: Isometry<N>) {
+        let rb = try_ret!(self.bodies.body_mut(handle).and_then(|b| b.downcast_mut::<RigidBody<N>>()));
+        rb.set_position(position);
+        rb.activate();
+
+        self.move_rigid_body_colliders(handle, &position);
+        self.cworld.perform_broad_phase();
+    }
+
+    // Moves the colliders attached to the given rigid body, ignoring its activation status.
+    fn move_rigid_body_colliders(&mut self, handle: BodyHandle, position: &Isometry<N>) {
+        let colliders: Vec<_> = self.cworld.body_colliders(handle).map(|co| {
+            let pos = match co.anchor() {
+                ColliderAnchor::OnBodyPart { position_wrt_body_part, .. } => position * position_wrt_body_part,
+                ColliderAnchor::OnDeformableBody { .. } => *co.position(),
+            };
+            (co.handle(), pos)
+        }).collect();
+
+        for (collider, pos) in colliders {
+            self.cworld.set_position(collider, pos);
+        }
+    }
+
     /// Reference to the underlying collision world.
     pub fn collider_world(&self) -> &ColliderWorld<N> {
         &self.cworld
@@ -496,9 +1577,14 @@ impl<N: Real> World<N> {
     }
 
     /// An iterator through all the bodies on this world.
+    ///
+    /// The iteration order only depends on the sequence of bodies added and removed, so it is
+    /// the same across runs performing the same operations. The ground is not yielded.
     pub fn bodies(&self) -> impl Iterator<Item = &Body<N>> { self.bodies.bodies() }
 
     /// A mutable iterator through all the bodies on this world.
+    ///
+    /// The iteration order is the same as for `self.bodies()`.
     pub fn bodies_mut(&mut self) -> impl Iterator<Item = &mut Body<N>> { self.bodies.bodies_mut() }
 
     /// An iterator through all the bodies with the given name.
@@ -511,11 +1597,409 @@ impl<N: Real> World<N> {
         self.bodies_mut().filter(move |b| b.name() == name)
     }
 
+    /// The handles of all the bodies having at least one collider intersecting the given AABB.
+    ///
+    /// This is a coarse query that only relies on the broad phase: the bounding boxes of the colliders
+    /// are tested instead of their shapes, so some of the returned bodies may not actually intersect the
+    /// AABB. Callers needing exact results should test the shapes of the colliders of each returned body.
+    /// Only colliders with collision groups compatible with `groups` are considered, as for the ray and
+    /// point queries of the `ColliderWorld`. The ground is never returned.
+    ///
+    /// The bounding boxes of the broad phase are only updated by `self.step()`, so colliders moved since
+    /// the last step are tested at their former position.
+    pub fn bodies_in_aabb(&self, aabb: &AABB<N>, groups: &CollisionGroups) -> Vec<BodyHandle> {
+        let mut seen = HashSet::new();
+        let mut res = Vec::new();
+
+        for co in self.cworld.interferences_with_aabb(aabb, groups) {
+            let body = co.body();
+
+            if !body.is_ground() && seen.insert(body) {
+                res.push(body);
+            }
+        }
+
+        res
+    }
+
+    /// The signed distance between two bodies, and the closest points of their colliders in world-space.
+    ///
+    /// The distance is the minimum over all the pairs of colliders of both bodies. If those bodies
+    /// penetrate, the distance is negative and its absolute value is the penetration depth, while the
+    /// returned points are the deepest points of each collider inside of the other one. Returns `None`
+    /// if one of the bodies does not exist or does not have any collider.
+    pub fn distance_between(&self, body1: BodyHandle, body2: BodyHandle) -> Option<(N, Point<N>, Point<N>)> {
+        let mut res: Option<(N, Point<N>, Point<N>)> = None;
+
+        for c1 in self.cworld.body_colliders(body1) {
+            for c2 in self.cworld.body_colliders(body2) {
+                let (m1, g1) = (c1.position(), &**c1.shape());
+                let (m2, g2) = (c2.position(), &**c2.shape());
+
+                let candidate = match query::closest_points(m1, g1, m2, g2, N::max_value()) {
+                    ClosestPoints::WithinMargin(p1, p2) => (na::distance(&p1, &p2), p1, p2),
+                    ClosestPoints::Intersecting => {
+                        let contact = try_continue!(query::contact(m1, g1, m2, g2, N::zero()));
+                        (-contact.depth, contact.world1, contact.world2)
+                    }
+                    ClosestPoints::Disjoint => continue,
+                };
+
+                if res.map(|r| candidate.0 < r.0).unwrap_or(true) {
+                    res = Some(candidate);
+                }
+            }
+        }
+
+        res
+    }
+
+    /// The first collider hit by `shape` moving from `start` with the constant linear velocity `velocity`.
+    ///
+    /// Only the colliders hit before the time `max_toi` are considered, i.e., those touched after
+    /// a displacement smaller than `velocity * max_toi`. Sensors, colliders with collision groups
+    /// incompatible with `groups`, and colliders attached to the `excluded` body (e.g. the body of a
+    /// character casting its own shape) are ignored. The time of impact is zero if the shape already
+    /// penetrates a collider at its starting position, or touches it while moving toward it. The
+    /// colliders the shape only slides along, or moves away from, are not hit.
+    pub fn cast_shape(
+        &self,
+        shape: &Shape<N>,
+        start: &Isometry<N>,
+        velocity: &Vector<N>,
+        max_toi: N,
+        groups: &CollisionGroups,
+        excluded: Option<BodyHandle>,
+    ) -> Option<ShapeCastHit<N>> {
+        let moved = |toi: N| {
+            let translation = Translation::from(start.translation.vector + velocity * toi);
+            Isometry::from_parts(translation, start.rotation)
+        };
+
+        let mut swept_aabb = shape.aabb(start);
+        swept_aabb.merge(&shape.aabb(&moved(max_toi)));
+
+        let speed = velocity.norm();
+        let mut best: Option<ShapeCastHit<N>> = None;
+
+        for co in self.cworld.interferences_with_aabb(&swept_aabb, groups) {
+            if co.is_sensor() || Some(co.body()) == excluded {
+                continue;
+            }
+
+            let toi = try_continue!(query::time_of_impact(
+                start,
+                velocity,
+                shape,
+                co.position(),
+                &Vector::zeros(),
+                &**co.shape(),
+            ));
+
+            if toi > max_toi || best.map(|b| toi >= b.toi).unwrap_or(false) {
+                continue;
+            }
+
+            // The shapes exactly touch at `toi`, which is a degenerate configuration for the contact
+            // computation. So the contact is computed slightly before the impact instead.
+            let contact_toi = if speed.is_zero() {
+                toi
+            } else {
+                (toi - na::convert::<_, N>(1.0e-3) / speed).max(N::zero())
+            };
+            let contact = try_continue!(query::contact(
+                &moved(contact_toi),
+                shape,
+                co.position(),
+                &**co.shape(),
+                N::max_value(),
+            ));
+
+            // A shape moving along, or away from, a collider it touches does not hit it.
+            let approach_speed = velocity.dot(&contact.normal);
+
+            if contact.depth <= N::zero() && approach_speed <= speed * na::convert(1.0e-3) {
+                continue;
+            }
+
+            best = Some(ShapeCastHit {
+                toi,
+                body: co.body(),
+                collider: co.handle(),
+                point: contact.world2,
+                normal: -contact.normal,
+            });
+        }
+
+        best
+    }
+
+    /// Moves the given rigid body by `desired_delta` like a character, without going through obstacles.
+    ///
+    /// The first non-sensor collider of the body is swept along the desired translation. When it
+    /// hits an obstacle, the body stops just before it and the rest of the move slides along its
+    /// surface. Surfaces steeper than the maximum slope of `self.kinematic_move_parameters()` are
+    /// never climbed, and obstacles lower than the maximum step height are stepped over. Dynamic
+    /// bodies hit by the move are pushed by an impulse but, unlike the moved body, they are not
+    /// moved immediately. The rotation of the body is left unchanged.
+    ///
+    /// This is designed for kinematic bodies, which are not pushed back by the dynamic bodies they
+    /// hit. Returns a zero move if the handle does not correspond to a rigid body with a collider.
+    pub fn move_kinematic(&mut self, handle: BodyHandle, desired_delta: &Vector<N>) -> ActualMove<N> {
+        let mut result = ActualMove::new();
+        let params = self.kinematic_move_params.clone();
+        let start = match self.rigid_body(handle) {
+            Some(rb) => *rb.position(),
+            None => return result,
+        };
+        let sweep = match self.cworld.body_colliders(handle).find(|co| !co.is_sensor()) {
+            Some(co) => KinematicSweep::new(handle, co, start),
+            None => return result,
+        };
+
+        let min_slope_cos = params.max_slope_angle.cos();
+        let mut remaining = *desired_delta;
+
+        for _ in 0..params.max_slides {
+            let length = remaining.norm();
+
+            if length <= N::default_epsilon() {
+                break;
+            }
+
+            let hit = match self.sweep_kinematic(&sweep, &result.translation, &remaining) {
+                Some(hit) => hit,
+                None => {
+                    result.translation += remaining;
+                    break;
+                }
+            };
+
+            // Stop slightly before the obstacle.
+            let dir = remaining / length;
+            let travel = (hit.toi * length - params.offset).max(N::zero());
+            result.translation += dir * travel;
+            remaining = dir * (length - travel);
+            result.hits.push(hit);
+
+            self.push_hit_body(&hit, &remaining, params.push_mass);
+
+            let walkable = hit.normal.dot(&params.up.into_inner()) >= min_slope_cos;
+
+            if walkable {
+                result.grounded = true;
+            } else if let Some((step, rest)) = self.step_up(&sweep, &result.translation, &remaining, &hit, &params) {
+                result.translation += step;
+                result.stepped_up = true;
+                remaining = rest;
+                continue;
+            }
+
+            // Slide along the obstacle, but never climb up a steep surface.
+            let normal_part = hit.normal.dot(&remaining);
+
+            if normal_part < N::zero() {
+                remaining -= *hit.normal * normal_part;
+            }
+
+            if !walkable {
+                let climb = params.up.dot(&remaining);
+
+                if climb > N::zero() {
+                    remaining -= *params.up * climb;
+                }
+            }
+        }
+
+        if result.translation != Vector::zeros() {
+            let translation = Translation::from(start.translation.vector + result.translation);
+            self.move_rigid_body(handle, Isometry::from_parts(translation, start.rotation));
+        }
+
+        result
+    }
+
+    // The first obstacle hit by the swept shape translated by `translation` and then moved by `delta`.
+    fn sweep_kinematic(&self, sweep: &KinematicSweep<N>, translation: &Vector<N>, delta: &Vector<N>) -> Option<ShapeCastHit<N>> {
+        let pos = sweep.position(translation);
+        self.cast_shape(&*sweep.shape, &pos, delta, N::one(), &sweep.groups, Some(sweep.body))
+    }
+
+    // Applies to the dynamic body hit by a kinematic move an impulse proportional to the blocked part of the move.
+    fn push_hit_body(&mut self, hit: &ShapeCastHit<N>, remaining: &Vector<N>, push_mass: N) {
+        let blocked = -hit.normal.dot(remaining);
+
+        if blocked <= N::zero() {
+            return;
+        }
+
+        let part = try_ret!(self.cworld.collider(hit.collider)).body_part(0);
+        let body = try_ret!(self.bodies.body_mut(part.0));
+
+        if body.is_dynamic() {
+            let impulse = *hit.normal * (-blocked * push_mass / self.params.dt);
+            body.apply_force_at_point(part.1, &impulse, &hit.point, ForceType::Impulse, true);
+        }
+    }
+
+    // Attempts to move up, forward along the horizontal part of `remaining`, and back down to step over
+    // the obstacle of `hit`. Returns the resulting displacement and the part of `remaining` left to move.
+    fn step_up(
+        &self,
+        sweep: &KinematicSweep<N>,
+        translation: &Vector<N>,
+        remaining: &Vector<N>,
+        hit: &ShapeCastHit<N>,
+        params: &KinematicMoveParameters<N>,
+    ) -> Option<(Vector<N>, Vector<N>)> {
+        if params.max_step_height <= N::zero() {
+            return None;
+        }
+
+        let up = params.up.into_inner();
+
+        // The obstacle must be lower than the maximum step height above the bottom of the shape.
+        let aabb = sweep.shape.aabb(&sweep.position(translation));
+        let bottom = aabb.center().coords.dot(&up) - aabb.half_extents().dot(&up.abs());
+
+        if hit.point.coords.dot(&up) - bottom > params.max_step_height {
+            return None;
+        }
+
+        let horizontal = remaining - up * remaining.dot(&up);
+        let horizontal_length = horizontal.norm();
+
+        if horizontal_length <= params.offset {
+            return None;
+        }
+
+        // The distance the shape can travel along `dir`, up to `length`, and the obstacle it hits.
+        let travel = |from: &Vector<N>, dir: &Vector<N>, length: N| {
+            match self.sweep_kinematic(sweep, from, &(dir * length)) {
+                Some(hit) => ((hit.toi * length - params.offset).max(N::zero()), Some(hit)),
+                None => (length, None),
+            }
+        };
+
+        let (rise, _) = travel(translation, &up, params.max_step_height);
+        let raised = translation + up * rise;
+
+        let forward = horizontal / horizontal_length;
+        let (advance, _) = travel(&raised, &forward, horizontal_length);
+
+        if advance <= params.offset {
+            return None;
+        }
+
+        let advanced = raised + forward * advance;
+        let (descent, landing) = travel(&advanced, &-up, rise);
+
+        // Do not step onto a surface too steep to walk on.
+        if let Some(landing) = landing {
+            if landing.normal.dot(&up) < params.max_slope_angle.cos() {
+                return None;
+            }
+        }
+
+        let step = advanced - up * descent - translation;
+        Some((step, forward * (horizontal_length - advance)))
+    }
+
+    /// Applies an impulse pushing away from `center` every dynamic body closer than `radius`.
+    ///
+    /// Each body part receives a single impulse, applied at the point of its colliders closest to
+    /// `center` so that it also makes the body spin. Its norm is `strength` multiplied by the factor
+    /// of `falloff` at the distance between this point and `center`. Sensors, as well as static and
+    /// kinematic bodies, are ignored.
+    pub fn apply_radial_impulse(&mut self, center: &Point<N>, radius: N, strength: N, falloff: RadialFalloff) {
+        let aabb = AABB::new(center - Vector::repeat(radius), center + Vector::repeat(radius));
+        let groups = CollisionGroups::new();
+        let mut closest: Vec<(BodyPartHandle, N, Point<N>)> = Vec::new();
+
+        for co in self.cworld.interferences_with_aabb(&aabb, &groups) {
+            let part = match co.anchor() {
+                ColliderAnchor::OnBodyPart { body_part, .. } => *body_part,
+                _ => continue,
+            };
+
+            if co.is_sensor() || !self.bodies.body(part.0).map(|b| b.is_dynamic()).unwrap_or(false) {
+                continue;
+            }
+
+            let point_query = try_continue!(co.shape().as_point_query());
+            let point = point_query.project_point(co.position(), center, true).point;
+            let distance = na::distance(&point, center);
+
+            if distance >= radius {
+                continue;
+            }
+
+            match closest.iter_mut().find(|c| c.0 == part) {
+                Some(c) => {
+                    if distance < c.1 {
+                        *c = (part, distance, point)
+                    }
+                }
+                None => closest.push((part, distance, point)),
+            }
+        }
+
+        for (part, distance, point) in closest {
+            let body = try_continue!(self.bodies.body_mut(part.0));
+
+            // If the center is inside of the body, push it away from its center of mass instead.
+            let dir = match (point - center).try_normalize(N::default_epsilon()) {
+                Some(dir) => dir,
+                None => {
+                    let com = try_continue!(body.part(part.1)).center_of_mass();
+                    try_continue!((com - center).try_normalize(N::default_epsilon()))
+                }
+            };
+
+            let impulse = dir * (strength * falloff.factor(distance, radius));
+            body.apply_force_at_point(part.1, &impulse, &point, ForceType::Impulse, true);
+        }
+    }
+
+    /// The islands solved independently during the last execution of `self.step()`.
+    ///
+    /// Each island is a group of active dynamic bodies connected by contacts or joints. Static and
+    /// kinematic bodies do not connect the bodies they touch into a single island.
+    pub fn islands(&self) -> &[Island] {
+        self.islands.islands()
+    }
+
     /// An iterator through all the contact events generated during the last execution of `self.step()`.
     pub fn contact_events(&self) -> &ContactEvents {
         self.cworld.contact_events()
     }
 
+    /// Set the listener notified at the end of each `self.step()` of all the colliders that started or stopped being in contact.
+    ///
+    /// The same listener is notified of sensors starting or stopping to intersect other colliders.
+    ///
+    /// This replaces the previous contact handler, if any.
+    pub fn set_contact_handler(&mut self, handler: Box<ContactHandler<N>>) {
+        self.contact_handler = Some(handler);
+    }
+
+    /// Remove the contact handler of this world, if any.
+    pub fn remove_contact_handler(&mut self) -> Option<Box<ContactHandler<N>>> {
+        self.contact_handler.take()
+    }
+
+    /// Set the listener notified at the end of each `self.step()` of all the dynamic bodies that fell asleep or woke up.
+    ///
+    /// This replaces the previous activation handler, if any.
+    pub fn set_activation_handler(&mut self, handler: Box<ActivationHandler<N>>) {
+        self.activation_handler = Some(handler);
+    }
+
+    /// Remove the activation handler of this world, if any.
+    pub fn remove_activation_handler(&mut self) -> Option<Box<ActivationHandler<N>>> {
+        self.activation_handler.take()
+    }
+
     /// An iterator through all the proximity events generated during the last execution of `self.step()`.
     pub fn proximity_events(&self) -> &ProximityEvents {
         self.cworld.proximity_events()
@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt;
+
+use na::Real;
+
+use crate::material::{BasicMaterial, MaterialHandle};
+use crate::math::Vector;
+use crate::solver::IntegrationParameters;
+use crate::world::World;
+
+/// An error preventing a `WorldDesc` from being built.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WorldDescError {
+    /// The timestep is not strictly positive.
+    NonPositiveTimestep,
+    /// The velocity solver performs no iteration.
+    ZeroVelocityIterations,
+    /// The number of substeps is zero.
+    ZeroSubsteps,
+    /// The friction coefficient of the default material is negative.
+    NegativeFriction,
+    /// The restitution coefficient of the default material is not in `[0, 1]`.
+    InvalidRestitution,
+    /// The margin of the bounding volumes of the broad phase is negative.
+    NegativeBroadPhaseMargin,
+}
+
+impl fmt::Display for WorldDescError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WorldDescError::NonPositiveTimestep => write!(f, "the timestep must be positive"),
+            WorldDescError::ZeroVelocityIterations => write!(f, "the velocity solver must perform at least one iteration"),
+            WorldDescError::ZeroSubsteps => write!(f, "the number of substeps must be at least 1"),
+            WorldDescError::NegativeFriction => write!(f, "the default friction coefficient must not be negative"),
+            WorldDescError::InvalidRestitution => write!(f, "the default restitution coefficient must be between 0 and 1"),
+            WorldDescError::NegativeBroadPhaseMargin => write!(f, "the broad phase margin must not be negative"),
+        }
+    }
+}
+
+impl Error for WorldDescError {}
+
+/// The description of a physics world, used to build a new `World`.
+///
+/// The default values are the same as the ones of `World::new()`.
+pub struct WorldDesc<N: Real> {
+    gravity: Vector<N>,
+    timestep: N,
+    velocity_iterations: usize,
+    position_iterations: usize,
+    substeps: usize,
+    friction: N,
+    restitution: N,
+    broad_phase_margin: N,
+}
+
+impl<N: Real> WorldDesc<N> {
+    /// A default world builder.
+    pub fn new() -> Self {
+        let params = IntegrationParameters::default();
+        let material = BasicMaterial::default();
+
+        WorldDesc {
+            gravity: Vector::zeros(),
+            timestep: params.dt,
+            velocity_iterations: params.max_velocity_iterations,
+            position_iterations: params.max_position_iterations,
+            substeps: 1,
+            friction: material.friction,
+            restitution: material.restitution,
+            broad_phase_margin: World::default_broad_phase_margin(),
+        }
+    }
+
+    desc_setters!(
+        gravity, set_gravity, gravity: Vector<N>
+        timestep, set_timestep, timestep: N
+        velocity_iterations, set_velocity_iterations, velocity_iterations: usize
+        position_iterations, set_position_iterations, position_iterations: usize
+        substeps, set_substeps, substeps: usize
+        friction, set_friction, friction: N
+        restitution, set_restitution, restitution: N
+        broad_phase_margin, set_broad_phase_margin, broad_phase_margin: N
+    );
+
+    desc_getters!(
+        [val] get_timestep -> timestep: N
+        [val] get_velocity_iterations -> velocity_iterations: usize
+        [val] get_position_iterations -> position_iterations: usize
+        [val] get_substeps -> substeps: usize
+        [val] get_friction -> friction: N
+        [val] get_restitution -> restitution: N
+        [val] get_broad_phase_margin -> broad_phase_margin: N
+        [ref] get_gravity -> gravity: Vector<N>
+    );
+
+    /// Checks that this description results in a world that can be simulated.
+    pub fn validate(&self) -> Result<(), WorldDescError> {
+        if self.timestep <= N::zero() {
+            return Err(WorldDescError::NonPositiveTimestep);
+        }
+
+        if self.velocity_iterations == 0 {
+            return Err(WorldDescError::ZeroVelocityIterations);
+        }
+
+        if self.substeps == 0 {
+            return Err(WorldDescError::ZeroSubsteps);
+        }
+
+        if self.friction < N::zero() {
+            return Err(WorldDescError::NegativeFriction);
+        }
+
+        if self.restitution < N::zero() || self.restitution > N::one() {
+            return Err(WorldDescError::InvalidRestitution);
+        }
+
+        if self.broad_phase_margin < N::zero() {
+            return Err(WorldDescError::NegativeBroadPhaseMargin);
+        }
+
+        Ok(())
+    }
+
+    /// Builds a world if `self.validate()` succeeds.
+    pub fn try_build(&self) -> Result<World<N>, WorldDescError> {
+        self.validate()?;
+
+        let mut world = World::with_broad_phase_margin(self.broad_phase_margin);
+        world.set_gravity(self.gravity);
+        world.set_timestep(self.timestep);
+        world.set_velocity_iterations(self.velocity_iterations);
+        world.set_position_iterations(self.position_iterations);
+        world.set_substeps(self.substeps);
+
+        let material = BasicMaterial::new(self.restitution, self.friction);
+        world.collider_world_mut().set_default_material(MaterialHandle::new(material));
+
+        Ok(world)
+    }
+
+    /// Builds a world.
+    ///
+    /// Panics if `self.validate()` fails.
+    pub fn build(&self) -> World<N> {
+        self.try_build().unwrap_or_else(|e| panic!("Invalid world description: {}", e))
+    }
+}
+
+impl<N: Real> Default for WorldDesc<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
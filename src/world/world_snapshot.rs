@@ -0,0 +1,42 @@
+use na::Real;
+
+use crate::object::{BodyHandle, RigidBodyState};
+
+/// A snapshot of the state of the rigid bodies of a `World`.
+///
+/// It is created by `World::snapshot` and can be used to restore the state of the
+/// world later with `World::restore`. It is cheap to clone so that several snapshots
+/// can be kept, e.g., to rewind the simulation.
+///
+/// With the `serde-serialize` feature enabled, snapshots can be serialized. The `version` field
+/// identifies the layout of the serialized data so that files written by older versions can be detected.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct WorldSnapshot<N: Real> {
+    pub(crate) version: u32,
+    pub(crate) t: N,
+    pub(crate) accumulator: N,
+    pub(crate) rigid_bodies: Vec<(BodyHandle, RigidBodyState<N>)>,
+}
+
+impl<N: Real> WorldSnapshot<N> {
+    /// The current version of the layout of world snapshots.
+    pub const VERSION: u32 = 2;
+
+    /// The version of the layout of this snapshot.
+    ///
+    /// This is `WorldSnapshot::VERSION` for snapshots created by this version of nphysics.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The simulation time at which this snapshot was taken.
+    pub fn time(&self) -> N {
+        self.t
+    }
+
+    /// The number of rigid bodies whose state is captured by this snapshot.
+    pub fn num_rigid_bodies(&self) -> usize {
+        self.rigid_bodies.len()
+    }
+}
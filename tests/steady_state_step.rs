@@ -0,0 +1,86 @@
+//! Checks that stepping a world that reached a steady state does not allocate.
+//!
+//! This lives in its own test binary because it replaces the global allocator.
+
+#[cfg(feature = "dim2")]
+extern crate ncollide2d as ncollide;
+#[cfg(feature = "dim3")]
+extern crate ncollide3d as ncollide;
+#[cfg(feature = "dim2")]
+extern crate nphysics2d as nphysics;
+#[cfg(feature = "dim3")]
+extern crate nphysics3d as nphysics;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ncollide::shape::{Cuboid, ShapeHandle};
+use nphysics::math::Vector;
+use nphysics::object::{ColliderDesc, RigidBodyDesc};
+use nphysics::world::World;
+
+// Counts the allocations performed by the current thread so that tests running in parallel
+// do not interfere with each other.
+struct CountingAllocator;
+
+thread_local!(static NALLOCS: Cell<usize> = Cell::new(0));
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = NALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = NALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn steady_state_step_does_not_allocate() {
+    let mut world = World::<f32>::new();
+    let mut gravity = Vector::zeros();
+    gravity[1] = -9.81;
+    world.set_gravity(gravity);
+
+    let ground_size = 2000.0;
+    ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(ground_size))))
+        .translation(Vector::y() * -ground_size)
+        .build(&mut world);
+
+    let rad = 0.5;
+    let collider_desc = ColliderDesc::new(ShapeHandle::new(Cuboid::new(Vector::repeat(rad))))
+        .density(1.0);
+    let shift = (rad + collider_desc.get_margin()) * 2.0;
+    // Keep the bodies awake so that the solver runs at every step. They lie side by side on the
+    // ground so that they come to rest quickly: the broad phase of ncollide allocates whenever
+    // a collider leaves its enlarged bounding volume.
+    let mut rb_desc = RigidBodyDesc::new().collider(&collider_desc).sleep_threshold(None);
+
+    for i in 0..1000 {
+        let x = (i as f32 - 500.0) * shift * 2.0;
+        let pos = Vector::x() * x + Vector::y() * shift / 2.0;
+        let _ = rb_desc.set_translation(pos).build(&mut world);
+    }
+
+    for _ in 0..100 {
+        world.step();
+    }
+
+    let before = NALLOCS.with(|n| n.get());
+
+    for _ in 0..10 {
+        world.step();
+    }
+
+    let nallocs = NALLOCS.with(|n| n.get()) - before;
+    assert_eq!(nallocs, 0, "{} allocations were performed by steady-state steps.", nallocs);
+}